@@ -1,5 +1,7 @@
 //! Actor Factory error types
 
+use std::path::PathBuf;
+
 use contracts::ContractError;
 use thiserror::Error;
 
@@ -62,5 +64,28 @@ impl ActorFactoryError {
     }
 }
 
+/// Recording cannot describe, or disagrees with, a blueprint
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    /// Recording files could not be read
+    #[error("failed to read recording {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// No sensor in the recording can be replayed
+    #[error("recording {0} has no replayable sensors")]
+    Empty(PathBuf),
+
+    /// Configuration and recording disagree
+    #[error("configuration does not match recording {}:\n  - {}", .path.display(), .problems.join("\n  - "))]
+    Mismatch {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+}
+
 /// Result alias
 pub type Result<T> = std::result::Result<T, ActorFactoryError>;
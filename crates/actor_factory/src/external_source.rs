@@ -0,0 +1,524 @@
+//! External-process sensor source
+//!
+//! Receives `SensorPacket`s from a foreign producer (custom models,
+//! hardware-in-the-loop stubs) over a TCP port or Unix domain socket.
+//!
+//! Wire format: each packet is one frame, a big-endian `u32` byte length
+//! followed by the packet encoded as JSON or bincode. Producers may
+//! disconnect and reconnect at any time; every connection is read on its
+//! own thread and reports its own metrics.
+
+use std::io::{self, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use contracts::{
+    ExternalEndpoint, PacketEncoding, SensorDataCallback, SensorPacket, SensorSource, SensorType,
+};
+use tracing::{debug, info, warn};
+
+/// Accept/read poll interval, bounds how long `stop` takes to be observed
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Largest accepted frame; larger lengths mean a corrupt stream
+const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// Frame length prefix size
+const LEN_PREFIX: usize = 4;
+
+/// Encode a packet as one length-prefixed frame
+pub fn encode_frame(packet: &SensorPacket, encoding: PacketEncoding) -> Result<Vec<u8>, String> {
+    let body = match encoding {
+        PacketEncoding::Json => {
+            serde_json::to_vec(packet).map_err(|e| format!("json error: {}", e))?
+        }
+        PacketEncoding::Bincode => {
+            bincode::serialize(packet).map_err(|e| format!("bincode error: {}", e))?
+        }
+    };
+    let len = u32::try_from(body.len()).map_err(|_| "packet too large".to_string())?;
+    let mut frame = Vec::with_capacity(LEN_PREFIX + body.len());
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&body);
+    Ok(frame)
+}
+
+fn decode_packet(body: &[u8], encoding: PacketEncoding) -> Result<SensorPacket, String> {
+    match encoding {
+        PacketEncoding::Json => {
+            serde_json::from_slice(body).map_err(|e| format!("json error: {}", e))
+        }
+        PacketEncoding::Bincode => {
+            bincode::deserialize(body).map_err(|e| format!("bincode error: {}", e))
+        }
+    }
+}
+
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Accept one connection without blocking
+    fn accept(&self) -> io::Result<(Connection, String)> {
+        match self {
+            Listener::Tcp(listener) => {
+                let (stream, peer) = listener.accept()?;
+                Ok((Connection::Tcp(stream), peer.to_string()))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let (stream, _) = listener.accept()?;
+                Ok((Connection::Unix(stream), "unix".to_string()))
+            }
+        }
+    }
+}
+
+enum Connection {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Connection {
+    /// Switch to blocking reads that time out so `stop` is noticed
+    fn configure(&self) -> io::Result<()> {
+        match self {
+            Connection::Tcp(stream) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(POLL_INTERVAL))
+            }
+            #[cfg(unix)]
+            Connection::Unix(stream) => {
+                stream.set_nonblocking(false)?;
+                stream.set_read_timeout(Some(POLL_INTERVAL))
+            }
+        }
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(stream) => stream.read(buf),
+            #[cfg(unix)]
+            Connection::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+/// Sensor source fed by an external process over a local socket.
+///
+/// The socket is bound on construction so configuration errors surface
+/// before the pipeline starts.
+pub struct ExternalSensorSource {
+    sensor_id: String,
+    sensor_type: SensorType,
+    endpoint: ExternalEndpoint,
+    encoding: PacketEncoding,
+    listener: Arc<Listener>,
+    listening: Arc<AtomicBool>,
+}
+
+impl ExternalSensorSource {
+    /// Bind `endpoint`, replacing a stale Unix socket file if present.
+    /// Any other file at a Unix socket path is left alone and an error.
+    pub fn bind(
+        sensor_id: String,
+        sensor_type: SensorType,
+        endpoint: ExternalEndpoint,
+        encoding: PacketEncoding,
+    ) -> io::Result<Self> {
+        let listener = match &endpoint {
+            ExternalEndpoint::Tcp(addr) => Listener::Tcp(TcpListener::bind(addr.as_str())?),
+            #[cfg(unix)]
+            ExternalEndpoint::Unix(path) => {
+                remove_stale_socket(path)?;
+                Listener::Unix(UnixListener::bind(path)?)
+            }
+            #[cfg(not(unix))]
+            ExternalEndpoint::Unix(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "unix sockets are not supported on this platform",
+                ))
+            }
+        };
+        match &listener {
+            Listener::Tcp(l) => l.set_nonblocking(true)?,
+            #[cfg(unix)]
+            Listener::Unix(l) => l.set_nonblocking(true)?,
+        }
+
+        info!(sensor_id = %sensor_id, endpoint = %endpoint, ?encoding, "external sensor endpoint bound");
+        Ok(Self {
+            sensor_id,
+            sensor_type,
+            endpoint,
+            encoding,
+            listener: Arc::new(listener),
+            listening: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Bound TCP address (useful when binding port 0)
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match self.listener.as_ref() {
+            Listener::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Listener::Unix(_) => None,
+        }
+    }
+}
+
+impl SensorSource for ExternalSensorSource {
+    fn sensor_id(&self) -> &str {
+        &self.sensor_id
+    }
+
+    fn sensor_type(&self) -> SensorType {
+        self.sensor_type
+    }
+
+    fn listen(&self, callback: SensorDataCallback) {
+        if self.listening.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let listener = self.listener.clone();
+        let reader = ConnectionReader {
+            sensor_id: self.sensor_id.clone(),
+            sensor_type: self.sensor_type,
+            encoding: self.encoding,
+            listening: self.listening.clone(),
+            callback,
+        };
+
+        thread::spawn(move || {
+            debug!(sensor_id = %reader.sensor_id, "waiting for external producers");
+            while reader.listening.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((connection, peer)) => {
+                        let reader = reader.clone();
+                        thread::spawn(move || reader.run(connection, peer));
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(POLL_INTERVAL);
+                    }
+                    Err(e) => {
+                        warn!(sensor_id = %reader.sensor_id, error = %e, "accept failed");
+                        thread::sleep(POLL_INTERVAL);
+                    }
+                }
+            }
+        });
+    }
+
+    fn stop(&self) {
+        self.listening.store(false, Ordering::SeqCst);
+    }
+
+    fn is_listening(&self) -> bool {
+        self.listening.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for ExternalSensorSource {
+    fn drop(&mut self) {
+        self.stop();
+        if let ExternalEndpoint::Unix(path) = &self.endpoint {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Per-connection frame reader
+#[derive(Clone)]
+struct ConnectionReader {
+    sensor_id: String,
+    sensor_type: SensorType,
+    encoding: PacketEncoding,
+    listening: Arc<AtomicBool>,
+    callback: SensorDataCallback,
+}
+
+impl ConnectionReader {
+    fn run(&self, mut connection: Connection, peer: String) {
+        let labels = [
+            ("sensor_id", self.sensor_id.clone()),
+            ("peer", peer.clone()),
+        ];
+        let packets = metrics::counter!("external_source_packets_total", &labels);
+        let bytes = metrics::counter!("external_source_bytes_total", &labels);
+        let errors = metrics::counter!("external_source_decode_errors_total", &labels);
+        let active =
+            metrics::gauge!("external_source_connections", "sensor_id" => self.sensor_id.clone());
+        metrics::counter!("external_source_connects_total", "sensor_id" => self.sensor_id.clone())
+            .increment(1);
+        active.increment(1.0);
+        info!(sensor_id = %self.sensor_id, peer = %peer, "external producer connected");
+
+        let (mut received, mut rejected, mut total_bytes) = (0u64, 0u64, 0usize);
+        let mut buffer = Vec::new();
+        let mut chunk = vec![0u8; 64 * 1024];
+
+        let reason = if let Err(e) = connection.configure() {
+            format!("configure failed: {}", e)
+        } else {
+            loop {
+                if !self.listening.load(Ordering::Relaxed) {
+                    break "stopped".to_string();
+                }
+                let n = match connection.read(&mut chunk) {
+                    Ok(0) => break "closed by peer".to_string(),
+                    Ok(n) => n,
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            io::ErrorKind::WouldBlock
+                                | io::ErrorKind::TimedOut
+                                | io::ErrorKind::Interrupted
+                        ) =>
+                    {
+                        continue
+                    }
+                    Err(e) => break format!("read failed: {}", e),
+                };
+                total_bytes += n;
+                bytes.increment(n as u64);
+                buffer.extend_from_slice(&chunk[..n]);
+
+                match self.drain_frames(&mut buffer) {
+                    Ok((ok, bad)) => {
+                        received += ok;
+                        rejected += bad;
+                        packets.increment(ok);
+                        errors.increment(bad);
+                    }
+                    Err(e) => break e,
+                }
+            }
+        };
+
+        active.decrement(1.0);
+        info!(
+            sensor_id = %self.sensor_id,
+            peer = %peer,
+            packets = received,
+            rejected,
+            bytes = total_bytes,
+            reason = %reason,
+            "external producer disconnected"
+        );
+    }
+
+    /// Deliver every complete frame in `buffer`.
+    ///
+    /// Returns (delivered, rejected) counts, or an error if the stream is
+    /// corrupt and the connection should be dropped.
+    fn drain_frames(&self, buffer: &mut Vec<u8>) -> Result<(u64, u64), String> {
+        let (mut delivered, mut rejected) = (0, 0);
+        let mut offset = 0;
+
+        while buffer.len() - offset >= LEN_PREFIX {
+            let prefix: [u8; LEN_PREFIX] = buffer[offset..offset + LEN_PREFIX]
+                .try_into()
+                .unwrap_or_default();
+            let len = u32::from_be_bytes(prefix) as usize;
+            if len > MAX_FRAME_BYTES {
+                return Err(format!("frame of {} bytes exceeds limit", len));
+            }
+            let end = offset + LEN_PREFIX + len;
+            if buffer.len() < end {
+                break;
+            }
+
+            match decode_packet(&buffer[offset + LEN_PREFIX..end], self.encoding) {
+                Ok(packet) if packet.sensor_type != self.sensor_type => {
+                    rejected += 1;
+                    warn!(
+                        sensor_id = %self.sensor_id,
+                        expected = ?self.sensor_type,
+                        got = ?packet.sensor_type,
+                        "external packet has wrong sensor type"
+                    );
+                }
+                Ok(mut packet) => {
+                    packet.sensor_id = self.sensor_id.as_str().into();
+                    (self.callback)(packet);
+                    delivered += 1;
+                }
+                Err(e) => {
+                    rejected += 1;
+                    warn!(sensor_id = %self.sensor_id, error = %e, "undecodable external packet");
+                }
+            }
+            offset = end;
+        }
+
+        buffer.drain(..offset);
+        Ok((delivered, rejected))
+    }
+}
+
+/// Remove a socket file left behind by an earlier run
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{ImuData, SensorPayload, SimTime, Vector3};
+    use std::io::Write;
+    use std::sync::Mutex;
+
+    fn imu_packet(frame_id: u64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "producer_side".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(frame_id as f64 * 0.01),
+            frame_id: Some(frame_id),
+            payload: SensorPayload::Imu(ImuData {
+                accelerometer: Vector3::default(),
+                gyroscope: Vector3::default(),
+                compass: 0.0,
+            }),
+        }
+    }
+
+    fn collect(source: &ExternalSensorSource) -> Arc<Mutex<Vec<SensorPacket>>> {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        source.listen(Arc::new(move |p| sink.lock().unwrap().push(p)));
+        received
+    }
+
+    fn wait_for(received: &Mutex<Vec<SensorPacket>>, count: usize) {
+        for _ in 0..100 {
+            if received.lock().unwrap().len() >= count {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_tcp_reconnect_and_bad_frames() {
+        let source = ExternalSensorSource::bind(
+            "ext_imu".into(),
+            SensorType::Imu,
+            ExternalEndpoint::Tcp("127.0.0.1:0".into()),
+            PacketEncoding::Json,
+        )
+        .unwrap();
+        let addr = source.local_addr().unwrap();
+        let received = collect(&source);
+
+        let mut first = TcpStream::connect(addr).unwrap();
+        let frame = encode_frame(&imu_packet(1), PacketEncoding::Json).unwrap();
+        // Split a frame across writes, then send an undecodable frame
+        first.write_all(&frame[..3]).unwrap();
+        first.flush().unwrap();
+        thread::sleep(Duration::from_millis(20));
+        first.write_all(&frame[3..]).unwrap();
+        first.write_all(&[0, 0, 0, 2, b'{', b'x']).unwrap();
+        drop(first);
+
+        let mut second = TcpStream::connect(addr).unwrap();
+        second
+            .write_all(&encode_frame(&imu_packet(2), PacketEncoding::Json).unwrap())
+            .unwrap();
+        wait_for(&received, 2);
+        source.stop();
+
+        let received = received.lock().unwrap();
+        let mut frames: Vec<_> = received.iter().map(|p| p.frame_id.unwrap()).collect();
+        frames.sort_unstable();
+        assert_eq!(frames, vec![1, 2]);
+        assert!(received.iter().all(|p| &*p.sensor_id == "ext_imu"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_socket_bincode() {
+        let path = std::env::temp_dir().join(format!("ext_sensor_{}.sock", std::process::id()));
+        let source = ExternalSensorSource::bind(
+            "ext_imu".into(),
+            SensorType::Imu,
+            ExternalEndpoint::Unix(path.clone()),
+            PacketEncoding::Bincode,
+        )
+        .unwrap();
+        let received = collect(&source);
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        let mut wrong_type = imu_packet(3);
+        wrong_type.sensor_type = SensorType::Gnss;
+        for packet in [imu_packet(1), wrong_type, imu_packet(2)] {
+            stream
+                .write_all(&encode_frame(&packet, PacketEncoding::Bincode).unwrap())
+                .unwrap();
+        }
+        wait_for(&received, 2);
+
+        let frames: Vec<_> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| p.frame_id.unwrap())
+            .collect();
+        assert_eq!(frames, vec![1, 2]);
+        drop(source);
+        assert!(!path.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_bind_keeps_other_files() {
+        let path = std::env::temp_dir().join(format!("ext_sensor_{}.txt", std::process::id()));
+        std::fs::write(&path, b"keep").unwrap();
+        let bind = |path: &std::path::Path| {
+            ExternalSensorSource::bind(
+                "ext_imu".into(),
+                SensorType::Imu,
+                ExternalEndpoint::Unix(path.to_path_buf()),
+                PacketEncoding::Bincode,
+            )
+        };
+
+        let err = bind(&path)
+            .err()
+            .expect("regular file must not be replaced");
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read(&path).unwrap(), b"keep");
+        std::fs::remove_file(&path).unwrap();
+
+        // A stale socket from an earlier run is replaced
+        let socket =
+            std::env::temp_dir().join(format!("ext_sensor_stale_{}.sock", std::process::id()));
+        drop(UnixListener::bind(&socket).unwrap());
+        assert!(socket.exists());
+        drop(bind(&socket).unwrap());
+        assert!(!socket.exists());
+    }
+}
@@ -1,59 +1,142 @@
 //! ActorFactory core implementation
 //!
 //! Spawns actors from WorldBlueprint, manages lifecycle.
+//!
+//! Vehicles are spawned first, then every sensor of every vehicle, each phase
+//! running at most `max_concurrent_spawns` requests at once. Results are
+//! collected in blueprint order, so the RuntimeGraph and the reported error do
+//! not depend on which request completes first.
+
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use contracts::{ActorId, RuntimeGraph, SensorConfig, SensorType, VehicleConfig, WorldBlueprint};
+use futures::stream::{self, StreamExt};
 use tracing::{error, info, instrument, warn};
 
 use crate::client::CarlaClient;
 use crate::error::{ActorFactoryError, Result};
 
+/// Default number of spawn requests in flight at once
+pub const DEFAULT_MAX_CONCURRENT_SPAWNS: usize = 8;
+
 /// Actor Factory
 ///
 /// Responsible for spawning vehicles and sensors from WorldBlueprint,
 /// and providing teardown and rollback capabilities.
 pub struct ActorFactory<C: CarlaClient> {
     client: C,
+    max_concurrent_spawns: usize,
 }
 
 impl<C: CarlaClient> ActorFactory<C> {
     /// Create new ActorFactory
     pub fn new(client: C) -> Self {
-        Self { client }
+        Self {
+            client,
+            max_concurrent_spawns: DEFAULT_MAX_CONCURRENT_SPAWNS,
+        }
+    }
+
+    /// Limit the number of spawn/destroy requests in flight (1 = sequential)
+    pub fn with_max_concurrent_spawns(mut self, max_concurrent_spawns: usize) -> Self {
+        self.max_concurrent_spawns = max_concurrent_spawns.max(1);
+        self
     }
 
     /// Spawn all actors from WorldBlueprint
     ///
     /// # Atomicity Guarantee
-    /// If any spawn fails, all created actors will be rolled back and destroyed.
+    /// If any spawn fails, no new request is started, in-flight requests are
+    /// awaited, and all created actors are rolled back and destroyed.
     #[instrument(
         name = "actor_factory_spawn_blueprint",
         skip(self, blueprint),
-        fields(vehicle_count = blueprint.vehicles.len())
+        fields(vehicle_count = blueprint.vehicles.len(), max_concurrent = self.max_concurrent_spawns)
     )]
     pub async fn spawn_from_blueprint(&self, blueprint: &WorldBlueprint) -> Result<RuntimeGraph> {
-        let mut graph = RuntimeGraph::new();
+        let failed = AtomicBool::new(false);
+
+        // Phase 1: vehicles
+        let vehicle_results: Vec<Option<Result<ActorId>>> = stream::iter(&blueprint.vehicles)
+            .map(|vehicle_config| self.guarded(&failed, self.spawn_vehicle_actor(vehicle_config)))
+            .buffered(self.max_concurrent_spawns)
+            .collect()
+            .await;
+
         let mut created_vehicles: Vec<(String, ActorId)> = Vec::new();
-        let mut created_sensors: Vec<(String, ActorId)> = Vec::new();
+        let mut first_error = None;
+        for (vehicle_config, result) in blueprint.vehicles.iter().zip(vehicle_results) {
+            match result {
+                Some(Ok(actor_id)) => created_vehicles.push((vehicle_config.id.clone(), actor_id)),
+                Some(Err(e)) => {
+                    first_error.get_or_insert(e);
+                }
+                None => {}
+            }
+        }
+        if let Some(e) = first_error {
+            warn!(error = %e, "vehicle spawn failed, rolling back all actors");
+            self.rollback(&[], &created_vehicles).await;
+            return Err(e);
+        }
 
-        for vehicle_config in &blueprint.vehicles {
-            match self
-                .spawn_vehicle_with_sensors(vehicle_config, &mut graph)
-                .await
-            {
-                Ok((vehicle_actor_id, sensor_ids)) => {
-                    created_vehicles.push((vehicle_config.id.clone(), vehicle_actor_id));
-                    created_sensors.extend(sensor_ids);
+        // Phase 2: sensors of all vehicles (external sensors need no actor)
+        let pending: Vec<(&VehicleConfig, ActorId, &SensorConfig)> = blueprint
+            .vehicles
+            .iter()
+            .zip(&created_vehicles)
+            .flat_map(|(vehicle_config, (_, vehicle_actor_id))| {
+                vehicle_config
+                    .sensors
+                    .iter()
+                    .filter(|sensor_config| !sensor_config.source.is_external())
+                    .map(move |sensor_config| (vehicle_config, *vehicle_actor_id, sensor_config))
+            })
+            .collect();
+
+        let sensor_results: Vec<Option<Result<ActorId>>> = stream::iter(&pending)
+            .map(|&(vehicle_config, vehicle_actor_id, sensor_config)| {
+                self.guarded(
+                    &failed,
+                    self.spawn_sensor_actor(vehicle_actor_id, vehicle_config, sensor_config),
+                )
+            })
+            .buffered(self.max_concurrent_spawns)
+            .collect()
+            .await;
+
+        let mut created_sensors: Vec<(String, ActorId)> = Vec::new();
+        for ((_, _, sensor_config), result) in pending.iter().zip(sensor_results) {
+            match result {
+                Some(Ok(actor_id)) => created_sensors.push((sensor_config.id.clone(), actor_id)),
+                Some(Err(e)) => {
+                    first_error.get_or_insert(e);
                 }
-                Err(e) => {
-                    // Rollback all created actors
-                    warn!(
-                        error = %e,
-                        vehicle_id = %vehicle_config.id,
-                        "spawn failed, rolling back all actors"
+                None => {}
+            }
+        }
+        if let Some(e) = first_error {
+            warn!(error = %e, "sensor spawn failed, rolling back all actors");
+            self.rollback(&created_sensors, &created_vehicles).await;
+            return Err(e);
+        }
+
+        // Register in blueprint order
+        let mut graph = RuntimeGraph::new();
+        let mut sensor_actors = created_sensors.iter();
+        for (vehicle_config, (vehicle_id, vehicle_actor_id)) in
+            blueprint.vehicles.iter().zip(&created_vehicles)
+        {
+            graph.register_vehicle(vehicle_id.clone(), *vehicle_actor_id);
+            for sensor_config in &vehicle_config.sensors {
+                if sensor_config.source.is_external() {
+                    graph.register_external_sensor(
+                        sensor_config.id.clone(),
+                        vehicle_config.id.clone(),
                     );
-                    self.rollback(&created_sensors, &created_vehicles).await;
-                    return Err(e);
+                    info!(sensor_id = %sensor_config.id, "external sensor, no actor spawned");
+                } else if let Some((sensor_id, actor_id)) = sensor_actors.next() {
+                    graph.register_sensor(sensor_id.clone(), vehicle_config.id.clone(), *actor_id);
                 }
             }
         }
@@ -67,62 +150,20 @@ impl<C: CarlaClient> ActorFactory<C> {
         Ok(graph)
     }
 
-    /// Spawn single vehicle and all its sensors
-    #[instrument(
-        name = "actor_factory_spawn_vehicle_with_sensors",
-        skip(self, config, graph),
-        fields(vehicle_id = %config.id)
-    )]
-    async fn spawn_vehicle_with_sensors(
+    /// Run a spawn request unless an earlier one failed (None = skipped)
+    async fn guarded(
         &self,
-        config: &VehicleConfig,
-        graph: &mut RuntimeGraph,
-    ) -> Result<(ActorId, Vec<(String, ActorId)>)> {
-        let vehicle_actor_id = self.spawn_vehicle_actor(config).await?;
-        graph.register_vehicle(config.id.clone(), vehicle_actor_id);
-
-        // Spawn sensors
-        let mut sensor_ids = Vec::new();
-
-        for sensor_config in &config.sensors {
-            match self
-                .spawn_sensor_actor(vehicle_actor_id, config, sensor_config)
-                .await
-            {
-                Ok(sensor_actor_id) => {
-                    graph.register_sensor(
-                        sensor_config.id.clone(),
-                        config.id.clone(),
-                        sensor_actor_id,
-                    );
-                    sensor_ids.push((sensor_config.id.clone(), sensor_actor_id));
-
-                    info!(
-                        sensor_id = %sensor_config.id,
-                        actor_id = sensor_actor_id,
-                        "sensor spawned and attached successfully"
-                    );
-                }
-                Err(e) => {
-                    // Rollback all sensors of this vehicle
-                    warn!(
-                        sensor_id = %sensor_config.id,
-                        vehicle_id = %config.id,
-                        error = %e,
-                        "sensor spawn failed, rolling back vehicle sensors"
-                    );
-
-                    for (sid, aid) in &sensor_ids {
-                        self.destroy_actor_safe(*aid, sid).await;
-                    }
-                    self.destroy_actor_safe(vehicle_actor_id, &config.id).await;
-
-                    return Err(e);
-                }
-            }
+        failed: &AtomicBool,
+        spawn: impl std::future::Future<Output = Result<ActorId>>,
+    ) -> Option<Result<ActorId>> {
+        if failed.load(Ordering::SeqCst) {
+            return None;
         }
-
-        Ok((vehicle_actor_id, sensor_ids))
+        let result = spawn.await;
+        if result.is_err() {
+            failed.store(true, Ordering::SeqCst);
+        }
+        Some(result)
     }
 
     /// Destroy all actors in RuntimeGraph
@@ -137,15 +178,17 @@ impl<C: CarlaClient> ActorFactory<C> {
     pub async fn teardown(&self, graph: &RuntimeGraph) -> Result<()> {
         info!("starting teardown");
 
-        // Destroy sensors first
-        for (sensor_id, actor_id) in &graph.sensors {
-            self.destroy_actor_safe(*actor_id, sensor_id).await;
-        }
-
-        // Then destroy vehicles
-        for (vehicle_id, actor_id) in &graph.vehicles {
-            self.destroy_actor_safe(*actor_id, vehicle_id).await;
-        }
+        let sensors: Vec<(String, ActorId)> = graph
+            .sensors
+            .iter()
+            .map(|(id, actor_id)| (id.clone(), *actor_id))
+            .collect();
+        let vehicles: Vec<(String, ActorId)> = graph
+            .vehicles
+            .iter()
+            .map(|(id, actor_id)| (id.clone(), *actor_id))
+            .collect();
+        self.destroy_all(&sensors, &vehicles).await;
 
         info!("teardown completed");
         Ok(())
@@ -159,15 +202,17 @@ impl<C: CarlaClient> ActorFactory<C> {
     )]
     async fn rollback(&self, sensors: &[(String, ActorId)], vehicles: &[(String, ActorId)]) {
         warn!("performing rollback");
+        self.destroy_all(sensors, vehicles).await;
+    }
 
-        // Destroy sensors first
-        for (sensor_id, actor_id) in sensors {
-            self.destroy_actor_safe(*actor_id, sensor_id).await;
-        }
-
-        // Then destroy vehicles
-        for (vehicle_id, actor_id) in vehicles {
-            self.destroy_actor_safe(*actor_id, vehicle_id).await;
+    /// Destroy sensors first, then vehicles, with bounded concurrency
+    async fn destroy_all(&self, sensors: &[(String, ActorId)], vehicles: &[(String, ActorId)]) {
+        for actors in [sensors, vehicles] {
+            stream::iter(actors)
+                .for_each_concurrent(self.max_concurrent_spawns, |(config_id, actor_id)| {
+                    self.destroy_actor_safe(*actor_id, config_id)
+                })
+                .await;
         }
     }
 
@@ -306,6 +351,11 @@ mod tests {
                         },
                         frequency_hz: 30.0,
                         attributes: HashMap::new(),
+                        mock_clock: None,
+                        ingestion: None,
+                        preprocess: Vec::new(),
+                        mock_faults: None,
+                        source: Default::default(),
                     },
                     SensorConfig {
                         id: "lidar".to_string(),
@@ -324,6 +374,11 @@ mod tests {
                         },
                         frequency_hz: 10.0,
                         attributes: HashMap::new(),
+                        mock_clock: None,
+                        ingestion: None,
+                        preprocess: Vec::new(),
+                        mock_faults: None,
+                        source: Default::default(),
                     },
                 ],
             }],
@@ -334,8 +389,11 @@ mod tests {
                 missing_frame_policy: contracts::MissingFramePolicy::Drop,
                 drop_policy: contracts::DropPolicy::DropOldest,
                 engine: SyncEngineOverrides::default(),
+                groups: Vec::new(),
+                cross_vehicle: None,
             },
             sinks: vec![],
+            memory: Default::default(),
         }
     }
 
@@ -418,12 +476,104 @@ mod tests {
                 missing_frame_policy: contracts::MissingFramePolicy::Drop,
                 drop_policy: contracts::DropPolicy::DropOldest,
                 engine: SyncEngineOverrides::default(),
+                groups: Vec::new(),
+                cross_vehicle: None,
             },
             sinks: vec![],
+            memory: Default::default(),
         };
 
         let graph = factory.spawn_from_blueprint(&blueprint).await.unwrap();
         assert!(graph.vehicles.is_empty());
         assert!(graph.sensors.is_empty());
     }
+
+    /// Test blueprint with `count` copies of the ego vehicle
+    fn create_fleet_blueprint(count: usize) -> WorldBlueprint {
+        let mut blueprint = create_test_blueprint();
+        let template = blueprint.vehicles.remove(0);
+        blueprint.vehicles = (0..count)
+            .map(|i| {
+                let mut vehicle = template.clone();
+                vehicle.id = format!("vehicle_{i}");
+                for sensor in &mut vehicle.sensors {
+                    sensor.id = format!("{}_{i}", sensor.id);
+                }
+                vehicle
+            })
+            .collect();
+        blueprint
+    }
+
+    fn latency_config() -> MockConfig {
+        MockConfig {
+            spawn_latency: std::time::Duration::from_millis(5),
+            spawn_jitter: std::time::Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_spawn_is_bounded() {
+        let mut client = MockCarlaClient::with_config(latency_config());
+        client.connect("localhost", 2000).await.unwrap();
+
+        let factory = ActorFactory::new(client.clone()).with_max_concurrent_spawns(3);
+        let graph = factory
+            .spawn_from_blueprint(&create_fleet_blueprint(6))
+            .await
+            .unwrap();
+
+        assert_eq!(graph.vehicles.len(), 6);
+        assert_eq!(graph.sensors.len(), 12);
+        assert_eq!(graph.sensor_to_vehicle["lidar_4"], "vehicle_4");
+        assert_eq!(client.actor_count(), 18);
+        assert_eq!(client.peak_spawns_in_flight(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_spawn_graph_is_deterministic() {
+        let mut graphs = Vec::new();
+        for max_concurrent in [1, 4, 16] {
+            let mut client = MockCarlaClient::with_config(latency_config());
+            client.connect("localhost", 2000).await.unwrap();
+            let factory = ActorFactory::new(client).with_max_concurrent_spawns(max_concurrent);
+            graphs.push(
+                factory
+                    .spawn_from_blueprint(&create_fleet_blueprint(4))
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        for graph in &graphs[1..] {
+            assert_eq!(graph.vehicles, graphs[0].vehicles);
+            assert_eq!(graph.sensors, graphs[0].sensors);
+            assert_eq!(graph.actor_to_id, graphs[0].actor_to_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parallel_spawn_failure_rolls_back_everything() {
+        // Requests 0-3 are the vehicles: fail a vehicle, then a sensor
+        for fail_spawn_at in [2, 6] {
+            let mut client = MockCarlaClient::with_config(MockConfig {
+                fail_spawn_at: Some(fail_spawn_at),
+                ..latency_config()
+            });
+            client.connect("localhost", 2000).await.unwrap();
+
+            let factory = ActorFactory::new(client.clone()).with_max_concurrent_spawns(4);
+            let result = factory
+                .spawn_from_blueprint(&create_fleet_blueprint(4))
+                .await;
+
+            assert!(result.is_err(), "spawn #{fail_spawn_at} should fail");
+            assert_eq!(
+                client.actor_count(),
+                0,
+                "spawn #{fail_spawn_at} leaked actors"
+            );
+        }
+    }
 }
@@ -0,0 +1,483 @@
+//! Fault-injecting SensorSource decorator
+//!
+//! Wraps any `SensorSource` and perturbs its stream according to a
+//! [`FaultModel`]: timestamp skew, blackouts, drops, reordering, duplicates,
+//! bursts and delayed delivery. All randomness is seeded, so a run can be
+//! reproduced exactly.
+//!
+//! The source declares the reordering and duplicates it produces as its
+//! [`OrderingTolerance`], so ingestion validation forwards them to the sync
+//! engine instead of quarantining them.
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use contracts::{
+    FaultModel, OrderingTolerance, SensorDataCallback, SensorPacket, SensorSource, SensorType,
+    SimTime,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tracing::debug;
+
+/// Delivery thread poll interval while no packet is pending
+const IDLE_POLL: Duration = Duration::from_millis(50);
+
+/// Seed for one sensor's random stream, mixing the configured seed with the
+/// sensor id (FNV-1a, stable across Rust releases)
+pub(crate) fn stream_seed(seed: u64, sensor_id: &str) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let hash = sensor_id.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    seed ^ hash
+}
+
+/// SensorSource decorator injecting faults from a [`FaultModel`]
+pub struct FaultInjectingSource {
+    inner: Box<dyn SensorSource>,
+    model: FaultModel,
+}
+
+impl FaultInjectingSource {
+    /// Wrap `inner` with the given fault model
+    pub fn new(inner: Box<dyn SensorSource>, model: FaultModel) -> Self {
+        Self { inner, model }
+    }
+}
+
+impl SensorSource for FaultInjectingSource {
+    fn sensor_id(&self) -> &str {
+        self.inner.sensor_id()
+    }
+
+    fn sensor_type(&self) -> SensorType {
+        self.inner.sensor_type()
+    }
+
+    fn listen(&self, callback: SensorDataCallback) {
+        debug!(sensor_id = %self.inner.sensor_id(), model = ?self.model, "injecting sensor faults");
+
+        let delayed = self.model.latency_s > 0.0 || self.model.jitter_s > 0.0;
+        let sink = if delayed {
+            Sink::Delayed(Mutex::new(spawn_delivery(callback)))
+        } else {
+            Sink::Direct(callback)
+        };
+        let injector = Injector {
+            state: Mutex::new(FaultState::new(self.model, self.inner.sensor_id())),
+            sink,
+        };
+
+        // The injector flushes held packets when the inner source releases
+        // this callback at the end of its stream
+        self.inner
+            .listen(Arc::new(move |packet| injector.process(packet)));
+    }
+
+    fn stop(&self) {
+        self.inner.stop();
+    }
+
+    fn is_listening(&self) -> bool {
+        self.inner.is_listening()
+    }
+
+    fn ordering_tolerance(&self) -> OrderingTolerance {
+        let model = &self.model;
+        OrderingTolerance {
+            out_of_order: model.reorder_prob > 0.0 || model.jitter_s > 0.0,
+            duplicates: model.duplicate_prob > 0.0,
+        }
+        .union(self.inner.ordering_tolerance())
+    }
+}
+
+/// Where faulted packets go
+enum Sink {
+    Direct(SensorDataCallback),
+    /// Delivery thread applying latency and jitter
+    Delayed(Mutex<mpsc::Sender<(Instant, SensorPacket)>>),
+}
+
+/// Fault state bound to its output for one `listen` session
+struct Injector {
+    state: Mutex<FaultState>,
+    sink: Sink,
+}
+
+impl Injector {
+    fn process(&self, packet: SensorPacket) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let out = state.process(packet);
+        self.deliver(&mut state, out);
+    }
+
+    fn deliver(&self, state: &mut FaultState, packets: Vec<SensorPacket>) {
+        match &self.sink {
+            Sink::Direct(callback) => packets.into_iter().for_each(|packet| callback(packet)),
+            Sink::Delayed(tx) => {
+                let tx = tx.lock().unwrap_or_else(|e| e.into_inner());
+                for packet in packets {
+                    let _ = tx.send((Instant::now() + state.delay(), packet));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Injector {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let out = state.flush();
+        self.deliver(&mut state, out);
+    }
+}
+
+/// Fault sequence state for one stream
+struct FaultState {
+    model: FaultModel,
+    rng: StdRng,
+    blackout_until: Option<SimTime>,
+    /// Packet held back for reordering
+    held: Option<SensorPacket>,
+    burst_left: u32,
+    burst: Vec<SensorPacket>,
+}
+
+impl FaultState {
+    /// RNG seeded from the model seed and sensor id, so sensors sharing a
+    /// seed still fault independently
+    fn new(model: FaultModel, sensor_id: &str) -> Self {
+        Self {
+            model,
+            rng: StdRng::seed_from_u64(stream_seed(model.seed, sensor_id)),
+            blackout_until: None,
+            held: None,
+            burst_left: 0,
+            burst: Vec::new(),
+        }
+    }
+
+    fn chance(&mut self, prob: f64) -> bool {
+        prob > 0.0 && self.rng.random::<f64>() < prob
+    }
+
+    /// Apply faults to one packet, returning the packets to deliver now
+    fn process(&mut self, mut packet: SensorPacket) -> Vec<SensorPacket> {
+        let model = self.model;
+        packet.timestamp += SimTime::from_secs_f64(model.skew_s);
+
+        if self
+            .blackout_until
+            .is_some_and(|until| packet.timestamp < until)
+        {
+            return Vec::new();
+        }
+        if self.chance(model.blackout_prob) {
+            self.blackout_until = Some(packet.timestamp + SimTime::from_secs_f64(model.blackout_s));
+            return Vec::new();
+        }
+        if self.chance(model.drop_prob) {
+            return Vec::new();
+        }
+
+        let mut out = match self.held.take() {
+            Some(held) => vec![packet, held],
+            None if self.chance(model.reorder_prob) => {
+                self.held = Some(packet);
+                return Vec::new();
+            }
+            None => vec![packet],
+        };
+        if self.chance(model.duplicate_prob) {
+            out.push(out[0].clone());
+        }
+
+        if self.burst_left > 0 {
+            self.burst.append(&mut out);
+            self.burst_left -= 1;
+            if self.burst_left == 0 {
+                return std::mem::take(&mut self.burst);
+            }
+            return Vec::new();
+        }
+        if model.burst_len > 1 && self.chance(model.burst_prob) {
+            self.burst = out;
+            self.burst_left = model.burst_len - 1;
+            return Vec::new();
+        }
+        out
+    }
+
+    /// Release packets held for reordering or a burst at the end of the
+    /// stream
+    fn flush(&mut self) -> Vec<SensorPacket> {
+        self.burst_left = 0;
+        let mut out = std::mem::take(&mut self.burst);
+        out.extend(self.held.take());
+        out
+    }
+
+    /// Delivery delay for the next packet
+    fn delay(&mut self) -> Duration {
+        let jitter = if self.model.jitter_s > 0.0 {
+            self.rng.random_range(0.0..=self.model.jitter_s)
+        } else {
+            0.0
+        };
+        Duration::from_secs_f64((self.model.latency_s + jitter).max(0.0))
+    }
+}
+
+/// Packet waiting in the delivery thread
+struct Delayed {
+    due: Instant,
+    seq: u64,
+    packet: SensorPacket,
+}
+
+impl PartialEq for Delayed {
+    fn eq(&self, other: &Self) -> bool {
+        (self.due, self.seq) == (other.due, other.seq)
+    }
+}
+
+impl Eq for Delayed {}
+
+impl PartialOrd for Delayed {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Delayed {
+    // Reversed: BinaryHeap pops the earliest due packet first
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (other.due, other.seq).cmp(&(self.due, self.seq))
+    }
+}
+
+/// Spawn a thread delivering packets to `callback` at their due time.
+///
+/// Runs until the sender is dropped, then flushes what is still pending.
+pub(crate) fn spawn_delivery(
+    callback: SensorDataCallback,
+) -> mpsc::Sender<(Instant, SensorPacket)> {
+    let (tx, rx) = mpsc::channel::<(Instant, SensorPacket)>();
+
+    thread::spawn(move || {
+        let mut pending = BinaryHeap::new();
+        let mut seq = 0u64;
+        let mut connected = true;
+
+        while connected || !pending.is_empty() {
+            let wait = pending.peek().map_or(IDLE_POLL, |d: &Delayed| {
+                d.due.saturating_duration_since(Instant::now())
+            });
+            if connected {
+                match rx.recv_timeout(wait) {
+                    Ok((due, packet)) => {
+                        seq += 1;
+                        pending.push(Delayed { due, seq, packet });
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => connected = false,
+                }
+            } else {
+                thread::sleep(wait);
+            }
+
+            let now = Instant::now();
+            while pending.peek().is_some_and(|d| d.due <= now) {
+                if let Some(d) = pending.pop() {
+                    callback(d.packet);
+                }
+            }
+        }
+    });
+
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::SensorPayload;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn packet(frame_id: u64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "imu".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(frame_id as f64 * 0.1),
+            frame_id: Some(frame_id),
+            payload: SensorPayload::Raw(Bytes::new()),
+        }
+    }
+
+    /// Emits `count` packets synchronously from `listen`
+    struct BurstSource {
+        count: u64,
+        listening: AtomicBool,
+    }
+
+    impl SensorSource for BurstSource {
+        fn sensor_id(&self) -> &str {
+            "imu"
+        }
+
+        fn sensor_type(&self) -> SensorType {
+            SensorType::Imu
+        }
+
+        fn listen(&self, callback: SensorDataCallback) {
+            self.listening.store(true, Ordering::SeqCst);
+            (0..self.count).for_each(|i| callback(packet(i)));
+        }
+
+        fn stop(&self) {
+            self.listening.store(false, Ordering::SeqCst);
+        }
+
+        fn is_listening(&self) -> bool {
+            self.listening.load(Ordering::SeqCst)
+        }
+    }
+
+    fn run(model: FaultModel, count: u64) -> Vec<u64> {
+        let source = FaultInjectingSource::new(
+            Box::new(BurstSource {
+                count,
+                listening: AtomicBool::new(false),
+            }),
+            model,
+        );
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        source.listen(Arc::new(move |p: SensorPacket| {
+            sink.lock().unwrap().push(p.frame_id.unwrap());
+        }));
+        source.stop();
+        let frames = received.lock().unwrap().clone();
+        frames
+    }
+
+    fn frames(state: &mut FaultState, ids: std::ops::Range<u64>) -> Vec<u64> {
+        ids.flat_map(|i| state.process(packet(i)))
+            .map(|p| p.frame_id.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_faults_are_reproducible() {
+        let model = FaultModel {
+            drop_prob: 0.2,
+            duplicate_prob: 0.1,
+            reorder_prob: 0.1,
+            seed: 42,
+            ..Default::default()
+        };
+        let first = run(model, 200);
+        assert_eq!(first, run(model, 200));
+        assert!(first.len() < 200 && first.len() > 100);
+        assert!(first.windows(2).any(|w| w[0] > w[1]));
+        assert!(first.windows(2).any(|w| w[0] == w[1]));
+
+        let clean = run(FaultModel::default(), 20);
+        assert_eq!(clean, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_reorder_burst_and_blackout() {
+        let always = |f: fn(&mut FaultModel)| {
+            let mut model = FaultModel::default();
+            f(&mut model);
+            FaultState::new(model, "imu")
+        };
+
+        let mut reorder = always(|m| m.reorder_prob = 1.0);
+        assert_eq!(frames(&mut reorder, 0..4), vec![1, 0, 3, 2]);
+
+        let mut burst = always(|m| {
+            m.burst_prob = 1.0;
+            m.burst_len = 3;
+        });
+        assert!(burst.process(packet(0)).is_empty());
+        assert!(burst.process(packet(1)).is_empty());
+        assert_eq!(burst.process(packet(2)).len(), 3);
+
+        // Blackout of 0.25 s swallows the triggering packet and the next two
+        let mut blackout = always(|m| {
+            m.blackout_prob = 1.0;
+            m.blackout_s = 0.25;
+            m.skew_s = 0.5;
+        });
+        assert!(frames(&mut blackout, 0..3).is_empty());
+        blackout.model.blackout_prob = 0.0;
+        let out = blackout.process(packet(3));
+        assert_eq!(out[0].timestamp, SimTime::from_secs_f64(0.8));
+    }
+
+    #[test]
+    fn test_held_packets_flush_at_end_of_stream() {
+        let reorder = FaultModel {
+            reorder_prob: 1.0,
+            ..Default::default()
+        };
+        // Frame 2 is held back waiting for a successor that never comes
+        assert_eq!(run(reorder, 3), vec![1, 0, 2]);
+
+        let burst = FaultModel {
+            burst_prob: 1.0,
+            burst_len: 4,
+            ..Default::default()
+        };
+        assert_eq!(run(burst, 6), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_stream_seed_is_stable() {
+        assert_eq!(stream_seed(0, ""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stream_seed(0, "a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(stream_seed(7, "imu"), stream_seed(7, "gnss"));
+    }
+
+    #[test]
+    fn test_latency_delays_delivery() {
+        let model = FaultModel {
+            latency_s: 0.05,
+            ..Default::default()
+        };
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let tx = spawn_delivery(Arc::new(move |p: SensorPacket| {
+            sink.lock()
+                .unwrap()
+                .push((Instant::now(), p.frame_id.unwrap()));
+        }));
+        let mut state = FaultState::new(model, "imu");
+        let sent = Instant::now();
+        let packets: Vec<_> = (0..3).flat_map(|i| state.process(packet(i))).collect();
+        for p in packets {
+            tx.send((sent + state.delay(), p)).unwrap();
+        }
+        drop(tx);
+        thread::sleep(Duration::from_millis(200));
+
+        let received = received.lock().unwrap();
+        assert_eq!(
+            received.iter().map(|r| r.1).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(received
+            .iter()
+            .all(|r| r.0 >= sent + Duration::from_millis(50)));
+    }
+}
@@ -8,6 +8,9 @@
 //! - Provide teardown and rollback
 //! - Provide unified `SensorSource` abstraction
 //! - Support Mock and Replay modes
+//! - Derive or check blueprints against recordings
+//! - Inject seeded stream faults for robustness testing
+//! - Accept sensors streamed by external processes over local sockets
 //!
 //! ## Feature Flags
 //!
@@ -15,9 +18,15 @@
 
 pub mod client;
 pub mod error;
+pub mod external_source;
 pub mod factory;
+pub mod fault_source;
 pub mod mock_client;
+pub mod mock_scene;
 pub mod mock_sensor;
+pub mod recording;
+pub mod replay_blueprint;
+pub mod replay_clock;
 pub mod replay_sensor;
 
 #[cfg(feature = "real-carla")]
@@ -29,11 +38,19 @@ pub mod sensor_data_converter;
 
 pub use client::CarlaClient;
 pub use contracts::{ActorId, RuntimeGraph, SensorSource, WorldBlueprint};
-pub use error::{ActorFactoryError, Result};
-pub use factory::ActorFactory;
+pub use error::{ActorFactoryError, RecordingError, Result};
+pub use external_source::{encode_frame, ExternalSensorSource};
+pub use factory::{ActorFactory, DEFAULT_MAX_CONCURRENT_SPAWNS};
+pub use fault_source::FaultInjectingSource;
 pub use mock_client::{MockCarlaClient, MockConfig};
+pub use mock_scene::MockScene;
 pub use mock_sensor::{MockSensor, MockSensorConfig};
-pub use replay_sensor::{ReplayConfig, ReplaySensor};
+pub use recording::{
+    Prefetcher, RecordingReader, RecordingWriter, SensorTrack, RECORDING_FORMAT_VERSION,
+};
+pub use replay_blueprint::{RecordedSensor, RecordingSummary};
+pub use replay_clock::{ReplayClock, ReplayCommand, ReplayStatus};
+pub use replay_sensor::{load_recording_packets, RecordingManifest, ReplayConfig, ReplaySensor};
 
 #[cfg(feature = "real-carla")]
 pub use carla_client::RealCarlaClient;
@@ -3,15 +3,23 @@
 //! Mock implementation for unit testing, supports injecting failure scenarios and replay mode.
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use contracts::{ActorId, SensorSource, SensorType, Transform};
+use contracts::{
+    ActorId, ClockModel, FaultModel, GroundTruthLog, SensorSource, SensorType, Transform,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use tracing::{info, instrument};
 
 use crate::client::CarlaClient;
 use crate::error::{ActorFactoryError, Result};
+use crate::fault_source::FaultInjectingSource;
 use crate::mock_sensor::{MockSensor, MockSensorConfig};
+use crate::recording::RecordingReader;
+use crate::replay_clock::ReplayClock;
 use crate::replay_sensor::{ReplayConfig, ReplaySensor};
 
 /// Mock client configuration
@@ -27,6 +35,18 @@ pub struct MockConfig {
     pub sensor_config: MockSensorConfig,
     /// Replay configuration (for replay mode)
     pub replay_config: ReplayConfig,
+    /// Simulated clock error per sensor ID (generation mode)
+    pub sensor_clocks: HashMap<String, ClockModel>,
+    /// Faults injected per sensor ID (generation and replay mode)
+    pub sensor_faults: HashMap<String, FaultModel>,
+    /// Log receiving true capture times from generated sensors
+    pub ground_truth: Option<GroundTruthLog>,
+    /// Time every vehicle/sensor spawn request takes
+    pub spawn_latency: Duration,
+    /// Extra spawn time, up to this much, seeded by actor ID
+    pub spawn_jitter: Duration,
+    /// Index (0-based, in request order) of a spawn request that should fail
+    pub fail_spawn_at: Option<usize>,
 }
 
 /// Mock CARLA client internal state
@@ -41,6 +61,16 @@ struct MockCarlaClientInner {
     connected: Mutex<bool>,
     /// Currently spawning ID (for conditional failure)
     current_spawn_id: Mutex<Option<String>>,
+    /// Spawn requests received so far
+    spawn_requests: AtomicUsize,
+    /// Spawn requests currently in progress
+    spawns_in_flight: AtomicUsize,
+    /// Highest number of spawn requests seen in progress at once
+    peak_spawns_in_flight: AtomicUsize,
+    /// Recording indexed once and read by all replay sensors (replay mode only)
+    recording: Option<RecordingReader>,
+    /// Clock shared by all replay sensors (replay mode only)
+    replay_clock: Option<ReplayClock>,
 }
 
 /// Mock CARLA client
@@ -57,6 +87,10 @@ pub struct MockCarlaClient {
 struct ActorInfo {
     blueprint: String,
     sensor_type: Option<SensorType>,
+    /// Mount pose (sensors only)
+    transform: Option<Transform>,
+    /// Blueprint attributes (sensors only)
+    attributes: HashMap<String, String>,
 }
 
 impl MockCarlaClient {
@@ -67,6 +101,16 @@ impl MockCarlaClient {
 
     /// Create mock client with configuration
     pub fn with_config(config: MockConfig) -> Self {
+        let recording = config.replay_config.replay_path.as_ref().and_then(|path| {
+            RecordingReader::open(path)
+                .inspect_err(|e| {
+                    tracing::warn!(error = %e, "Failed to load recording, falling back to MockSensor")
+                })
+                .ok()
+        });
+        let replay_clock = recording
+            .as_ref()
+            .map(|recording| ReplayClock::new(&config.replay_config, recording.timestamps()));
         Self {
             inner: Arc::new(MockCarlaClientInner {
                 config,
@@ -74,6 +118,11 @@ impl MockCarlaClient {
                 actors: Mutex::new(HashMap::new()),
                 connected: Mutex::new(false),
                 current_spawn_id: Mutex::new(None),
+                spawn_requests: AtomicUsize::new(0),
+                spawns_in_flight: AtomicUsize::new(0),
+                peak_spawns_in_flight: AtomicUsize::new(0),
+                recording,
+                replay_clock,
             }),
         }
     }
@@ -83,6 +132,11 @@ impl MockCarlaClient {
         *self.inner.current_spawn_id.lock().unwrap() = id;
     }
 
+    /// Clock followed by all replay sensors (None outside replay mode)
+    pub fn replay_clock(&self) -> Option<&ReplayClock> {
+        self.inner.replay_clock.as_ref()
+    }
+
     /// Get current created actor count
     pub fn actor_count(&self) -> usize {
         self.inner.actors.lock().unwrap().len()
@@ -93,10 +147,40 @@ impl MockCarlaClient {
         self.inner.actors.lock().unwrap().keys().copied().collect()
     }
 
+    /// Highest number of spawn requests that were in progress at once
+    pub fn peak_spawns_in_flight(&self) -> usize {
+        self.inner.peak_spawns_in_flight.load(Ordering::SeqCst)
+    }
+
     fn allocate_actor_id(&self) -> ActorId {
         self.inner.next_actor_id.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Count a spawn request, returning whether it is the one to fail
+    fn next_spawn_request_fails(&self) -> bool {
+        let index = self.inner.spawn_requests.fetch_add(1, Ordering::SeqCst);
+        self.inner.config.fail_spawn_at == Some(index)
+    }
+
+    /// Simulate spawn latency (jitter seeded by `seed` for reproducibility)
+    async fn spawn_delay(&self, seed: u64) {
+        let config = &self.inner.config;
+        if config.spawn_latency.is_zero() && config.spawn_jitter.is_zero() {
+            return;
+        }
+        let in_flight = self.inner.spawns_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.inner
+            .peak_spawns_in_flight
+            .fetch_max(in_flight, Ordering::SeqCst);
+
+        let jitter = config
+            .spawn_jitter
+            .mul_f64(StdRng::seed_from_u64(seed).random::<f64>());
+        tokio::time::sleep(config.spawn_latency + jitter).await;
+
+        self.inner.spawns_in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
     fn should_fail_spawn(&self) -> bool {
         let current_id = self.inner.current_spawn_id.lock().unwrap();
         if let Some(id) = current_id.as_ref() {
@@ -162,6 +246,14 @@ impl CarlaClient for MockCarlaClient {
         let _ = transform;
         self.ensure_connected()?;
 
+        if self.next_spawn_request_fails() {
+            self.spawn_delay(u64::MAX).await;
+            return Err(ActorFactoryError::VehicleSpawnFailed {
+                vehicle_id: blueprint.to_string(),
+                message: "mock failure".into(),
+            });
+        }
+
         if self.should_fail_spawn() {
             let id = self
                 .inner
@@ -177,11 +269,14 @@ impl CarlaClient for MockCarlaClient {
         }
 
         let actor_id = self.allocate_actor_id();
+        self.spawn_delay(actor_id.into()).await;
         self.inner.actors.lock().unwrap().insert(
             actor_id,
             ActorInfo {
                 blueprint: blueprint.to_string(),
                 sensor_type: None,
+                transform: None,
+                attributes: HashMap::new(),
             },
         );
         Ok(actor_id)
@@ -189,15 +284,15 @@ impl CarlaClient for MockCarlaClient {
 
     #[instrument(
         name = "mock_carla_spawn_sensor",
-        skip(self, _transform, _attributes),
+        skip(self, transform, attributes),
         fields(blueprint = %blueprint, parent_id)
     )]
     async fn spawn_sensor(
         &self,
         blueprint: &str,
-        _transform: Transform,
+        transform: Transform,
         parent_id: ActorId,
-        _attributes: &HashMap<String, String>,
+        attributes: &HashMap<String, String>,
     ) -> Result<ActorId> {
         self.ensure_connected()?;
 
@@ -210,6 +305,15 @@ impl CarlaClient for MockCarlaClient {
             });
         }
 
+        if self.next_spawn_request_fails() {
+            self.spawn_delay(u64::MAX).await;
+            return Err(ActorFactoryError::SensorSpawnFailed {
+                sensor_id: blueprint.to_string(),
+                vehicle_id: format!("actor_{}", parent_id),
+                message: "mock failure".into(),
+            });
+        }
+
         if self.should_fail_spawn() {
             let id = self
                 .inner
@@ -226,12 +330,15 @@ impl CarlaClient for MockCarlaClient {
         }
 
         let actor_id = self.allocate_actor_id();
+        self.spawn_delay(actor_id.into()).await;
         let sensor_type = Self::infer_sensor_type(blueprint);
         self.inner.actors.lock().unwrap().insert(
             actor_id,
             ActorInfo {
                 blueprint: blueprint.to_string(),
                 sensor_type,
+                transform: Some(transform),
+                attributes: attributes.clone(),
             },
         );
         Ok(actor_id)
@@ -263,32 +370,51 @@ impl CarlaClient for MockCarlaClient {
         sensor_type: SensorType,
     ) -> Option<Box<dyn SensorSource>> {
         // Verify actor exists
-        if !self.inner.actors.lock().unwrap().contains_key(&actor_id) {
-            return None;
+        let actor = self.inner.actors.lock().unwrap().get(&actor_id).cloned()?;
+
+        let source = self.create_sensor_source(sensor_id, sensor_type, &actor);
+        match self.inner.config.sensor_faults.get(source.sensor_id()) {
+            Some(faults) => Some(Box::new(FaultInjectingSource::new(source, *faults))),
+            None => Some(source),
         }
+    }
+}
 
-        // If replay_path is configured, use ReplaySensor
-        if let Some(ref replay_path) = self.inner.config.replay_config.replay_path {
-            info!(sensor_id = %sensor_id, path = %replay_path.display(), "Using ReplaySensor");
-            match ReplaySensor::load(
-                replay_path,
-                sensor_id.clone(),
+impl MockCarlaClient {
+    /// Replay or generated source for a sensor, before fault injection
+    fn create_sensor_source(
+        &self,
+        sensor_id: String,
+        sensor_type: SensorType,
+        actor: &ActorInfo,
+    ) -> Box<dyn SensorSource> {
+        // If a recording is loaded, use ReplaySensor
+        if let Some(recording) = &self.inner.recording {
+            info!(sensor_id = %sensor_id, path = %recording.root().display(), "Using ReplaySensor");
+            let sensor = ReplaySensor::from_recording(
+                recording,
+                &sensor_id,
                 sensor_type,
-                self.inner.config.replay_config.clone(),
-            ) {
-                Ok(sensor) => return Some(Box::new(sensor)),
-                Err(e) => {
-                    tracing::warn!(error = %e, "Failed to load ReplaySensor, falling back to MockSensor");
-                }
-            }
+                &self.inner.config.replay_config,
+            );
+            return match &self.inner.replay_clock {
+                Some(clock) => Box::new(sensor.with_clock(clock.clone())),
+                None => Box::new(sensor),
+            };
         }
 
         // Default to MockSensor generation mode
-        Some(Box::new(MockSensor::new(
-            sensor_id,
-            sensor_type,
-            self.inner.config.sensor_config.clone(),
-        )))
+        let mut sensor_config = self.inner.config.sensor_config.clone();
+        sensor_config.mount = actor.transform;
+        sensor_config.attributes = actor.attributes.clone();
+        if let Some(clock) = self.inner.config.sensor_clocks.get(&sensor_id) {
+            sensor_config.clock = *clock;
+        }
+        if let Some(log) = &self.inner.config.ground_truth {
+            log.register_clock(sensor_id.as_str(), sensor_config.clock);
+            sensor_config.ground_truth = Some(log.clone());
+        }
+        Box::new(MockSensor::new(sensor_id, sensor_type, sensor_config))
     }
 }
 
@@ -362,4 +488,88 @@ mod tests {
         client.destroy_actor(actor_id).await.unwrap();
         assert_eq!(client.actor_count(), 0);
     }
+
+    #[tokio::test]
+    async fn test_replay_sensors_share_clock() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = std::fs::File::create(dir.path().join("sensors.jsonl")).unwrap();
+        for (sensor_id, timestamp) in [("gnss_a", 0.0), ("gnss_b", 0.03), ("gnss_a", 0.06)] {
+            writeln!(
+                file,
+                r#"{{"sensor_id":"{sensor_id}","sensor_type":"gnss","timestamp":{timestamp},"frame_id":1,"latitude":0.0,"longitude":0.0,"altitude":0.0}}"#
+            )
+            .unwrap();
+        }
+
+        // Fast mode emits in shared timestamp order, with no wall-clock pacing
+        let mut client = MockCarlaClient::with_config(MockConfig {
+            replay_config: ReplayConfig {
+                replay_path: Some(dir.path().to_path_buf()),
+                speed_multiplier: 1.0,
+                loop_playback: true,
+                fast: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        client.connect("localhost", 2000).await.unwrap();
+        let vehicle_id = client
+            .spawn_vehicle("vehicle.tesla.model3", None)
+            .await
+            .unwrap();
+        let mut sources = Vec::new();
+        for sensor_id in ["gnss_a", "gnss_b"] {
+            let actor_id = client
+                .spawn_sensor(
+                    "sensor.other.gnss",
+                    default_transform(),
+                    vehicle_id,
+                    &HashMap::new(),
+                )
+                .await
+                .unwrap();
+            sources.push(
+                client
+                    .get_sensor_source(actor_id, sensor_id.to_string(), SensorType::Gnss)
+                    .unwrap(),
+            );
+        }
+
+        // The second sensor starts after the first but still joins the
+        // shared schedule from the beginning
+        let (tx, rx) = std::sync::mpsc::channel();
+        for source in &sources {
+            let tx = Mutex::new(tx.clone());
+            source.listen(Arc::new(move |packet| {
+                let _ = tx.lock().unwrap().send(packet);
+            }));
+        }
+        let arrivals: Vec<contracts::SensorPacket> = (0..7)
+            .map(|_| {
+                rx.recv_timeout(Duration::from_secs(5))
+                    .expect("replay stalled")
+            })
+            .collect();
+        sources.iter().for_each(|source| source.stop());
+
+        // Two laps of 0.09 s: a, b, a, then shifted by one period
+        let clock = client.replay_clock().unwrap();
+        assert!((clock.period() - 0.09).abs() < 1e-9);
+        let expected = [
+            ("gnss_a", 0.0),
+            ("gnss_b", 0.03),
+            ("gnss_a", 0.06),
+            ("gnss_a", 0.09),
+            ("gnss_b", 0.12),
+            ("gnss_a", 0.15),
+            ("gnss_a", 0.18),
+        ];
+        for (packet, (sensor_id, timestamp)) in arrivals.iter().zip(expected) {
+            assert_eq!(packet.sensor_id.as_str(), sensor_id);
+            assert!((packet.timestamp.as_secs_f64() - timestamp).abs() < 1e-6);
+        }
+        assert!(clock.status().lap >= 2);
+    }
 }
@@ -0,0 +1,633 @@
+//! Procedural scene for mock sensors
+//!
+//! An ego vehicle drives a circular loop at constant speed over a flat
+//! ground plane lined with box obstacles. Every mock sensor samples the
+//! same scene at its capture time, so IMU, GNSS, LiDAR, radar and camera
+//! data stay mutually consistent without a CARLA server.
+//!
+//! Coordinates follow CARLA: x forward, y right, z up, angles in degrees
+//! on transforms and radians in sensor data.
+
+use std::collections::HashMap;
+use std::f64::consts::{PI, TAU};
+
+use bytes::Bytes;
+use contracts::{
+    GnssData, ImageData, ImageFormat, ImuData, LidarPoint, PointCloudData, RadarData,
+    RadarDetection, Transform, Vector3,
+};
+
+const GRAVITY: f64 = 9.81;
+const EARTH_RADIUS_M: f64 = 6_378_137.0;
+/// CARLA's default LiDAR atmospheric attenuation (1/m)
+const ATTENUATION: f64 = 0.004;
+/// Reflectivity of the ground plane and of obstacles
+const GROUND_REFLECTIVITY: f64 = 0.4;
+const OBSTACLE_REFLECTIVITY: f64 = 0.9;
+/// Radar rays cast per frame (azimuth x elevation)
+const RADAR_GRID: (usize, usize) = (32, 8);
+
+type Vec3 = [f64; 3];
+
+/// Axis-aligned box obstacle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obstacle {
+    /// Box center (m)
+    pub center: Vec3,
+    /// Half size along x, y, z (m)
+    pub half_extents: Vec3,
+}
+
+/// Ego vehicle state at one instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EgoState {
+    /// Position (m)
+    pub position: Vec3,
+    /// Velocity (m/s)
+    pub velocity: Vec3,
+    /// Heading (rad)
+    pub yaw: f64,
+    /// Yaw rate (rad/s)
+    pub yaw_rate: f64,
+    /// Distance travelled since t = 0 (m)
+    pub travelled: f64,
+}
+
+/// Procedural world shared by all mock sensors
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockScene {
+    /// Radius of the ego loop (m)
+    pub loop_radius_m: f64,
+    /// Ego speed (m/s)
+    pub speed_mps: f64,
+    /// Static obstacles
+    pub obstacles: Vec<Obstacle>,
+    /// Geographic reference of the world origin (lat, lon, alt)
+    pub geo_origin: (f64, f64, f64),
+}
+
+impl Default for MockScene {
+    fn default() -> Self {
+        let radius = 40.0;
+        let mut obstacles = Vec::new();
+        // Parked cars alternating inside and outside the loop
+        for k in 0..8 {
+            let angle = k as f64 * TAU / 8.0 + 0.2;
+            let r = if k % 2 == 0 {
+                radius - 6.0
+            } else {
+                radius + 6.0
+            };
+            obstacles.push(Obstacle {
+                center: [r * angle.cos(), r * angle.sin(), 0.8],
+                half_extents: [2.3, 1.0, 0.8],
+            });
+        }
+        // Buildings further out
+        for k in 0..4 {
+            let angle = k as f64 * TAU / 4.0 + PI / 4.0;
+            let r = radius + 30.0;
+            obstacles.push(Obstacle {
+                center: [r * angle.cos(), r * angle.sin(), 6.0],
+                half_extents: [8.0, 8.0, 6.0],
+            });
+        }
+        Self {
+            loop_radius_m: radius,
+            speed_mps: 8.0,
+            obstacles,
+            geo_origin: (40.0, -74.0, 100.0),
+        }
+    }
+}
+
+/// LiDAR parameters read from blueprint attributes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LidarParams {
+    pub channels: u32,
+    pub range: f64,
+    pub upper_fov: f64,
+    pub lower_fov: f64,
+    /// Rays cast per frame (`points_per_second / rotation_frequency`)
+    pub rays_per_frame: u32,
+}
+
+impl LidarParams {
+    /// Parse CARLA LiDAR attributes, falling back to `default_rays` per frame
+    pub fn from_attributes(attributes: &HashMap<String, String>, default_rays: u32) -> Self {
+        let rays_per_frame = match (
+            attr::<f64>(attributes, "points_per_second"),
+            attr::<f64>(attributes, "rotation_frequency"),
+        ) {
+            (Some(pps), Some(hz)) if hz > 0.0 => (pps / hz) as u32,
+            _ => default_rays,
+        };
+        Self {
+            channels: attr(attributes, "channels").unwrap_or(32).max(1),
+            range: attr(attributes, "range").unwrap_or(100.0),
+            upper_fov: attr(attributes, "upper_fov").unwrap_or(10.0),
+            lower_fov: attr(attributes, "lower_fov").unwrap_or(-30.0),
+            rays_per_frame,
+        }
+    }
+}
+
+/// Radar parameters read from blueprint attributes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RadarParams {
+    pub horizontal_fov: f64,
+    pub vertical_fov: f64,
+    pub range: f64,
+}
+
+impl RadarParams {
+    /// Parse CARLA radar attributes
+    pub fn from_attributes(attributes: &HashMap<String, String>) -> Self {
+        Self {
+            horizontal_fov: attr(attributes, "horizontal_fov").unwrap_or(30.0),
+            vertical_fov: attr(attributes, "vertical_fov").unwrap_or(30.0),
+            range: attr(attributes, "range").unwrap_or(100.0),
+        }
+    }
+}
+
+/// Parse a numeric blueprint attribute
+pub(crate) fn attr<T: std::str::FromStr>(
+    attributes: &HashMap<String, String>,
+    key: &str,
+) -> Option<T> {
+    attributes.get(key).and_then(|v| v.trim().parse().ok())
+}
+
+impl MockScene {
+    /// Ego state at time `t` (s)
+    pub fn ego(&self, t: f64) -> EgoState {
+        let omega = self.speed_mps / self.loop_radius_m;
+        let theta = omega * t;
+        let yaw = theta + PI / 2.0;
+        EgoState {
+            position: [
+                self.loop_radius_m * theta.cos(),
+                self.loop_radius_m * theta.sin(),
+                0.0,
+            ],
+            velocity: [self.speed_mps * yaw.cos(), self.speed_mps * yaw.sin(), 0.0],
+            yaw,
+            yaw_rate: omega,
+            travelled: self.speed_mps * t,
+        }
+    }
+
+    /// IMU reading: centripetal acceleration plus gravity, constant yaw rate
+    pub fn imu(&self, t: f64) -> ImuData {
+        let ego = self.ego(t);
+        ImuData {
+            // The loop center is always to the right of the ego
+            accelerometer: Vector3 {
+                x: 0.0,
+                y: self.speed_mps * ego.yaw_rate,
+                z: GRAVITY,
+            },
+            gyroscope: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: ego.yaw_rate,
+            },
+            // CARLA compass: 0 = north = -y
+            compass: (ego.yaw + PI / 2.0).rem_euclid(TAU),
+        }
+    }
+
+    /// GNSS fix of a sensor mounted on the ego
+    pub fn gnss(&self, t: f64, mount: Option<&Transform>) -> GnssData {
+        let (origin, _) = self.sensor_pose(t, mount);
+        let (lat0, lon0, alt0) = self.geo_origin;
+        GnssData {
+            latitude: lat0 - (origin[1] / EARTH_RADIUS_M).to_degrees(),
+            longitude: lon0 + (origin[0] / (EARTH_RADIUS_M * lat0.to_radians().cos())).to_degrees(),
+            altitude: alt0 + origin[2],
+        }
+    }
+
+    /// Ray-cast LiDAR sweep; points are in the sensor frame
+    pub fn lidar(&self, t: f64, mount: Option<&Transform>, params: &LidarParams) -> PointCloudData {
+        let (origin, axes) = self.sensor_pose(t, mount);
+        let channels = params.channels as usize;
+        let steps = (params.rays_per_frame as usize).div_ceil(channels).max(1);
+        let step_angle = TAU / steps as f64;
+
+        // Obstacles in range as seen from the sensor: azimuth, elevation and
+        // angular radius of their bounding spheres
+        let targets: Vec<_> = self
+            .obstacles
+            .iter()
+            .filter_map(|obstacle| {
+                let local = unrotate(&axes, sub(obstacle.center, origin));
+                let dist = dot(local, local).sqrt();
+                let radius = dot(obstacle.half_extents, obstacle.half_extents).sqrt();
+                if dist - radius > params.range {
+                    return None;
+                }
+                let angular = if dist <= radius {
+                    PI
+                } else {
+                    (radius / dist).asin()
+                };
+                let azimuth = local[1].atan2(local[0]);
+                Some((obstacle, azimuth, (local[2] / dist).asin(), angular))
+            })
+            .collect();
+
+        let azimuths: Vec<(f64, f64)> = (0..steps)
+            .map(|step| (step as f64 * step_angle).sin_cos())
+            .collect();
+        let mut points = Vec::with_capacity(channels * steps);
+        let mut rays = vec![([0.0; 3], [0.0; 3]); steps];
+        let mut hits: Vec<Option<(f64, f64)>> = vec![None; steps];
+        for channel in 0..channels {
+            let elevation = if channels == 1 {
+                params.upper_fov
+            } else {
+                params.upper_fov
+                    - channel as f64 * (params.upper_fov - params.lower_fov) / (channels - 1) as f64
+            }
+            .to_radians();
+            let (sin_el, cos_el) = elevation.sin_cos();
+            for ((ray, hit), &(sin_az, cos_az)) in rays.iter_mut().zip(&mut hits).zip(&azimuths) {
+                let local = [cos_el * cos_az, cos_el * sin_az, sin_el];
+                *ray = (local, rotate(&axes, local));
+                *hit = ground_hit(origin, ray.1, params.range).map(|d| (d, GROUND_REFLECTIVITY));
+            }
+            // Only test obstacles on the steps their bounding sphere covers
+            for &(obstacle, azimuth, obstacle_elevation, angular) in &targets {
+                let Some(span) = azimuth_span(elevation, obstacle_elevation, angular) else {
+                    continue;
+                };
+                let first = ((azimuth - span) / step_angle).floor() as i64;
+                let last = ((azimuth + span) / step_angle).ceil() as i64;
+                let last = last.min(first + steps as i64 - 1);
+                for k in first..=last {
+                    let step = k.rem_euclid(steps as i64) as usize;
+                    let limit = hits[step].map_or(params.range, |(d, _)| d);
+                    if let Some(d) = intersect_box(origin, rays[step].1, obstacle, limit) {
+                        hits[step] = Some((d, OBSTACLE_REFLECTIVITY));
+                    }
+                }
+            }
+
+            for (&(local, _), hit) in rays.iter().zip(&hits) {
+                let Some((dist, reflectivity)) = *hit else {
+                    continue;
+                };
+                points.push(LidarPoint {
+                    x: (local[0] * dist) as f32,
+                    y: (local[1] * dist) as f32,
+                    z: (local[2] * dist) as f32,
+                    intensity: (reflectivity * (-ATTENUATION * dist).exp()) as f32,
+                });
+            }
+        }
+        PointCloudData::from_points_vec(points)
+    }
+
+    /// Radar detections over the field of view
+    pub fn radar(&self, t: f64, mount: Option<&Transform>, params: &RadarParams) -> RadarData {
+        let ego = self.ego(t);
+        let (origin, axes) = self.sensor_pose(t, mount);
+        let (cols, rows) = RADAR_GRID;
+        let mut detections = Vec::new();
+        for row in 0..rows {
+            let altitude =
+                (params.vertical_fov * (row as f64 / (rows - 1) as f64 - 0.5)).to_radians();
+            for col in 0..cols {
+                let azimuth =
+                    (params.horizontal_fov * (col as f64 / (cols - 1) as f64 - 0.5)).to_radians();
+                let world = rotate(&axes, direction(azimuth, altitude));
+                let Some((depth, _)) = self.cast(origin, world, params.range) else {
+                    continue;
+                };
+                // The world is static: relative velocity is minus the ego's
+                detections.push(RadarDetection {
+                    velocity: -dot(ego.velocity, world) as f32,
+                    azimuth: azimuth as f32,
+                    altitude: altitude as f32,
+                    depth: depth as f32,
+                });
+            }
+        }
+        RadarData::from_detections_vec(detections)
+    }
+
+    /// BGRA image: sky over a ground checkerboard that scrolls with the ego,
+    /// with the frame counter drawn in the top-left corner
+    pub fn camera(&self, t: f64, frame_id: u64, width: u32, height: u32) -> ImageData {
+        const CELL: i64 = 32;
+        const PIXELS_PER_M: f64 = 16.0;
+        let (w, h) = (width as usize, height as usize);
+        let horizon = h * 2 / 5;
+        let scroll = (self.ego(t).travelled * PIXELS_PER_M) as i64;
+        // Ground rows alternate between two checker phases
+        let phases: [Vec<u8>; 2] = [0, 1].map(|phase| {
+            (0..w)
+                .flat_map(|x| {
+                    let v = if (phase + x as i64 / CELL) % 2 == 0 {
+                        170
+                    } else {
+                        70
+                    };
+                    [v, v, v, 255]
+                })
+                .collect()
+        });
+        let mut data = Vec::with_capacity(w * h * 4);
+        for y in 0..h {
+            if y < horizon {
+                let shade = (200 - 80 * y / horizon.max(1)) as u8;
+                data.extend_from_slice(&[255, shade, shade / 2, 255].repeat(w));
+            } else {
+                let band = (y as i64 + scroll) / CELL;
+                data.extend_from_slice(&phases[band.rem_euclid(2) as usize]);
+            }
+        }
+        draw_number(&mut data, w, h, frame_id);
+        ImageData {
+            width,
+            height,
+            format: ImageFormat::Bgra8,
+            data: Bytes::from(data),
+        }
+    }
+
+    /// Sensor origin and world-frame axes (forward, right, up)
+    fn sensor_pose(&self, t: f64, mount: Option<&Transform>) -> (Vec3, [Vec3; 3]) {
+        let ego = self.ego(t);
+        let ego_axes = axes(0.0, ego.yaw, 0.0);
+        let Some(mount) = mount else {
+            return (ego.position, ego_axes);
+        };
+        let offset = rotate(
+            &ego_axes,
+            [mount.location.x, mount.location.y, mount.location.z],
+        );
+        let r = mount.rotation;
+        let local = axes(
+            r.pitch.to_radians(),
+            r.yaw.to_radians(),
+            r.roll.to_radians(),
+        );
+        let world = local.map(|axis| rotate(&ego_axes, axis));
+        (add(ego.position, offset), world)
+    }
+
+    /// Nearest hit along a ray within `range`: (distance, reflectivity)
+    fn cast(&self, origin: Vec3, dir: Vec3, range: f64) -> Option<(f64, f64)> {
+        let mut best = ground_hit(origin, dir, range).map(|d| (d, GROUND_REFLECTIVITY));
+        for obstacle in &self.obstacles {
+            let limit = best.map_or(range, |(d, _)| d);
+            if let Some(d) = intersect_box(origin, dir, obstacle, limit) {
+                best = Some((d, OBSTACLE_REFLECTIVITY));
+            }
+        }
+        best
+    }
+}
+
+/// Distance to the ground plane (z = 0) along a ray, within `range`
+fn ground_hit(origin: Vec3, dir: Vec3, range: f64) -> Option<f64> {
+    (dir[2] < 0.0)
+        .then(|| -origin[2] / dir[2])
+        .filter(|&d| d > 0.0 && d <= range)
+}
+
+/// Half-width in azimuth of the rays at `elevation` that can pass within
+/// `angular` of a direction at `target_elevation`; None if no ray can
+fn azimuth_span(elevation: f64, target_elevation: f64, angular: f64) -> Option<f64> {
+    if (elevation - target_elevation).abs() > angular {
+        return None;
+    }
+    let scale = elevation.cos() * target_elevation.cos();
+    if angular >= PI / 2.0 || scale < 1e-9 {
+        return Some(PI);
+    }
+    // Spherical law of cosines solved for the azimuth difference
+    let x = 1.0 - ((elevation - target_elevation).cos() - angular.cos()) / scale;
+    Some(if x <= -1.0 { PI } else { x.min(1.0).acos() })
+}
+
+/// Slab test; entry distance if the ray hits the box before `limit`
+fn intersect_box(origin: Vec3, dir: Vec3, obstacle: &Obstacle, limit: f64) -> Option<f64> {
+    let (mut near, mut far) = (0.0_f64, limit);
+    for i in 0..3 {
+        let lo = obstacle.center[i] - obstacle.half_extents[i];
+        let hi = obstacle.center[i] + obstacle.half_extents[i];
+        if dir[i].abs() < 1e-12 {
+            if origin[i] < lo || origin[i] > hi {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = ((lo - origin[i]) / dir[i], (hi - origin[i]) / dir[i]);
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+        if near > far {
+            return None;
+        }
+    }
+    (near > 0.0).then_some(near)
+}
+
+/// Unit vector from azimuth (towards +y) and elevation, in radians
+fn direction(azimuth: f64, elevation: f64) -> Vec3 {
+    [
+        elevation.cos() * azimuth.cos(),
+        elevation.cos() * azimuth.sin(),
+        elevation.sin(),
+    ]
+}
+
+/// Forward, right and up axes of a CARLA rotation (radians)
+fn axes(pitch: f64, yaw: f64, roll: f64) -> [Vec3; 3] {
+    let (sp, cp) = pitch.sin_cos();
+    let (sy, cy) = yaw.sin_cos();
+    let (sr, cr) = roll.sin_cos();
+    [
+        [cp * cy, cp * sy, sp],
+        [cy * sp * sr - sy * cr, sy * sp * sr + cy * cr, -cp * sr],
+        [-cy * sp * cr - sy * sr, -sy * sp * cr + cy * sr, cp * cr],
+    ]
+}
+
+/// Express a world vector in the frame spanned by orthonormal `axes`
+fn unrotate(axes: &[Vec3; 3], v: Vec3) -> Vec3 {
+    axes.map(|axis| dot(axis, v))
+}
+
+/// Express a local vector in the frame spanned by `axes`
+fn rotate(axes: &[Vec3; 3], v: Vec3) -> Vec3 {
+    let mut out = [0.0; 3];
+    for (axis, s) in axes.iter().zip(v) {
+        for i in 0..3 {
+            out[i] += axis[i] * s;
+        }
+    }
+    out
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// 3x5 bitmap digits, one row per entry (bit 2 = left column)
+const DIGITS: [[u8; 5]; 10] = [
+    [7, 5, 5, 5, 7],
+    [2, 6, 2, 2, 7],
+    [7, 1, 7, 4, 7],
+    [7, 1, 7, 1, 7],
+    [5, 5, 7, 1, 1],
+    [7, 4, 7, 1, 7],
+    [7, 4, 7, 5, 7],
+    [7, 1, 1, 1, 1],
+    [7, 5, 7, 5, 7],
+    [7, 5, 7, 1, 7],
+];
+
+/// Draw `value` in white on a black box in the top-left corner
+fn draw_number(data: &mut [u8], width: usize, height: usize, value: u64) {
+    const SCALE: usize = 4;
+    const MARGIN: usize = 8;
+    let digits = value.to_string();
+    let box_w = (MARGIN + digits.len() * 4 * SCALE + MARGIN).min(width);
+    let box_h = (MARGIN + 5 * SCALE + MARGIN).min(height);
+    let mut set = |x: usize, y: usize, v: u8| {
+        if x < width && y < height {
+            let i = (y * width + x) * 4;
+            data[i..i + 4].copy_from_slice(&[v, v, v, 255]);
+        }
+    };
+    for y in 0..box_h {
+        for x in 0..box_w {
+            set(x, y, 0);
+        }
+    }
+    for (n, digit) in digits.bytes().enumerate() {
+        let glyph = DIGITS[(digit - b'0') as usize];
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (4 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        let x = MARGIN + (n * 4 + col) * SCALE + dx;
+                        set(x, MARGIN + row * SCALE + dy, 255);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{Location, Rotation};
+
+    fn roof_mount() -> Transform {
+        Transform {
+            location: Location {
+                x: 0.0,
+                y: 0.0,
+                z: 2.5,
+            },
+            rotation: Rotation {
+                pitch: 0.0,
+                yaw: 0.0,
+                roll: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_imu_and_gnss_follow_trajectory() {
+        let scene = MockScene::default();
+        let dt = 0.01;
+        let (a, b) = (scene.ego(1.0), scene.ego(1.0 + dt));
+        let speed = ((b.position[0] - a.position[0]).powi(2)
+            + (b.position[1] - a.position[1]).powi(2))
+        .sqrt()
+            / dt;
+        assert!((speed - scene.speed_mps).abs() < 1e-3);
+
+        let imu = scene.imu(1.0);
+        assert!((imu.gyroscope.z * dt - (b.yaw - a.yaw)).abs() < 1e-9);
+        let centripetal = scene.speed_mps.powi(2) / scene.loop_radius_m;
+        assert!((imu.accelerometer.y - centripetal).abs() < 1e-9);
+        assert_eq!(imu.accelerometer.z, GRAVITY);
+
+        // GNSS moves by the travelled distance
+        let (g0, g1) = (scene.gnss(1.0, None), scene.gnss(1.0 + dt, None));
+        let north = (g1.latitude - g0.latitude).to_radians() * EARTH_RADIUS_M;
+        let east = (g1.longitude - g0.longitude).to_radians()
+            * EARTH_RADIUS_M
+            * scene.geo_origin.0.to_radians().cos();
+        assert!(((north.powi(2) + east.powi(2)).sqrt() / dt - scene.speed_mps).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_lidar_respects_channels_and_range() {
+        let scene = MockScene::default();
+        let attributes = HashMap::from([
+            ("channels".to_string(), "16".to_string()),
+            ("range".to_string(), "30".to_string()),
+        ]);
+        let params = LidarParams::from_attributes(&attributes, 4096);
+        let cloud = scene.lidar(0.0, Some(&roof_mount()), &params);
+        assert!(cloud.num_points > 0);
+
+        let mut elevations = Vec::new();
+        let mut ground = 0;
+        for p in cloud.iter_points::<LidarPoint>() {
+            let dist = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();
+            assert!(dist <= 30.0 + 1e-3);
+            let elevation = ((p.z / dist).asin().to_degrees() * 10.0).round() as i32;
+            if !elevations.contains(&elevation) {
+                elevations.push(elevation);
+            }
+            if (p.z + 2.5).abs() < 1e-3 {
+                ground += 1;
+            }
+        }
+        assert!(elevations.len() <= 16);
+        assert!(ground > 0);
+        // Obstacles along the loop return stronger echoes than the ground
+        assert!(cloud
+            .iter_points::<LidarPoint>()
+            .any(|p| p.z > -2.4 && p.intensity > 0.5));
+    }
+
+    #[test]
+    fn test_camera_scrolls_and_shows_frame_counter() {
+        let scene = MockScene::default();
+        let a = scene.camera(0.0, 7, 160, 120);
+        let b = scene.camera(0.3, 7, 160, 120);
+        let c = scene.camera(0.0, 8, 160, 120);
+        assert_eq!(a.data.len(), 160 * 120 * 4);
+        assert_ne!(a.data, b.data);
+
+        // Only the counter differs between frames at the same pose
+        let changed: Vec<usize> = (0..a.data.len())
+            .filter(|&i| a.data[i] != c.data[i])
+            .map(|i| i / 4)
+            .collect();
+        assert!(!changed.is_empty());
+        assert!(changed.iter().all(|&px| px % 160 < 40 && px / 160 < 40));
+    }
+}
@@ -1,19 +1,26 @@
 //! Mock sensor implementation
 //!
 //! Implements `SensorSource` trait, generates simulated sensor data.
-//! Used for testing and development without CARLA environment.
+//! Payloads are sampled from a shared [`MockScene`], so sensors agree on the
+//! ego motion and surroundings. Used for testing and development without
+//! CARLA environment.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use bytes::Bytes;
 use contracts::{
-    GnssData, ImageData, ImageFormat, ImuData, PointCloudData, RadarData, SensorDataCallback,
-    SensorPacket, SensorPayload, SensorSource, SensorType, Vector3,
+    CaptureRecord, ClockModel, GroundTruthLog, OrderingTolerance, SensorDataCallback, SensorPacket,
+    SensorPayload, SensorSource, SensorType, SimTime, Transform,
 };
-use tracing::{debug, trace};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tracing::{debug, trace, warn};
+
+use crate::fault_source::{spawn_delivery, stream_seed};
+use crate::mock_scene::{attr, LidarParams, MockScene, RadarParams};
 
 /// Mock sensor configuration
 #[derive(Debug, Clone)]
@@ -24,8 +31,19 @@ pub struct MockSensorConfig {
     pub image_width: u32,
     /// Image height (Camera only)
     pub image_height: u32,
-    /// LiDAR point count (Lidar only)
+    /// LiDAR rays per frame unless `points_per_second` and
+    /// `rotation_frequency` attributes are set (Lidar only)
     pub lidar_points: u32,
+    /// Mount pose relative to the ego vehicle (None = ego origin)
+    pub mount: Option<Transform>,
+    /// Blueprint attributes (`image_size_x`, `channels`, `range`, ...)
+    pub attributes: HashMap<String, String>,
+    /// Scene sampled by all sensors sharing this config
+    pub scene: Arc<MockScene>,
+    /// Simulated clock error applied to packet timestamps, and delivery delay
+    pub clock: ClockModel,
+    /// Log receiving true capture times (also provides the shared epoch)
+    pub ground_truth: Option<GroundTruthLog>,
 }
 
 impl Default for MockSensorConfig {
@@ -35,6 +53,11 @@ impl Default for MockSensorConfig {
             image_width: 800,
             image_height: 600,
             lidar_points: 10000,
+            mount: None,
+            attributes: HashMap::new(),
+            scene: Arc::new(MockScene::default()),
+            clock: ClockModel::default(),
+            ground_truth: None,
         }
     }
 }
@@ -66,48 +89,37 @@ impl MockSensor {
         Self::new(sensor_id, sensor_type, MockSensorConfig::default())
     }
 
-    /// Generate simulated data payload
+    /// Jitter RNG seeded from the clock seed and sensor id
+    fn jitter_rng(sensor_id: &str, seed: u64) -> StdRng {
+        StdRng::seed_from_u64(stream_seed(seed, sensor_id))
+    }
+
+    /// Sample the scene at capture time `t` (s)
     fn generate_payload(
         config: &MockSensorConfig,
         sensor_type: SensorType,
         frame_id: u64,
+        t: f64,
     ) -> SensorPayload {
+        let scene = &config.scene;
+        let mount = config.mount.as_ref();
         match sensor_type {
             SensorType::Camera => {
-                let size = (config.image_width * config.image_height * 4) as usize;
-                SensorPayload::Image(ImageData {
-                    width: config.image_width,
-                    height: config.image_height,
-                    format: ImageFormat::Bgra8,
-                    data: Bytes::from(vec![128u8; size]),
-                })
+                let width = attr(&config.attributes, "image_size_x").unwrap_or(config.image_width);
+                let height =
+                    attr(&config.attributes, "image_size_y").unwrap_or(config.image_height);
+                SensorPayload::Image(scene.camera(t, frame_id, width, height))
             }
             SensorType::Lidar => {
-                let size = (config.lidar_points * 16) as usize;
-                SensorPayload::PointCloud(PointCloudData {
-                    num_points: config.lidar_points,
-                    point_stride: 16,
-                    data: Bytes::from(vec![0u8; size]),
-                })
+                let params = LidarParams::from_attributes(&config.attributes, config.lidar_points);
+                SensorPayload::PointCloud(scene.lidar(t, mount, &params))
+            }
+            SensorType::Imu => SensorPayload::Imu(scene.imu(t)),
+            SensorType::Gnss => SensorPayload::Gnss(scene.gnss(t, mount)),
+            SensorType::Radar => {
+                let params = RadarParams::from_attributes(&config.attributes);
+                SensorPayload::Radar(scene.radar(t, mount, &params))
             }
-            SensorType::Imu => SensorPayload::Imu(ImuData {
-                accelerometer: Vector3 {
-                    x: 0.0,
-                    y: 0.0,
-                    z: 9.81,
-                },
-                gyroscope: Vector3::default(),
-                compass: 0.0,
-            }),
-            SensorType::Gnss => SensorPayload::Gnss(GnssData {
-                latitude: 40.0 + (frame_id as f64 * 0.0001),
-                longitude: -74.0 + (frame_id as f64 * 0.0001),
-                altitude: 100.0,
-            }),
-            SensorType::Radar => SensorPayload::Radar(RadarData {
-                num_detections: 5,
-                data: Bytes::from(vec![0u8; 5 * 16]),
-            }),
         }
     }
 }
@@ -137,6 +149,13 @@ impl SensorSource for MockSensor {
         thread::spawn(move || {
             let mut frame_id: u64 = 0;
             let start_time = std::time::Instant::now();
+            let mut rng = Self::jitter_rng(&sensor_id, config.clock.seed);
+            // Delayed packets are handed to a delivery thread, which flushes
+            // what is still pending once the sensor stops
+            let delivery = config
+                .clock
+                .delays_delivery()
+                .then(|| spawn_delivery(callback.clone()));
 
             debug!(
                 sensor_id = %sensor_id,
@@ -147,19 +166,55 @@ impl SensorSource for MockSensor {
 
             while listening.load(Ordering::Relaxed) {
                 frame_id += 1;
-                let timestamp = start_time.elapsed().as_secs_f64();
-
-                let payload = Self::generate_payload(&config, sensor_type, frame_id);
-
+                let true_time = match &config.ground_truth {
+                    Some(log) => log.now(),
+                    None => start_time.elapsed().as_secs_f64(),
+                };
+                let timestamp = if config.clock.is_ideal() {
+                    true_time
+                } else {
+                    config
+                        .clock
+                        .reported_time(true_time, rng.random_range(-1.0..=1.0))
+                };
+                if let Some(log) = &config.ground_truth {
+                    log.record(
+                        sensor_id.as_str(),
+                        frame_id,
+                        CaptureRecord {
+                            true_time,
+                            reported_time: timestamp,
+                        },
+                    );
+                }
+
+                let payload = Self::generate_payload(&config, sensor_type, frame_id, true_time);
+
+                let Some(reported) = SimTime::try_from_secs_f64(timestamp) else {
+                    warn!(
+                        sensor_id = %sensor_id,
+                        frame_id,
+                        "Skipping mock packet with non-finite timestamp"
+                    );
+                    thread::sleep(interval);
+                    continue;
+                };
                 let packet = SensorPacket {
                     sensor_id: sensor_id.clone().into(),
                     sensor_type,
-                    timestamp,
+                    timestamp: reported,
                     frame_id: Some(frame_id),
                     payload,
                 };
 
-                callback(packet);
+                match &delivery {
+                    Some(tx) => {
+                        let delay = config.clock.delivery_delay(rng.random_range(-1.0..=1.0));
+                        let due = Instant::now() + Duration::from_secs_f64(delay);
+                        let _ = tx.send((due, packet));
+                    }
+                    None => callback(packet),
+                }
 
                 trace!(
                     sensor_id = %sensor_id,
@@ -182,13 +237,22 @@ impl SensorSource for MockSensor {
     fn is_listening(&self) -> bool {
         self.listening.load(Ordering::Relaxed)
     }
+
+    fn ordering_tolerance(&self) -> OrderingTolerance {
+        // Timestamp or delivery jitter wider than the period reorders packets
+        OrderingTolerance {
+            out_of_order: self.config.clock.jitter_s > 0.0
+                || self.config.clock.latency_jitter_s > 0.0,
+            duplicates: false,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::atomic::AtomicU64;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_mock_sensor_camera() {
@@ -240,6 +304,88 @@ mod tests {
         assert!(received_imu.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_mock_sensor_records_ground_truth() {
+        let log = GroundTruthLog::new();
+        let clock = ClockModel {
+            offset_s: 0.25,
+            drift: 0.0,
+            jitter_s: 0.001,
+            seed: 7,
+            ..Default::default()
+        };
+        let sensor = MockSensor::new(
+            "test_gnss".to_string(),
+            SensorType::Gnss,
+            MockSensorConfig {
+                frequency_hz: 200.0,
+                clock,
+                ground_truth: Some(log.clone()),
+                ..Default::default()
+            },
+        );
+
+        let packets = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let packets_clone = packets.clone();
+        sensor.listen(Arc::new(move |packet| {
+            packets_clone.lock().unwrap().push(packet);
+        }));
+        thread::sleep(Duration::from_millis(50));
+        sensor.stop();
+
+        let packets = packets.lock().unwrap();
+        assert!(!packets.is_empty());
+        for packet in packets.iter() {
+            let record = log.lookup("test_gnss", packet.frame_id.unwrap()).unwrap();
+            assert_eq!(
+                SimTime::from_secs_f64(record.reported_time),
+                packet.timestamp
+            );
+            let error = record.reported_time - record.true_time;
+            assert!((error - 0.25).abs() <= 0.001 + 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_mock_sensor_delays_delivery() {
+        let sensor = MockSensor::new(
+            "test_imu".to_string(),
+            SensorType::Imu,
+            MockSensorConfig {
+                frequency_hz: 100.0,
+                clock: ClockModel {
+                    latency_s: 0.06,
+                    latency_jitter_s: 0.01,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        let arrivals = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let arrivals_clone = arrivals.clone();
+        let start = Instant::now();
+        sensor.listen(Arc::new(move |packet| {
+            arrivals_clone
+                .lock()
+                .unwrap()
+                .push((start.elapsed(), packet.timestamp));
+        }));
+        thread::sleep(Duration::from_millis(30));
+        // Nothing captured so far is due yet
+        assert!(arrivals.lock().unwrap().is_empty());
+        sensor.stop();
+
+        // Pending packets are still delivered after the sensor stops
+        thread::sleep(Duration::from_millis(150));
+        let arrivals = arrivals.lock().unwrap();
+        assert!(!arrivals.is_empty());
+        assert!(arrivals
+            .iter()
+            .all(|(elapsed, _)| *elapsed >= Duration::from_millis(50)));
+        assert!(sensor.ordering_tolerance().out_of_order);
+    }
+
     #[test]
     fn test_mock_sensor_idempotent_listen() {
         let sensor = MockSensor::with_defaults("test".to_string(), SensorType::Camera);
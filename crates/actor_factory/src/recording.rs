@@ -0,0 +1,860 @@
+//! Recording reader - shared, indexed access to a recording directory
+//!
+//! `sensors.jsonl` is parsed once and indexed per sensor; every replay
+//! sensor reads from the same [`RecordingReader`]. Binary payloads are read
+//! ahead of playback by a background [`Prefetcher`] so file I/O stays off
+//! the timing thread.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use bytes::Bytes;
+use contracts::{
+    GnssData, ImageData, ImageFormat, ImuData, PointCloudData, RadarData, SensorPacket,
+    SensorPayload, SensorType, SimTime, Vector3,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::replay_sensor::{RecordingManifest, SensorMetadata};
+
+/// Records read ahead of the playback cursor by default
+pub const DEFAULT_PREFETCH_DEPTH: usize = 16;
+
+/// Recording format written by [`RecordingWriter`] (the manifest `version`).
+///
+/// Version 1 is the Python recorder; version 2 adds the camera pixel
+/// format and keeps each packet's own frame ID.
+pub const RECORDING_FORMAT_VERSION: u32 = 2;
+
+/// Sensor record in JSONL
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SensorRecord {
+    sensor_id: String,
+    sensor_type: String,
+    timestamp: f64,
+    frame_id: u64,
+
+    // Camera fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data_file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<ImageFormat>,
+
+    // LiDAR fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_points: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    point_stride: Option<u32>,
+
+    // IMU fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    accelerometer: Option<[f64; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    gyroscope: Option<[f64; 3]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compass: Option<f64>,
+
+    // GNSS fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    longitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    altitude: Option<f64>,
+
+    // Radar fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_detections: Option<u32>,
+}
+
+struct RecordingInner {
+    root: PathBuf,
+    /// All records, sorted by timestamp
+    records: Vec<SensorRecord>,
+    /// Record indices per sensor ID, in timestamp order
+    index: HashMap<String, Arc<[usize]>>,
+}
+
+/// Parsed and indexed recording directory, cheap to clone
+#[derive(Clone)]
+pub struct RecordingReader {
+    inner: Arc<RecordingInner>,
+}
+
+impl RecordingReader {
+    /// Parse `sensors.jsonl` in a recording directory
+    pub fn open(root: &Path) -> std::io::Result<Self> {
+        let file = File::open(root.join("sensors.jsonl"))?;
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let record: SensorRecord = serde_json::from_str(&line)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            if let Some(data_file) = &record.data_file {
+                if !is_contained(Path::new(data_file)) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("data file '{}' is outside the recording", data_file),
+                    ));
+                }
+            }
+            records.push(record);
+        }
+        records.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+        let mut index: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, record) in records.iter().enumerate() {
+            index.entry(record.sensor_id.clone()).or_default().push(i);
+        }
+
+        info!(
+            path = %root.display(),
+            records = records.len(),
+            sensors = index.len(),
+            "Indexed recording"
+        );
+
+        Ok(Self {
+            inner: Arc::new(RecordingInner {
+                root: root.to_path_buf(),
+                records,
+                index: index
+                    .into_iter()
+                    .map(|(sensor_id, indices)| (sensor_id, indices.into()))
+                    .collect(),
+            }),
+        })
+    }
+
+    /// Recording directory
+    pub fn root(&self) -> &Path {
+        &self.inner.root
+    }
+
+    /// Total number of records
+    pub fn len(&self) -> usize {
+        self.inner.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.records.is_empty()
+    }
+
+    /// Timestamps of every record, in order
+    pub fn timestamps(&self) -> impl Iterator<Item = f64> + '_ {
+        self.inner.records.iter().map(|record| record.timestamp)
+    }
+
+    /// IDs of the sensors present in the recording
+    pub fn sensor_ids(&self) -> impl Iterator<Item = &str> {
+        self.inner.index.keys().map(String::as_str)
+    }
+
+    /// Sensor type as written in the records of `sensor_id`
+    pub fn recorded_type(&self, sensor_id: &str) -> Option<&str> {
+        let first = *self.inner.index.get(sensor_id)?.first()?;
+        Some(&self.inner.records[first].sensor_type)
+    }
+
+    /// Number of records of `sensor_id`
+    pub fn record_count(&self, sensor_id: &str) -> usize {
+        self.inner
+            .index
+            .get(sensor_id)
+            .map_or(0, |indices| indices.len())
+    }
+
+    /// Recording rate (Hz) of `sensor_id` from the median interval between
+    /// its records
+    pub fn frequency_hz(&self, sensor_id: &str) -> Option<f64> {
+        let indices = self.inner.index.get(sensor_id)?;
+        let records = &self.inner.records;
+        let mut intervals: Vec<f64> = indices
+            .windows(2)
+            .map(|pair| records[pair[1]].timestamp - records[pair[0]].timestamp)
+            .filter(|interval| *interval > 0.0)
+            .collect();
+        if intervals.is_empty() {
+            return None;
+        }
+        intervals.sort_by(f64::total_cmp);
+        Some(1.0 / intervals[intervals.len() / 2])
+    }
+
+    /// Records of one sensor; empty if the sensor was not recorded
+    pub fn track(&self, sensor_id: &str, sensor_type: SensorType) -> SensorTrack {
+        SensorTrack {
+            reader: self.clone(),
+            sensor_id: sensor_id.into(),
+            sensor_type,
+            records: self
+                .inner
+                .index
+                .get(sensor_id)
+                .cloned()
+                .unwrap_or_else(|| Arc::from([])),
+        }
+    }
+}
+
+impl std::fmt::Debug for RecordingReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingReader")
+            .field("root", &self.inner.root)
+            .field("records", &self.inner.records.len())
+            .field("sensors", &self.inner.index.len())
+            .finish()
+    }
+}
+
+/// Records of one sensor in timestamp order, addressed by position
+#[derive(Clone)]
+pub struct SensorTrack {
+    reader: RecordingReader,
+    sensor_id: Arc<str>,
+    sensor_type: SensorType,
+    records: Arc<[usize]>,
+}
+
+impl SensorTrack {
+    pub fn sensor_id(&self) -> &str {
+        &self.sensor_id
+    }
+
+    pub fn sensor_type(&self) -> SensorType {
+        self.sensor_type
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn record(&self, position: usize) -> &SensorRecord {
+        &self.reader.inner.records[self.records[position]]
+    }
+
+    /// Recorded timestamp (s) of the record at `position`
+    pub fn timestamp(&self, position: usize) -> f64 {
+        self.record(position).timestamp
+    }
+
+    /// Position of the first record at or after `timestamp`
+    pub fn locate(&self, timestamp: f64) -> usize {
+        self.records
+            .partition_point(|&i| self.reader.inner.records[i].timestamp < timestamp)
+    }
+
+    /// Binary payload file of the record, if its payload lives in one.
+    /// Data files are checked to stay inside the root when the recording
+    /// is opened.
+    fn data_path(&self, position: usize) -> Option<PathBuf> {
+        match self.sensor_type {
+            SensorType::Camera | SensorType::Lidar | SensorType::Radar => {
+                let data_file = self.record(position).data_file.as_ref()?;
+                Some(self.reader.root().join(data_file))
+            }
+            SensorType::Imu | SensorType::Gnss => None,
+        }
+    }
+
+    /// Read the binary payload of the record synchronously
+    pub fn read_data(&self, position: usize) -> Option<Bytes> {
+        let path = self.data_path(position)?;
+        match std::fs::read(&path) {
+            Ok(data) => Some(Bytes::from(data)),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to read binary file");
+                None
+            }
+        }
+    }
+
+    /// Read the record at `position` into a packet
+    pub fn packet(&self, position: usize) -> Option<SensorPacket> {
+        self.build_packet(position, self.read_data(position))
+    }
+
+    /// Build the packet for the record at `position` from its binary payload
+    fn build_packet(&self, position: usize, data: Option<Bytes>) -> Option<SensorPacket> {
+        let record = self.record(position);
+        let payload = match self.sensor_type {
+            SensorType::Camera => SensorPayload::Image(ImageData {
+                width: record.width.unwrap_or(0),
+                height: record.height.unwrap_or(0),
+                format: record.format.unwrap_or(ImageFormat::Bgra8),
+                data: data?,
+            }),
+            SensorType::Lidar => SensorPayload::PointCloud(PointCloudData {
+                num_points: record.num_points.unwrap_or(0),
+                point_stride: record.point_stride.unwrap_or(16),
+                data: data?,
+            }),
+            SensorType::Radar => SensorPayload::Radar(RadarData {
+                num_detections: record.num_detections.unwrap_or(0),
+                data: data?,
+            }),
+            SensorType::Imu => {
+                let accel = record.accelerometer?;
+                let gyro = record.gyroscope?;
+                SensorPayload::Imu(ImuData {
+                    accelerometer: Vector3 {
+                        x: accel[0],
+                        y: accel[1],
+                        z: accel[2],
+                    },
+                    gyroscope: Vector3 {
+                        x: gyro[0],
+                        y: gyro[1],
+                        z: gyro[2],
+                    },
+                    compass: record.compass.unwrap_or(0.0),
+                })
+            }
+            SensorType::Gnss => SensorPayload::Gnss(GnssData {
+                latitude: record.latitude?,
+                longitude: record.longitude?,
+                altitude: record.altitude?,
+            }),
+        };
+
+        Some(SensorPacket {
+            sensor_id: self.sensor_id.as_ref().into(),
+            sensor_type: self.sensor_type,
+            timestamp: SimTime::try_from_secs_f64(record.timestamp)?,
+            frame_id: Some(record.frame_id),
+            payload,
+        })
+    }
+}
+
+impl std::fmt::Debug for SensorTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SensorTrack")
+            .field("sensor_id", &self.sensor_id)
+            .field("sensor_type", &self.sensor_type)
+            .field("records", &self.records.len())
+            .finish()
+    }
+}
+
+#[derive(Default)]
+struct PrefetchState {
+    /// Next position the consumer will ask for
+    cursor: usize,
+    /// Payloads read ahead, by track position
+    ready: BTreeMap<usize, Option<Bytes>>,
+    /// Position being read by the worker
+    loading: Option<usize>,
+    stopped: bool,
+}
+
+struct PrefetchShared {
+    state: Mutex<PrefetchState>,
+    changed: Condvar,
+}
+
+impl PrefetchShared {
+    fn lock(&self) -> MutexGuard<'_, PrefetchState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Reads binary payloads of a track ahead of the playback cursor
+/// on a background thread.
+///
+/// Only payloads within `depth` records of the cursor are kept; a request
+/// outside that window (e.g. after a seek) is read synchronously.
+pub struct Prefetcher {
+    track: SensorTrack,
+    shared: Arc<PrefetchShared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Prefetcher {
+    pub fn spawn(track: SensorTrack, depth: usize) -> Self {
+        let shared = Arc::new(PrefetchShared {
+            state: Mutex::new(PrefetchState::default()),
+            changed: Condvar::new(),
+        });
+        let has_data = (0..track.len()).any(|position| track.data_path(position).is_some());
+        let worker = (has_data && depth > 0).then(|| {
+            let track = track.clone();
+            let shared = shared.clone();
+            thread::spawn(move || prefetch_loop(&track, &shared, depth))
+        });
+        Self {
+            track,
+            shared,
+            worker,
+        }
+    }
+
+    pub fn track(&self) -> &SensorTrack {
+        &self.track
+    }
+
+    /// Packet for the record at `position`, and move the cursor past it
+    pub fn packet(&self, position: usize) -> Option<SensorPacket> {
+        if self.worker.is_none() {
+            return self.track.packet(position);
+        }
+
+        let mut state = self.shared.lock();
+        // The worker may be reading exactly this payload right now
+        while state.loading == Some(position) {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+        let data = state.ready.remove(&position);
+        state.cursor = position + 1;
+        drop(state);
+        self.shared.changed.notify_all();
+
+        let data = match data {
+            Some(data) => data,
+            None => {
+                debug!(sensor_id = %self.track.sensor_id(), position, "Prefetch miss");
+                metrics::counter!(
+                    "replay_prefetch_misses_total",
+                    "sensor_id" => self.track.sensor_id().to_string()
+                )
+                .increment(1);
+                self.track.read_data(position)
+            }
+        };
+        self.track.build_packet(position, data)
+    }
+}
+
+impl Drop for Prefetcher {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.changed.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn prefetch_loop(track: &SensorTrack, shared: &PrefetchShared, depth: usize) {
+    let mut state = shared.lock();
+    loop {
+        if state.stopped {
+            return;
+        }
+
+        // Forget payloads that fell out of the window
+        let window = state.cursor..(state.cursor + depth).min(track.len());
+        state.ready.retain(|position, _| window.contains(position));
+
+        let wanted = window.clone().find(|position| {
+            !state.ready.contains_key(position) && track.data_path(*position).is_some()
+        });
+        let Some(position) = wanted else {
+            state = shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+            continue;
+        };
+
+        state.loading = Some(position);
+        drop(state);
+        let data = track.read_data(position);
+        state = shared.lock();
+        state.loading = None;
+        if position >= state.cursor {
+            state.ready.insert(position, data);
+        }
+        shared.changed.notify_all();
+    }
+}
+
+/// Writes packets in the layout read by [`RecordingReader`]: `sensors.jsonl`,
+/// one `.bin` file per camera, LiDAR or radar frame, and `manifest.json`
+/// once [`finish`](Self::finish) is called.
+pub struct RecordingWriter {
+    root: PathBuf,
+    jsonl: BufWriter<File>,
+    carla_version: String,
+    sensors: BTreeMap<String, SensorMetadata>,
+    /// First and last recorded timestamp
+    span: Option<(f64, f64)>,
+}
+
+impl RecordingWriter {
+    /// Start a recording in `root`, which must not already hold one
+    pub fn create(root: &Path, carla_version: impl Into<String>) -> std::io::Result<Self> {
+        std::fs::create_dir_all(root)?;
+        let jsonl = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(root.join("sensors.jsonl"))?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            jsonl: BufWriter::new(jsonl),
+            carla_version: carla_version.into(),
+            sensors: BTreeMap::new(),
+            span: None,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Packets written so far, per sensor
+    pub fn frame_counts(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.sensors
+            .iter()
+            .map(|(sensor_id, metadata)| (sensor_id.as_str(), metadata.frame_count))
+    }
+
+    /// Append one packet
+    pub fn write(&mut self, packet: &SensorPacket) -> std::io::Result<()> {
+        let sensor_id = packet.sensor_id.as_str();
+        // The sensor ID names the directory holding its binary payloads
+        if !is_file_name(sensor_id) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "sensor ID '{}' cannot be used as a directory name",
+                    sensor_id
+                ),
+            ));
+        }
+        let metadata = self
+            .sensors
+            .entry(sensor_id.to_string())
+            .or_insert_with(|| SensorMetadata {
+                sensor_type: sensor_type_name(packet.sensor_type).to_string(),
+                frame_count: 0,
+            });
+        let count = metadata.frame_count + 1;
+
+        let timestamp = packet.timestamp.as_secs_f64();
+        let mut record = SensorRecord {
+            sensor_id: sensor_id.to_string(),
+            sensor_type: metadata.sensor_type.clone(),
+            timestamp,
+            frame_id: packet.frame_id.unwrap_or(count),
+            ..Default::default()
+        };
+        match &packet.payload {
+            SensorPayload::Image(image) => {
+                record.data_file = Some(self.write_data(sensor_id, count, &image.data)?);
+                record.width = Some(image.width);
+                record.height = Some(image.height);
+                record.format = Some(image.format);
+            }
+            SensorPayload::PointCloud(cloud) => {
+                record.data_file = Some(self.write_data(sensor_id, count, &cloud.data)?);
+                record.num_points = Some(cloud.num_points);
+                record.point_stride = Some(cloud.point_stride);
+            }
+            SensorPayload::Radar(radar) => {
+                record.data_file = Some(self.write_data(sensor_id, count, &radar.data)?);
+                record.num_detections = Some(radar.num_detections);
+            }
+            SensorPayload::Imu(imu) => {
+                let (a, g) = (&imu.accelerometer, &imu.gyroscope);
+                record.accelerometer = Some([a.x, a.y, a.z]);
+                record.gyroscope = Some([g.x, g.y, g.z]);
+                record.compass = Some(imu.compass);
+            }
+            SensorPayload::Gnss(gnss) => {
+                record.latitude = Some(gnss.latitude);
+                record.longitude = Some(gnss.longitude);
+                record.altitude = Some(gnss.altitude);
+            }
+            SensorPayload::Raw(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("sensor '{}': raw payloads cannot be recorded", sensor_id),
+                ));
+            }
+        }
+
+        serde_json::to_writer(&mut self.jsonl, &record)?;
+        self.jsonl.write_all(b"\n")?;
+
+        if let Some(metadata) = self.sensors.get_mut(sensor_id) {
+            metadata.frame_count = count;
+        }
+        self.span = Some(match self.span {
+            Some((first, last)) => (first.min(timestamp), last.max(timestamp)),
+            None => (timestamp, timestamp),
+        });
+        Ok(())
+    }
+
+    /// Write a binary payload, returning its path relative to the root
+    fn write_data(&self, sensor_id: &str, count: u64, data: &[u8]) -> std::io::Result<String> {
+        let relative = format!("{}/frame_{:06}.bin", sensor_id, count);
+        let path = self.root.join(&relative);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, data)?;
+        Ok(relative)
+    }
+
+    /// Flush all records and write `manifest.json`
+    pub fn finish(mut self) -> std::io::Result<RecordingManifest> {
+        self.jsonl.flush()?;
+        let manifest = RecordingManifest {
+            version: RECORDING_FORMAT_VERSION.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            carla_version: self.carla_version,
+            duration_sec: self.span.map_or(0.0, |(first, last)| last - first),
+            sensors: self.sensors.into_iter().collect(),
+        };
+        let content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(self.root.join("manifest.json"), content)?;
+
+        info!(
+            path = %self.root.display(),
+            sensors = manifest.sensors.len(),
+            duration_sec = manifest.duration_sec,
+            "Recording written"
+        );
+        Ok(manifest)
+    }
+}
+
+/// Whether `path` is relative and cannot leave the directory it is joined to
+fn is_contained(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Whether `name` is a single plain path component
+fn is_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(n)) if n == name)
+        && components.next().is_none()
+}
+
+/// Sensor type as written in records and manifests
+fn sensor_type_name(sensor_type: SensorType) -> &'static str {
+    match sensor_type {
+        SensorType::Camera => "camera",
+        SensorType::Lidar => "lidar",
+        SensorType::Imu => "imu",
+        SensorType::Gnss => "gnss",
+        SensorType::Radar => "radar",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_recording(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("recording_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(dir.join("lidar")).unwrap();
+        let mut file = File::create(dir.join("sensors.jsonl")).unwrap();
+        for frame in 0..20u64 {
+            let timestamp = 1.0 + frame as f64 * 0.05;
+            writeln!(
+                file,
+                r#"{{"sensor_id":"imu","sensor_type":"imu","timestamp":{timestamp},"frame_id":{frame},"accelerometer":[0,0,9.8],"gyroscope":[0,0,0],"compass":0}}"#
+            )
+            .unwrap();
+            if frame % 2 == 0 {
+                let data_file = format!("lidar/{frame}.bin");
+                std::fs::write(dir.join(&data_file), vec![frame as u8; 32]).unwrap();
+                writeln!(
+                    file,
+                    r#"{{"sensor_id":"lidar","sensor_type":"lidar","timestamp":{timestamp},"frame_id":{frame},"data_file":"{data_file}","num_points":2,"point_stride":16}}"#
+                )
+                .unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn test_index_per_sensor() {
+        let dir = write_recording("index");
+        let reader = RecordingReader::open(&dir).unwrap();
+        assert_eq!(reader.len(), 30);
+
+        let lidar = reader.track("lidar", SensorType::Lidar);
+        assert_eq!(lidar.len(), 10);
+        assert!((lidar.timestamp(1) - 1.1).abs() < 1e-9);
+        assert_eq!(lidar.locate(1.12), 2);
+        assert!((reader.frequency_hz("lidar").unwrap() - 10.0).abs() < 1e-6);
+        assert_eq!(reader.recorded_type("lidar"), Some("lidar"));
+        assert!(reader.track("camera", SensorType::Camera).is_empty());
+
+        let packet = lidar.packet(3).unwrap();
+        assert_eq!(packet.frame_id, Some(6));
+        match packet.payload {
+            SensorPayload::PointCloud(pc) => assert_eq!(pc.data.as_ref(), &[6u8; 32]),
+            other => panic!("unexpected payload {other:?}"),
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prefetcher_matches_direct_reads_across_seeks() {
+        let dir = write_recording("prefetch");
+        let reader = RecordingReader::open(&dir).unwrap();
+        let track = reader.track("lidar", SensorType::Lidar);
+        let prefetcher = Prefetcher::spawn(track.clone(), 4);
+
+        // Sequential playback, a backward seek, then a forward skip
+        for position in (0..6).chain(1..3).chain(8..10) {
+            let fetched = prefetcher.packet(position).unwrap();
+            let direct = track.packet(position).unwrap();
+            assert_eq!(fetched.frame_id, direct.frame_id);
+            match (fetched.payload, direct.payload) {
+                (SensorPayload::PointCloud(a), SensorPayload::PointCloud(b)) => {
+                    assert_eq!(a.data, b.data)
+                }
+                other => panic!("unexpected payloads {other:?}"),
+            }
+        }
+        // Payload-less sensors build packets without a worker
+        let imu = Prefetcher::spawn(reader.track("imu", SensorType::Imu), 4);
+        assert!(imu.worker.is_none());
+        assert!(imu.packet(0).is_some());
+
+        drop(prefetcher);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_writer_round_trip() {
+        use contracts::{GnssData, ImageData, RadarData};
+
+        let dir = std::env::temp_dir().join(format!("recording_writer_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let packet = |sensor_id: &str, sensor_type, t: f64, payload| SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type,
+            timestamp: SimTime::from_secs_f64(t),
+            frame_id: Some((t * 100.0).round() as u64),
+            payload,
+        };
+        let packets = vec![
+            packet(
+                "front",
+                SensorType::Camera,
+                1.0,
+                SensorPayload::Image(ImageData {
+                    width: 2,
+                    height: 1,
+                    format: ImageFormat::Rgb8,
+                    data: Bytes::from_static(&[1, 2, 3, 4, 5, 6]),
+                }),
+            ),
+            packet(
+                "radar",
+                SensorType::Radar,
+                1.02,
+                SensorPayload::Radar(RadarData {
+                    num_detections: 1,
+                    data: Bytes::from_static(&[7; 16]),
+                }),
+            ),
+            packet(
+                "gps",
+                SensorType::Gnss,
+                1.05,
+                SensorPayload::Gnss(GnssData {
+                    latitude: 48.1,
+                    longitude: 11.5,
+                    altitude: 520.0,
+                }),
+            ),
+        ];
+
+        let mut writer = RecordingWriter::create(&dir, "mock").unwrap();
+        packets.iter().for_each(|p| writer.write(p).unwrap());
+        let manifest = writer.finish().unwrap();
+        assert_eq!(manifest.format_version(), Some(RECORDING_FORMAT_VERSION));
+        assert!((manifest.duration_sec - 0.05).abs() < 1e-9);
+        // An existing recording is never overwritten
+        assert!(RecordingWriter::create(&dir, "mock").is_err());
+
+        let manifest = RecordingManifest::load(&dir).unwrap();
+        assert_eq!(manifest.sensors["radar"].sensor_type, "radar");
+        assert_eq!(manifest.sensors["radar"].frame_count, 1);
+        let reader = RecordingReader::open(&dir).unwrap();
+        for original in &packets {
+            let replayed = reader
+                .track(original.sensor_id.as_str(), original.sensor_type)
+                .packet(0)
+                .unwrap();
+            assert_eq!(replayed.timestamp, original.timestamp);
+            assert_eq!(replayed.frame_id, original.frame_id);
+            assert_eq!(
+                serde_json::to_value(&replayed.payload).unwrap(),
+                serde_json::to_value(&original.payload).unwrap()
+            );
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_paths_stay_inside_recording() {
+        let dir = std::env::temp_dir().join(format!("recording_paths_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut writer = RecordingWriter::create(&dir, "mock").unwrap();
+        for sensor_id in ["../escape", "a/b", "..", ""] {
+            let packet = SensorPacket {
+                sensor_id: sensor_id.into(),
+                sensor_type: SensorType::Radar,
+                timestamp: SimTime::from_secs_f64(1.0),
+                frame_id: None,
+                payload: SensorPayload::Radar(RadarData {
+                    num_detections: 0,
+                    data: Bytes::new(),
+                }),
+            };
+            let err = writer.write(&packet).unwrap_err();
+            assert_eq!(
+                err.kind(),
+                std::io::ErrorKind::InvalidInput,
+                "{sensor_id:?}"
+            );
+        }
+        assert_eq!(writer.frame_counts().count(), 0);
+        writer.finish().unwrap();
+        assert!(!dir.join("../escape").exists());
+
+        for data_file in ["../outside.bin", "/etc/hostname", "lidar/../../x.bin"] {
+            let record = format!(
+                r#"{{"sensor_id":"lidar","sensor_type":"lidar","timestamp":1.0,"frame_id":1,"data_file":"{data_file}","num_points":0,"point_stride":16}}"#
+            );
+            std::fs::write(dir.join("sensors.jsonl"), record + "\n").unwrap();
+            let err = RecordingReader::open(&dir).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData, "{data_file}");
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
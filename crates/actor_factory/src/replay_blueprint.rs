@@ -0,0 +1,389 @@
+//! Replay blueprint - derive a `WorldBlueprint` from a recording, or check
+//! a configured one against it
+//!
+//! Sensors are listed by `manifest.json` and `sensors.jsonl`; frequencies are
+//! inferred from record timestamps.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use contracts::{
+    Location, MemoryConfig, Rotation, SensorConfig, SensorType, SinkConfig, SinkType, SyncConfig,
+    Transform, VehicleConfig, WorldBlueprint, WorldConfig,
+};
+use tracing::warn;
+
+use crate::error::RecordingError;
+use crate::recording::RecordingReader;
+use crate::replay_sensor::RecordingManifest;
+
+/// Largest relative difference between configured and recorded frequency
+/// before a warning
+const FREQUENCY_TOLERANCE: f64 = 0.25;
+
+/// Vehicle carrying every sensor of a derived blueprint
+const REPLAY_VEHICLE_ID: &str = "ego";
+
+/// Sensor found in a recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedSensor {
+    pub id: String,
+    /// Type as recorded (e.g. `lidar` or `sensor.lidar.ray_cast`)
+    pub type_name: String,
+    /// None if the type name is not recognised
+    pub sensor_type: Option<SensorType>,
+    pub records: usize,
+    /// From record timestamps, else from the manifest frame count
+    pub frequency_hz: Option<f64>,
+}
+
+/// Sensors of a recording directory
+#[derive(Debug, Clone)]
+pub struct RecordingSummary {
+    pub root: PathBuf,
+    /// Sorted by sensor ID
+    pub sensors: Vec<RecordedSensor>,
+}
+
+impl RecordingSummary {
+    /// Summarize a recording directory; `manifest.json` is optional
+    pub fn load(root: &Path) -> Result<Self, RecordingError> {
+        let io = |source| RecordingError::Io {
+            path: root.to_path_buf(),
+            source,
+        };
+        let recording = RecordingReader::open(root).map_err(io)?;
+        let manifest = match RecordingManifest::load(root) {
+            Ok(manifest) => Some(manifest),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(io(e)),
+        };
+        Ok(Self::from_recording(&recording, manifest.as_ref()))
+    }
+
+    pub fn from_recording(
+        recording: &RecordingReader,
+        manifest: Option<&RecordingManifest>,
+    ) -> Self {
+        let mut ids: BTreeSet<&str> = recording.sensor_ids().collect();
+        if let Some(manifest) = manifest {
+            ids.extend(manifest.sensors.keys().map(String::as_str));
+        }
+
+        let sensors = ids
+            .into_iter()
+            .map(|id| {
+                let metadata = manifest.and_then(|manifest| manifest.sensors.get(id));
+                let type_name = metadata
+                    .map(|metadata| metadata.sensor_type.as_str())
+                    .or_else(|| recording.recorded_type(id))
+                    .unwrap_or_default()
+                    .to_string();
+                let frequency_hz = recording.frequency_hz(id).or_else(|| {
+                    let duration = manifest?.duration_sec;
+                    let frames = metadata?.frame_count;
+                    (duration > 0.0 && frames > 1).then(|| frames as f64 / duration)
+                });
+                RecordedSensor {
+                    id: id.to_string(),
+                    sensor_type: parse_sensor_type(&type_name),
+                    type_name,
+                    records: recording.record_count(id),
+                    frequency_hz,
+                }
+            })
+            .collect();
+
+        Self {
+            root: recording.root().to_path_buf(),
+            sensors,
+        }
+    }
+
+    fn sensor(&self, sensor_id: &str) -> Option<&RecordedSensor> {
+        self.sensors.iter().find(|sensor| sensor.id == sensor_id)
+    }
+
+    /// Blueprint replaying every usable recorded sensor on one vehicle.
+    ///
+    /// Sensors with an unknown type, no records or no inferable frequency
+    /// are skipped. The primary sensor is the first camera, else LiDAR,
+    /// radar, GNSS, IMU.
+    pub fn to_blueprint(&self) -> Result<WorldBlueprint, RecordingError> {
+        let mut sensors = Vec::new();
+        for recorded in &self.sensors {
+            let (Some(sensor_type), Some(frequency_hz), true) = (
+                recorded.sensor_type,
+                recorded.frequency_hz,
+                recorded.records > 0,
+            ) else {
+                warn!(
+                    sensor_id = %recorded.id,
+                    sensor_type = %recorded.type_name,
+                    records = recorded.records,
+                    "Recorded sensor cannot be replayed, skipping"
+                );
+                continue;
+            };
+            sensors.push(SensorConfig {
+                id: recorded.id.clone(),
+                sensor_type,
+                transform: Transform {
+                    location: Location {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                    rotation: Rotation {
+                        pitch: 0.0,
+                        yaw: 0.0,
+                        roll: 0.0,
+                    },
+                },
+                frequency_hz,
+                attributes: HashMap::new(),
+                mock_clock: None,
+                ingestion: None,
+                preprocess: Vec::new(),
+                mock_faults: None,
+                source: Default::default(),
+            });
+        }
+
+        let primary = [
+            SensorType::Camera,
+            SensorType::Lidar,
+            SensorType::Radar,
+            SensorType::Gnss,
+            SensorType::Imu,
+        ]
+        .iter()
+        .find_map(|sensor_type| sensors.iter().find(|s| s.sensor_type == *sensor_type))
+        .ok_or_else(|| RecordingError::Empty(self.root.clone()))?
+        .id
+        .clone();
+
+        Ok(WorldBlueprint {
+            version: Default::default(),
+            world: WorldConfig::new("replay"),
+            vehicles: vec![VehicleConfig {
+                id: REPLAY_VEHICLE_ID.to_string(),
+                blueprint: "vehicle.tesla.model3".to_string(),
+                spawn_point: None,
+                sensors,
+            }],
+            sync: SyncConfig::new(primary),
+            sinks: vec![SinkConfig {
+                name: "console_log".to_string(),
+                sink_type: SinkType::Log,
+                queue_capacity: 100,
+                params: HashMap::new(),
+            }],
+            memory: MemoryConfig::default(),
+        })
+    }
+
+    /// Check that `blueprint` can be replayed from this recording.
+    ///
+    /// Errors list every disagreement that would break the replay: missing
+    /// or empty sensors the sync engine needs, and type mismatches. Softer
+    /// disagreements are returned as warnings.
+    pub fn check(&self, blueprint: &WorldBlueprint) -> Result<Vec<String>, RecordingError> {
+        let needed: HashSet<String> = blueprint
+            .to_sync_group_configs()
+            .into_iter()
+            .flat_map(|config| {
+                std::iter::once(config.reference_sensor_id)
+                    .chain(config.required_sensors)
+                    .map(|id| id.as_str().to_string())
+            })
+            .collect();
+        let recorded_ids: Vec<&str> = self
+            .sensors
+            .iter()
+            .map(|sensor| sensor.id.as_str())
+            .collect();
+
+        let mut problems = Vec::new();
+        let mut warnings = Vec::new();
+        let mut configured = HashSet::new();
+        for sensor in blueprint
+            .vehicles
+            .iter()
+            .flat_map(|vehicle| vehicle.sensors.iter())
+            .filter(|sensor| !sensor.source.is_external())
+        {
+            configured.insert(sensor.id.as_str());
+            let is_needed = needed.contains(&sensor.id);
+            let Some(recorded) = self.sensor(&sensor.id) else {
+                let message = format!(
+                    "sensor '{}' is not in the recording (recorded: {})",
+                    sensor.id,
+                    recorded_ids.join(", ")
+                );
+                if is_needed {
+                    problems.push(message);
+                } else {
+                    warnings.push(message);
+                }
+                continue;
+            };
+
+            if recorded.sensor_type != Some(sensor.sensor_type) {
+                problems.push(format!(
+                    "sensor '{}' is configured as {:?} but recorded as '{}'",
+                    sensor.id, sensor.sensor_type, recorded.type_name
+                ));
+                continue;
+            }
+            if recorded.records == 0 {
+                let message = format!("sensor '{}' has no records", sensor.id);
+                if is_needed {
+                    problems.push(message);
+                } else {
+                    warnings.push(message);
+                }
+                continue;
+            }
+            if let Some(recorded_hz) = recorded.frequency_hz {
+                if (sensor.frequency_hz - recorded_hz).abs() > FREQUENCY_TOLERANCE * recorded_hz {
+                    warnings.push(format!(
+                        "sensor '{}' is configured at {} Hz but recorded at {:.1} Hz",
+                        sensor.id, sensor.frequency_hz, recorded_hz
+                    ));
+                }
+            }
+        }
+
+        for recorded in &self.sensors {
+            if !configured.contains(recorded.id.as_str()) {
+                warnings.push(format!(
+                    "recorded sensor '{}' is not configured and will be ignored",
+                    recorded.id
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(RecordingError::Mismatch {
+                path: self.root.clone(),
+                problems,
+            })
+        }
+    }
+}
+
+/// Parse a recorded sensor type, either a blueprint type name (`lidar`) or a
+/// CARLA blueprint ID (`sensor.lidar.ray_cast`)
+pub fn parse_sensor_type(name: &str) -> Option<SensorType> {
+    let name = name.to_ascii_lowercase();
+    [
+        ("camera", SensorType::Camera),
+        ("lidar", SensorType::Lidar),
+        ("imu", SensorType::Imu),
+        ("gnss", SensorType::Gnss),
+        ("radar", SensorType::Radar),
+    ]
+    .into_iter()
+    .find(|(key, _)| name.split('.').any(|part| part == *key))
+    .map(|(_, sensor_type)| sensor_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_recording(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("replay_blueprint_{}_{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut file = std::fs::File::create(dir.join("sensors.jsonl")).unwrap();
+        for frame in 0..10u64 {
+            let timestamp = frame as f64 * 0.01;
+            writeln!(
+                file,
+                r#"{{"sensor_id":"imu","sensor_type":"sensor.other.imu","timestamp":{timestamp},"frame_id":{frame},"accelerometer":[0,0,9.8],"gyroscope":[0,0,0]}}"#
+            )
+            .unwrap();
+            if frame % 5 == 0 {
+                writeln!(
+                    file,
+                    r#"{{"sensor_id":"gnss","sensor_type":"gnss","timestamp":{timestamp},"frame_id":{frame},"latitude":0,"longitude":0,"altitude":0}}"#
+                )
+                .unwrap();
+            }
+        }
+        std::fs::write(
+            dir.join("manifest.json"),
+            r#"{"version":"1","created_at":"now","carla_version":"0.9.15","duration_sec":0.1,
+                "sensors":{"front":{"sensor_type":"sensor.camera.rgb","frame_count":0}}}"#,
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_sensor_type() {
+        assert_eq!(parse_sensor_type("lidar"), Some(SensorType::Lidar));
+        assert_eq!(
+            parse_sensor_type("sensor.camera.rgb"),
+            Some(SensorType::Camera)
+        );
+        assert_eq!(
+            parse_sensor_type("sensor.other.gnss"),
+            Some(SensorType::Gnss)
+        );
+        assert_eq!(parse_sensor_type("sensor.other.collision"), None);
+    }
+
+    #[test]
+    fn test_blueprint_from_recording() {
+        let dir = write_recording("derive");
+        let summary = RecordingSummary::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let ids: Vec<&str> = summary.sensors.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["front", "gnss", "imu"]);
+
+        let blueprint = summary.to_blueprint().unwrap();
+        // The camera has no records, so GNSS is the primary sensor
+        assert_eq!(blueprint.sync.primary_sensor_id, "gnss");
+        let sensors = &blueprint.vehicles[0].sensors;
+        assert_eq!(sensors.len(), 2);
+        let imu = sensors.iter().find(|s| s.id == "imu").unwrap();
+        assert_eq!(imu.sensor_type, SensorType::Imu);
+        assert!((imu.frequency_hz - 100.0).abs() < 1e-6);
+        assert!(summary.check(&blueprint).is_ok());
+    }
+
+    #[test]
+    fn test_check_reports_mismatches() {
+        let dir = write_recording("check");
+        let summary = RecordingSummary::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut blueprint = summary.to_blueprint().unwrap();
+        let sensors = &mut blueprint.vehicles[0].sensors;
+        sensors[0].id = "gps".to_string();
+        sensors[1].sensor_type = SensorType::Radar;
+        blueprint.sync.primary_sensor_id = "gps".to_string();
+
+        match summary.check(&blueprint) {
+            Err(RecordingError::Mismatch { problems, .. }) => {
+                assert_eq!(problems.len(), 2, "{problems:?}");
+                assert!(problems[0].contains("'gps' is not in the recording"));
+                assert!(problems[1].contains("recorded as 'sensor.other.imu'"));
+            }
+            other => panic!("expected mismatch, got {other:?}"),
+        }
+
+        // A slower configured rate is only a warning
+        let mut blueprint = summary.to_blueprint().unwrap();
+        blueprint.vehicles[0].sensors[1].frequency_hz = 20.0;
+        let warnings = summary.check(&blueprint).unwrap();
+        assert!(warnings.iter().any(|w| w.contains("recorded at 100.0 Hz")));
+    }
+}
@@ -0,0 +1,905 @@
+//! Replay clock and transport controls shared by all replay sensors
+//!
+//! Every `ReplaySensor` follows the same playback position, so inter-sensor
+//! timing matches the recording no matter when each sensor starts
+//! listening. The clock also implements the transport: a start/end range,
+//! seek, pause/resume, single-stepping by time or record, and an "as fast
+//! as possible" mode that merges sensors in timestamp order and holds back
+//! while a downstream gate reports backpressure.
+//!
+//! Replayed timestamps never go backwards: looping and backward seeks add a
+//! shift so downstream validation sees a continuous stream.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::recording::RecordingReader;
+use crate::replay_sensor::ReplayConfig;
+
+/// Lowest accepted playback speed
+const MIN_SPEED: f64 = 0.1;
+/// Longest a sensor sleeps before rechecking for stop requests
+const MAX_WAIT: Duration = Duration::from_millis(50);
+/// Poll interval while the downstream gate is closed
+const GATE_POLL: Duration = Duration::from_millis(2);
+
+/// Downstream readiness check for fast playback
+pub type ReplayGate = Arc<dyn Fn() -> bool + Send + Sync>;
+
+/// Snapshot of the transport state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayStatus {
+    /// Playback position (s after the first recorded timestamp)
+    pub position: f64,
+    /// Playback range (s after the first recorded timestamp)
+    pub range: (f64, f64),
+    pub paused: bool,
+    pub speed: f64,
+    /// As fast as downstream allows
+    pub fast: bool,
+    /// Completed loops
+    pub lap: u64,
+}
+
+impl fmt::Display for ReplayStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = if self.paused { "paused" } else { "playing" };
+        let rate = if self.fast {
+            "fast".to_string()
+        } else {
+            format!("{}x", self.speed)
+        };
+        write!(
+            f,
+            "{} at {:.3}s of [{:.3}, {:.3}] ({}, lap {})",
+            state, self.position, self.range.0, self.range.1, rate, self.lap
+        )
+    }
+}
+
+/// Command accepted by [`ReplayClock::apply`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayCommand {
+    Pause,
+    Resume,
+    /// Jump to a position (s after the first recorded timestamp)
+    Seek(f64),
+    /// Pause and advance by this much recording time (s)
+    Step(f64),
+    /// Pause and advance to the n-th next record
+    StepFrames(u64),
+    Speed(f64),
+    /// Toggle "as fast as possible" playback
+    Fast(bool),
+    Status,
+}
+
+impl FromStr for ReplayCommand {
+    type Err = String;
+
+    /// Parse a control line such as `seek 1620`, `step 0.1` or `next 5`
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let arg = words.next();
+        if words.next().is_some() {
+            return Err(format!("too many arguments: '{}'", line.trim()));
+        }
+        let number = |name: &str| -> Result<f64, String> {
+            let value = arg.ok_or_else(|| format!("'{}' needs a number", name))?;
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| format!("invalid number '{}'", value))
+        };
+        match command {
+            "pause" => Ok(Self::Pause),
+            "resume" | "play" => Ok(Self::Resume),
+            "seek" => number("seek").map(Self::Seek),
+            "step" => number("step").map(Self::Step),
+            "next" => match arg {
+                None => Ok(Self::StepFrames(1)),
+                Some(n) => n
+                    .parse()
+                    .map(Self::StepFrames)
+                    .map_err(|_| format!("invalid count '{}'", n)),
+            },
+            "speed" => number("speed").map(Self::Speed),
+            "fast" => match arg {
+                Some("on") => Ok(Self::Fast(true)),
+                Some("off") => Ok(Self::Fast(false)),
+                _ => Err("'fast' takes 'on' or 'off'".to_string()),
+            },
+            "status" => Ok(Self::Status),
+            other => Err(format!("unknown command '{}'", other)),
+        }
+    }
+}
+
+/// What a replay sensor should do with its next record
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Turn {
+    /// Send the record, adding `shift` to its timestamp
+    Emit { shift: f64 },
+    /// Playback jumped; continue from the first record at `position`
+    Relocate { epoch: u64, position: f64 },
+    /// No more records to play
+    Finished,
+    /// Sensor was stopped
+    Stopped,
+}
+
+struct Transport {
+    /// Playback position at `anchor`, or the fixed position while paused
+    position: f64,
+    anchor: Instant,
+    started: bool,
+    paused: bool,
+    speed: f64,
+    fast: bool,
+    /// Highest offset emitted since the last jump (position in fast mode)
+    emitted: f64,
+    /// Added to recording timestamps so replayed time never goes backwards
+    shift: f64,
+    /// Bumped on seeks and laps
+    epoch: u64,
+    /// Position each sensor restarts from after the last jump
+    epoch_position: f64,
+    lap: u64,
+    /// Next offset per joined sensor (None = done for this lap)
+    lanes: HashMap<usize, Option<f64>>,
+    next_lane: usize,
+    gate: Option<ReplayGate>,
+}
+
+impl Transport {
+    fn current(&self, now: Instant) -> f64 {
+        if self.paused || !self.started {
+            self.position
+        } else if self.fast {
+            self.emitted
+        } else {
+            self.position + now.saturating_duration_since(self.anchor).as_secs_f64() * self.speed
+        }
+    }
+
+    /// Highest offset that may be emitted now
+    fn limit(&self, now: Instant) -> f64 {
+        if self.fast && !self.paused {
+            f64::INFINITY
+        } else {
+            self.current(now)
+        }
+    }
+
+    /// Freeze the current position as the new anchor
+    fn rebase(&mut self, now: Instant) {
+        self.position = self.current(now);
+        self.emitted = self.position;
+        self.anchor = now;
+    }
+
+    /// Restart all sensors from `position`
+    fn jump(&mut self, position: f64) {
+        self.position = position;
+        self.emitted = position;
+        self.anchor = Instant::now();
+        self.epoch_position = position;
+        self.epoch += 1;
+        // No sensor may run ahead of one that has not relocated yet
+        for lane in self.lanes.values_mut() {
+            *lane = Some(position);
+        }
+    }
+}
+
+struct ReplayClockInner {
+    /// Earliest recording timestamp (s)
+    origin: f64,
+    /// Offsets of all records in the range, sorted
+    offsets: Vec<f64>,
+    /// Playback range (offsets)
+    start: f64,
+    end: f64,
+    /// Smallest interval between records, inserted between laps
+    gap: f64,
+    loop_playback: bool,
+    transport: Mutex<Transport>,
+    changed: Condvar,
+}
+
+/// Shared playback position and transport controls.
+///
+/// Cloning is cheap; all clones control the same playback.
+#[derive(Clone)]
+pub struct ReplayClock {
+    inner: Arc<ReplayClockInner>,
+}
+
+impl fmt::Debug for ReplayClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayClock")
+            .field("origin", &self.inner.origin)
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+impl ReplayClock {
+    /// Create a clock over the given recording timestamps
+    pub fn new(config: &ReplayConfig, timestamps: impl IntoIterator<Item = f64>) -> Self {
+        let mut timestamps: Vec<f64> = timestamps.into_iter().collect();
+        timestamps.sort_by(f64::total_cmp);
+        let origin = timestamps.first().copied().unwrap_or(0.0);
+        let last = timestamps.last().map_or(0.0, |t| t - origin);
+        let start = config.start_s.unwrap_or(0.0).clamp(0.0, last);
+        let end = config.end_s.unwrap_or(last).clamp(start, last);
+        let offsets: Vec<f64> = timestamps
+            .iter()
+            .map(|t| t - origin)
+            .filter(|o| (start..=end).contains(o))
+            .collect();
+        // One lap ends one (smallest) sample interval after the last record,
+        // so the next lap never repeats a timestamp
+        let gap = offsets
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|dt| *dt > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        let gap = if gap.is_finite() { gap } else { 1.0 };
+        Self {
+            inner: Arc::new(ReplayClockInner {
+                origin,
+                offsets,
+                start,
+                end,
+                gap,
+                loop_playback: config.loop_playback,
+                transport: Mutex::new(Transport {
+                    position: start,
+                    anchor: Instant::now(),
+                    started: false,
+                    paused: config.start_paused,
+                    speed: config.speed_multiplier.max(MIN_SPEED),
+                    fast: config.fast,
+                    emitted: start,
+                    shift: 0.0,
+                    epoch: 0,
+                    epoch_position: start,
+                    lap: 0,
+                    lanes: HashMap::new(),
+                    next_lane: 0,
+                    gate: None,
+                }),
+                changed: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Create a clock over every record of a recording directory
+    pub fn load(replay_path: &Path, config: &ReplayConfig) -> std::io::Result<Self> {
+        Ok(Self::new(
+            config,
+            RecordingReader::open(replay_path)?.timestamps(),
+        ))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Transport> {
+        self.inner
+            .transport
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply a change and wake waiting sensors
+    fn update<T>(&self, f: impl FnOnce(&mut Transport) -> T) -> T {
+        let result = f(&mut self.lock());
+        self.inner.changed.notify_all();
+        result
+    }
+
+    /// Earliest recording timestamp (s); positions are relative to it
+    pub fn origin(&self) -> f64 {
+        self.inner.origin
+    }
+
+    /// Recording time covered by one lap (s)
+    pub fn period(&self) -> f64 {
+        self.inner.end - self.inner.start + self.inner.gap
+    }
+
+    /// Whether playback restarts after the end of the range
+    pub fn loop_playback(&self) -> bool {
+        self.inner.loop_playback
+    }
+
+    /// Replayed sim time (s) at the playback position, including the shift
+    /// added on laps and backward seeks; None before playback starts
+    pub fn sim_time(&self) -> Option<f64> {
+        let transport = self.lock();
+        transport
+            .started
+            .then(|| self.inner.origin + transport.shift + transport.current(Instant::now()))
+    }
+
+    /// Current transport state
+    pub fn status(&self) -> ReplayStatus {
+        let transport = self.lock();
+        ReplayStatus {
+            position: transport.current(Instant::now()),
+            range: (self.inner.start, self.inner.end),
+            paused: transport.paused,
+            speed: transport.speed,
+            fast: transport.fast,
+            lap: transport.lap,
+        }
+    }
+
+    /// Hold fast playback while `gate` returns false
+    pub fn set_gate(&self, gate: impl Fn() -> bool + Send + Sync + 'static) {
+        self.update(|t| t.gate = Some(Arc::new(gate)));
+    }
+
+    /// Stop advancing playback
+    pub fn pause(&self) {
+        self.update(|t| {
+            if !t.paused {
+                t.rebase(Instant::now());
+                t.paused = true;
+            }
+        });
+    }
+
+    /// Continue playback from the current position
+    pub fn resume(&self) {
+        self.update(|t| {
+            if t.paused {
+                t.paused = false;
+                t.anchor = Instant::now();
+            }
+        });
+    }
+
+    /// Jump to `position` (s after the first recorded timestamp), clamped
+    /// to the playback range
+    pub fn seek(&self, position: f64) {
+        let position = position.clamp(self.inner.start, self.inner.end);
+        let gap = self.inner.gap;
+        self.update(|t| {
+            let current = t.current(Instant::now());
+            if position < current {
+                t.shift += current - position + gap;
+            }
+            t.jump(position);
+        });
+    }
+
+    /// Pause and advance by `seconds` of recording time
+    pub fn step(&self, seconds: f64) {
+        let lap_end = self.inner.end + self.inner.gap;
+        self.update(|t| {
+            t.rebase(Instant::now());
+            t.paused = true;
+            t.position = (t.position + seconds.max(0.0)).min(lap_end);
+        });
+    }
+
+    /// Pause and advance to the `frames`-th next record of any sensor
+    pub fn step_frames(&self, frames: u64) {
+        let offsets = &self.inner.offsets;
+        let lap_end = self.inner.end + self.inner.gap;
+        self.update(|t| {
+            t.rebase(Instant::now());
+            t.paused = true;
+            let next = offsets.partition_point(|o| *o <= t.position);
+            t.position = match frames.checked_sub(1) {
+                None => t.position,
+                Some(skip) => offsets
+                    .get(next.saturating_add(skip as usize))
+                    .copied()
+                    .unwrap_or(lap_end),
+            };
+        });
+    }
+
+    /// Change the playback speed multiplier
+    pub fn set_speed(&self, speed: f64) {
+        self.update(|t| {
+            t.rebase(Instant::now());
+            t.speed = speed.max(MIN_SPEED);
+        });
+    }
+
+    /// Switch "as fast as possible" playback on or off
+    pub fn set_fast(&self, fast: bool) {
+        self.update(|t| {
+            t.rebase(Instant::now());
+            t.fast = fast;
+        });
+    }
+
+    /// Apply a control command and return the resulting state
+    pub fn apply(&self, command: ReplayCommand) -> ReplayStatus {
+        match command {
+            ReplayCommand::Pause => self.pause(),
+            ReplayCommand::Resume => self.resume(),
+            ReplayCommand::Seek(position) => self.seek(position),
+            ReplayCommand::Step(seconds) => self.step(seconds),
+            ReplayCommand::StepFrames(frames) => self.step_frames(frames),
+            ReplayCommand::Speed(speed) => self.set_speed(speed),
+            ReplayCommand::Fast(fast) => self.set_fast(fast),
+            ReplayCommand::Status => {}
+        }
+        self.status()
+    }
+
+    /// Reserve a lane for a sensor before playback starts. In fast mode no
+    /// sensor runs ahead of a reserved lane until its sensor has reported
+    /// its first record.
+    pub(crate) fn reserve(&self) -> usize {
+        self.update(|t| {
+            let lane = t.next_lane;
+            t.next_lane += 1;
+            t.lanes.insert(lane, Some(t.epoch_position));
+            lane
+        })
+    }
+
+    /// Start playing on a reserved lane; returns the epoch and the position
+    /// to start from. The first sensor starts the session.
+    pub(crate) fn join(&self, lane: usize) -> (u64, f64) {
+        self.update(|t| {
+            if !t.started {
+                t.started = true;
+                t.anchor = Instant::now();
+            }
+            t.lanes.insert(lane, Some(t.epoch_position));
+            (t.epoch, t.epoch_position)
+        })
+    }
+
+    /// Release a lane
+    pub(crate) fn leave(&self, lane: usize) {
+        self.update(|t| {
+            t.lanes.remove(&lane);
+        });
+    }
+
+    /// Whether a record at `offset` (s after origin) belongs to the range
+    pub(crate) fn in_range(&self, offset: f64) -> bool {
+        (self.inner.start..=self.inner.end).contains(&offset)
+    }
+
+    /// Block until the sensor on `lane` may act on its next record at
+    /// `offset` (None = no more records this lap)
+    pub(crate) fn wait_turn(
+        &self,
+        lane: usize,
+        offset: Option<f64>,
+        epoch: u64,
+        listening: &AtomicBool,
+    ) -> Turn {
+        let lap_end = self.inner.end + self.inner.gap;
+        let mut t = self.lock();
+        t.lanes.insert(lane, offset);
+        self.inner.changed.notify_all();
+        loop {
+            if !listening.load(Ordering::Relaxed) {
+                return Turn::Stopped;
+            }
+            if t.epoch != epoch {
+                return Turn::Relocate {
+                    epoch: t.epoch,
+                    position: t.epoch_position,
+                };
+            }
+            let now = Instant::now();
+            let wait = match offset {
+                None if !self.inner.loop_playback => return Turn::Finished,
+                None => {
+                    let all_done = t.lanes.values().all(Option::is_none);
+                    if all_done && t.limit(now) >= lap_end {
+                        // Wrap: keep any overshoot and continue replayed time
+                        let carry = if t.fast || t.paused {
+                            0.0
+                        } else {
+                            t.current(now) - lap_end
+                        };
+                        t.shift += lap_end - self.inner.start;
+                        t.lap += 1;
+                        t.jump(self.inner.start);
+                        t.position += carry;
+                        self.inner.changed.notify_all();
+                        continue;
+                    }
+                    MAX_WAIT
+                }
+                Some(offset) if offset > t.limit(now) => {
+                    if t.paused || t.fast {
+                        MAX_WAIT
+                    } else {
+                        let ahead = (offset - t.current(now)) / t.speed;
+                        Duration::from_secs_f64(ahead).min(MAX_WAIT)
+                    }
+                }
+                Some(offset) if t.fast => {
+                    // Merge sensors in timestamp order
+                    let first = t.lanes.values().flatten().all(|other| offset <= *other);
+                    let open = t.paused || t.gate.as_ref().is_none_or(|gate| gate());
+                    if first && open {
+                        t.emitted = t.emitted.max(offset);
+                        return Turn::Emit { shift: t.shift };
+                    }
+                    if first {
+                        GATE_POLL
+                    } else {
+                        MAX_WAIT
+                    }
+                }
+                Some(offset) => {
+                    t.emitted = t.emitted.max(offset);
+                    return Turn::Emit { shift: t.shift };
+                }
+            };
+            t = self
+                .inner
+                .changed
+                .wait_timeout(t, wait)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(speed_multiplier: f64, loop_playback: bool) -> ReplayConfig {
+        ReplayConfig {
+            speed_multiplier,
+            loop_playback,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_range_and_laps() {
+        let clock = ReplayClock::new(&config(1.0, true), [10.0, 10.1, 10.2, 10.25]);
+        assert_eq!(clock.origin(), 10.0);
+        // Span 0.25 s plus the smallest interval 0.05 s
+        assert!((clock.period() - 0.3).abs() < 1e-9);
+
+        let ranged = ReplayClock::new(
+            &ReplayConfig {
+                start_s: Some(0.05),
+                end_s: Some(0.2),
+                ..config(1.0, false)
+            },
+            [10.0, 10.1, 10.2, 10.25],
+        );
+        let status = ranged.status();
+        assert_eq!(status.range, (0.05, 0.2));
+        assert_eq!(status.position, 0.05);
+        assert!(ranged.in_range(0.1) && !ranged.in_range(0.25));
+    }
+
+    #[test]
+    fn test_transport_controls() {
+        let clock = ReplayClock::new(&config(1.0, false), [0.0, 0.1, 0.2, 0.3, 0.4]);
+        clock.pause();
+        assert!(clock.status().paused);
+
+        clock.step_frames(2);
+        assert!((clock.status().position - 0.2).abs() < 1e-9);
+        clock.step(0.15);
+        assert!((clock.status().position - 0.35).abs() < 1e-9);
+
+        clock.seek(0.1);
+        assert!((clock.status().position - 0.1).abs() < 1e-9);
+        // A backward seek shifts replayed time forward
+        assert!(clock.lock().shift > 0.0);
+        assert_eq!(clock.lock().epoch, 1);
+
+        let status = clock.apply("speed 4".parse().unwrap());
+        assert_eq!(status.speed, 4.0);
+        assert!(!clock.apply(ReplayCommand::Resume).paused);
+        assert!("seek".parse::<ReplayCommand>().is_err());
+        assert_eq!("next".parse(), Ok(ReplayCommand::StepFrames(1)));
+    }
+
+    #[test]
+    fn test_fast_mode_merges_sensors_in_order() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                ..config(1.0, false)
+            },
+            [0.0, 0.5, 1.0, 1.5],
+        );
+        let listening = AtomicBool::new(true);
+        let (a, b) = (clock.reserve(), clock.reserve());
+        let (epoch, _) = clock.join(a);
+        clock.join(b);
+
+        // b has not reported yet, so a may only emit up to the range start
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || {
+                let listening = AtomicBool::new(true);
+                clock.wait_turn(a, Some(1.0), epoch, &listening)
+            })
+        };
+        assert_eq!(
+            clock.wait_turn(b, Some(0.5), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+        assert_eq!(clock.wait_turn(b, None, epoch, &listening), Turn::Finished);
+        clock.leave(b);
+        assert_eq!(waiter.join().unwrap(), Turn::Emit { shift: 0.0 });
+    }
+
+    #[test]
+    fn test_fast_mode_waits_for_reserved_lanes() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                ..config(1.0, false)
+            },
+            [0.0, 0.5, 1.0],
+        );
+        let listening = AtomicBool::new(true);
+        let (a, b) = (clock.reserve(), clock.reserve());
+        let (epoch, _) = clock.join(a);
+
+        let (emitted, waited) = std::sync::mpsc::channel();
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || {
+                let listening = AtomicBool::new(true);
+                let turn = clock.wait_turn(a, Some(0.5), epoch, &listening);
+                emitted.send(()).unwrap();
+                turn
+            })
+        };
+        // b has not started yet, so a must not emit ahead of it
+        assert!(waited.recv_timeout(Duration::from_millis(100)).is_err());
+
+        assert_eq!(clock.join(b), (epoch, 0.0));
+        assert_eq!(
+            clock.wait_turn(b, Some(0.0), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+        assert!(waited.try_recv().is_err());
+        assert_eq!(clock.wait_turn(b, None, epoch, &listening), Turn::Finished);
+        clock.leave(b);
+        assert_eq!(waiter.join().unwrap(), Turn::Emit { shift: 0.0 });
+    }
+
+    #[test]
+    fn test_backward_seek_relocates_every_lane() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                start_paused: true,
+                ..config(1.0, false)
+            },
+            [0.0, 0.1, 0.2, 0.3, 0.4],
+        );
+        let lanes: Vec<usize> = (0..3).map(|_| clock.reserve()).collect();
+        let mut epoch = 0;
+        for &lane in &lanes {
+            epoch = clock.join(lane).0;
+        }
+        clock.step(0.3);
+
+        // Every lane waits on a record the paused position has not reached
+        let waiters: Vec<_> = lanes
+            .iter()
+            .map(|&lane| {
+                let clock = clock.clone();
+                std::thread::spawn(move || {
+                    let listening = AtomicBool::new(true);
+                    clock.wait_turn(lane, Some(0.4), epoch, &listening)
+                })
+            })
+            .collect();
+        clock.seek(0.1);
+        for waiter in waiters {
+            assert_eq!(
+                waiter.join().unwrap(),
+                Turn::Relocate {
+                    epoch: epoch + 1,
+                    position: 0.1
+                }
+            );
+        }
+
+        // Replayed time continues past 0.3 by one sample interval
+        let listening = AtomicBool::new(true);
+        let Turn::Emit { shift } = clock.wait_turn(lanes[0], Some(0.1), epoch + 1, &listening)
+        else {
+            panic!("expected the record at the seek position");
+        };
+        assert!((0.1 + shift - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_step_frames_across_sensors() {
+        // a records at 0.0, 0.1, 0.2; b at 0.05, 0.15
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                start_paused: true,
+                ..config(1.0, false)
+            },
+            [0.0, 0.05, 0.1, 0.15, 0.2],
+        );
+        let listening = AtomicBool::new(true);
+        let (a, b) = (clock.reserve(), clock.reserve());
+        let (epoch, _) = clock.join(a);
+        clock.join(b);
+        assert_eq!(
+            clock.wait_turn(a, Some(0.0), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+
+        clock.step_frames(1);
+        assert!((clock.status().position - 0.05).abs() < 1e-9);
+        assert_eq!(
+            clock.wait_turn(b, Some(0.05), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+
+        // Two records later, counting both sensors
+        clock.step_frames(2);
+        assert!((clock.status().position - 0.15).abs() < 1e-9);
+        assert_eq!(
+            clock.wait_turn(a, Some(0.1), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+        assert_eq!(
+            clock.wait_turn(b, Some(0.15), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+
+        // Past the last record the position stops at the end of the lap
+        clock.step_frames(5);
+        assert!((clock.status().position - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_loop_wrap_shifts_continuously() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                ..config(1.0, true)
+            },
+            [0.0, 0.1, 0.2],
+        );
+        let listening = AtomicBool::new(true);
+        let lane = clock.reserve();
+        let (epoch, _) = clock.join(lane);
+        for offset in [0.0, 0.1, 0.2] {
+            assert_eq!(
+                clock.wait_turn(lane, Some(offset), epoch, &listening),
+                Turn::Emit { shift: 0.0 }
+            );
+        }
+
+        let Turn::Relocate { epoch, position } = clock.wait_turn(lane, None, epoch, &listening)
+        else {
+            panic!("expected the lap to wrap");
+        };
+        assert_eq!((position, clock.status().lap), (0.0, 1));
+        let Turn::Emit { shift } = clock.wait_turn(lane, Some(0.0), epoch, &listening) else {
+            panic!("expected the first record of the next lap");
+        };
+        // The next lap starts one sample interval after the last record
+        assert!((0.0 + shift - 0.3).abs() < 1e-9);
+        assert!((shift - clock.period()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_range_end_finishes() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                end_s: Some(0.1),
+                ..config(1.0, false)
+            },
+            [0.0, 0.1, 0.2, 0.3],
+        );
+        let listening = AtomicBool::new(true);
+        let lane = clock.reserve();
+        let (epoch, _) = clock.join(lane);
+        assert_eq!(
+            clock.wait_turn(lane, Some(0.1), epoch, &listening),
+            Turn::Emit { shift: 0.0 }
+        );
+        // Records past the range end are reported as no further record
+        assert!(!clock.in_range(0.2));
+        assert_eq!(
+            clock.wait_turn(lane, None, epoch, &listening),
+            Turn::Finished
+        );
+    }
+
+    #[test]
+    fn test_reserved_lane_released_without_joining() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                ..config(1.0, false)
+            },
+            [0.0, 0.5, 1.0],
+        );
+        let (a, b) = (clock.reserve(), clock.reserve());
+        let (epoch, _) = clock.join(a);
+
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || {
+                let listening = AtomicBool::new(true);
+                clock.wait_turn(a, Some(0.5), epoch, &listening)
+            })
+        };
+        // b never joins; releasing its lane lets a run on
+        clock.leave(b);
+        assert_eq!(waiter.join().unwrap(), Turn::Emit { shift: 0.0 });
+    }
+
+    #[test]
+    fn test_fast_mode_keeps_order_across_laps() {
+        let clock = ReplayClock::new(
+            &ReplayConfig {
+                fast: true,
+                ..config(1.0, true)
+            },
+            [0.0, 0.03, 0.06],
+        );
+        let listening = AtomicBool::new(true);
+        let (a, b) = (clock.reserve(), clock.reserve());
+        let (epoch, _) = clock.join(a);
+        clock.join(b);
+
+        // b finished its lap; a finishing too wraps playback
+        clock.lock().lanes.insert(b, None);
+        let Turn::Relocate { epoch, .. } = clock.wait_turn(a, None, epoch, &listening) else {
+            panic!("expected the lap to wrap");
+        };
+
+        // a relocates first; b has not reported its next record yet
+        let (emitted, waited) = std::sync::mpsc::channel();
+        let waiter = {
+            let clock = clock.clone();
+            std::thread::spawn(move || {
+                let listening = AtomicBool::new(true);
+                let turn = clock.wait_turn(a, Some(0.06), epoch, &listening);
+                emitted.send(()).unwrap();
+                turn
+            })
+        };
+        while clock.lock().lanes[&a] != Some(0.06) {
+            std::thread::yield_now();
+        }
+        assert_eq!(
+            clock.wait_turn(b, Some(0.03), epoch, &listening),
+            Turn::Emit {
+                shift: clock.period()
+            }
+        );
+        assert!(waited.try_recv().is_err());
+        clock.leave(b);
+        assert_eq!(
+            waiter.join().unwrap(),
+            Turn::Emit {
+                shift: clock.period()
+            }
+        );
+    }
+}
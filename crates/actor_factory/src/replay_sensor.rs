@@ -1,25 +1,24 @@
 //! Replay Sensor - Replay sensor data from recorded files
 //!
-//! Reads JSONL + binary files recorded by Python script,
-//! replays sensor data at original timestamps.
+//! Reads JSONL + binary files recorded by Python script through a shared
+//! [`RecordingReader`], replays sensor data at original timestamps, following
+//! a [`ReplayClock`] shared with the other sensors of the session.
 
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::{Duration, Instant};
 
-use bytes::Bytes;
-use contracts::{
-    GnssData, ImageData, ImageFormat, ImuData, PointCloudData, RadarData, SensorDataCallback,
-    SensorPacket, SensorPayload, SensorSource, SensorType, Vector3,
-};
-use serde::Deserialize;
+use contracts::{SensorDataCallback, SensorPacket, SensorSource, SensorType, SimTime};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
+use crate::recording::{
+    Prefetcher, RecordingReader, SensorTrack, DEFAULT_PREFETCH_DEPTH, RECORDING_FORMAT_VERSION,
+};
+use crate::replay_clock::{ReplayClock, Turn};
+
 /// Replay configuration
 #[derive(Debug, Clone, Default)]
 pub struct ReplayConfig {
@@ -31,11 +30,24 @@ pub struct ReplayConfig {
 
     /// Whether to loop playback
     pub loop_playback: bool,
+
+    /// Start of the playback range (s after the first recorded timestamp)
+    pub start_s: Option<f64>,
+
+    /// End of the playback range (s after the first recorded timestamp)
+    pub end_s: Option<f64>,
+
+    /// Start paused, waiting for transport commands
+    pub start_paused: bool,
+
+    /// Replay as fast as downstream accepts instead of in real time
+    pub fast: bool,
 }
 
 /// Recording session manifest
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RecordingManifest {
+    /// Recording format version, see [`RECORDING_FORMAT_VERSION`]
     pub version: String,
     pub created_at: String,
     pub carla_version: String,
@@ -43,216 +55,122 @@ pub struct RecordingManifest {
     pub sensors: HashMap<String, SensorMetadata>,
 }
 
+impl RecordingManifest {
+    /// Read `manifest.json` in a recording directory.
+    ///
+    /// Fails on recordings newer than [`RECORDING_FORMAT_VERSION`].
+    pub fn load(replay_path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(replay_path.join("manifest.json"))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        match manifest.format_version() {
+            Some(version) if version > RECORDING_FORMAT_VERSION => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "recording format version {} is newer than supported version {}",
+                    manifest.version, RECORDING_FORMAT_VERSION
+                ),
+            )),
+            _ => Ok(manifest),
+        }
+    }
+
+    /// Major recording format version (`"1.0"` -> 1)
+    pub fn format_version(&self) -> Option<u32> {
+        self.version.split('.').next()?.trim().parse().ok()
+    }
+}
+
 /// Sensor metadata
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SensorMetadata {
     pub sensor_type: String,
     pub frame_count: u64,
 }
 
-/// Sensor record in JSONL
-#[derive(Debug, Deserialize)]
-struct SensorRecord {
-    sensor_id: String,
-    sensor_type: String,
-    timestamp: f64,
-    frame_id: u64,
-
-    // Camera fields
-    #[serde(default)]
-    data_file: Option<String>,
-    #[serde(default)]
-    width: Option<u32>,
-    #[serde(default)]
-    height: Option<u32>,
-    #[serde(default)]
-    format: Option<String>,
-
-    // LiDAR fields
-    #[serde(default)]
-    num_points: Option<u32>,
-    #[serde(default)]
-    point_stride: Option<u32>,
-
-    // IMU fields
-    #[serde(default)]
-    accelerometer: Option<[f64; 3]>,
-    #[serde(default)]
-    gyroscope: Option<[f64; 3]>,
-    #[serde(default)]
-    compass: Option<f64>,
-
-    // GNSS fields
-    #[serde(default)]
-    latitude: Option<f64>,
-    #[serde(default)]
-    longitude: Option<f64>,
-    #[serde(default)]
-    altitude: Option<f64>,
-
-    // Radar fields
-    #[serde(default)]
-    num_detections: Option<u32>,
-}
-
 /// Replay Sensor - Replay sensor data from recorded files
 pub struct ReplaySensor {
-    sensor_id: String,
-    sensor_type: SensorType,
-    replay_path: PathBuf,
-    records: Vec<SensorRecord>,
-    config: ReplayConfig,
+    track: SensorTrack,
+    clock: ReplayClock,
+    /// Lane reserved on `clock` when the sensor is created
+    lane: usize,
     listening: Arc<AtomicBool>,
     thread_handle: std::sync::Mutex<Option<JoinHandle<()>>>,
 }
 
 impl ReplaySensor {
-    /// Load sensor from recording directory
+    /// Load sensor from recording directory.
+    ///
+    /// The sensor gets its own clock; use [`with_clock`](Self::with_clock)
+    /// to keep several sensors aligned.
     pub fn load(
         replay_path: &Path,
         sensor_id: String,
         sensor_type: SensorType,
         config: ReplayConfig,
     ) -> std::io::Result<Self> {
-        let jsonl_path = replay_path.join("sensors.jsonl");
-        let file = File::open(&jsonl_path)?;
-        let reader = BufReader::new(file);
-
-        let mut records = Vec::new();
-
-        for line in reader.lines() {
-            let line = line?;
-            if line.is_empty() {
-                continue;
-            }
-
-            let record: SensorRecord = serde_json::from_str(&line)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-
-            // Only keep records for this sensor
-            if record.sensor_id == sensor_id {
-                records.push(record);
-            }
-        }
-
-        // Sort by timestamp
-        records.sort_by(|a, b| a.timestamp.partial_cmp(&b.timestamp).unwrap());
+        let recording = RecordingReader::open(replay_path)?;
+        Ok(Self::from_recording(
+            &recording,
+            &sensor_id,
+            sensor_type,
+            &config,
+        ))
+    }
 
+    /// Replay one sensor of an already indexed recording
+    pub fn from_recording(
+        recording: &RecordingReader,
+        sensor_id: &str,
+        sensor_type: SensorType,
+        config: &ReplayConfig,
+    ) -> Self {
+        let track = recording.track(sensor_id, sensor_type);
         info!(
             sensor_id = %sensor_id,
-            records = records.len(),
+            records = track.len(),
             "Loaded replay sensor"
         );
 
-        Ok(Self {
-            sensor_id,
-            sensor_type,
-            replay_path: replay_path.to_path_buf(),
-            records,
-            config,
+        let clock = ReplayClock::new(config, (0..track.len()).map(|i| track.timestamp(i)));
+        let lane = clock.reserve();
+        Self {
+            track,
+            clock,
+            lane,
             listening: Arc::new(AtomicBool::new(false)),
             thread_handle: std::sync::Mutex::new(None),
-        })
-    }
-
-    /// Build SensorPacket from record
-    fn build_packet(&self, record: &SensorRecord) -> Option<SensorPacket> {
-        let payload = match self.sensor_type {
-            SensorType::Camera => self.build_camera_payload(record)?,
-            SensorType::Lidar => self.build_lidar_payload(record)?,
-            SensorType::Imu => self.build_imu_payload(record)?,
-            SensorType::Gnss => self.build_gnss_payload(record)?,
-            SensorType::Radar => self.build_radar_payload(record)?,
-        };
-
-        Some(SensorPacket {
-            sensor_id: self.sensor_id.clone().into(),
-            sensor_type: self.sensor_type,
-            timestamp: record.timestamp,
-            frame_id: Some(record.frame_id),
-            payload,
-        })
-    }
-
-    fn build_camera_payload(&self, record: &SensorRecord) -> Option<SensorPayload> {
-        let data_file = record.data_file.as_ref()?;
-        let data = self.read_binary_file(data_file)?;
-
-        Some(SensorPayload::Image(ImageData {
-            width: record.width.unwrap_or(0),
-            height: record.height.unwrap_or(0),
-            format: ImageFormat::Bgra8,
-            data,
-        }))
-    }
-
-    fn build_lidar_payload(&self, record: &SensorRecord) -> Option<SensorPayload> {
-        let data_file = record.data_file.as_ref()?;
-        let data = self.read_binary_file(data_file)?;
-
-        Some(SensorPayload::PointCloud(PointCloudData {
-            num_points: record.num_points.unwrap_or(0),
-            point_stride: record.point_stride.unwrap_or(16),
-            data,
-        }))
-    }
-
-    fn build_imu_payload(&self, record: &SensorRecord) -> Option<SensorPayload> {
-        let accel = record.accelerometer?;
-        let gyro = record.gyroscope?;
-
-        Some(SensorPayload::Imu(ImuData {
-            accelerometer: Vector3 {
-                x: accel[0],
-                y: accel[1],
-                z: accel[2],
-            },
-            gyroscope: Vector3 {
-                x: gyro[0],
-                y: gyro[1],
-                z: gyro[2],
-            },
-            compass: record.compass.unwrap_or(0.0),
-        }))
-    }
-
-    fn build_gnss_payload(&self, record: &SensorRecord) -> Option<SensorPayload> {
-        Some(SensorPayload::Gnss(GnssData {
-            latitude: record.latitude?,
-            longitude: record.longitude?,
-            altitude: record.altitude?,
-        }))
+        }
     }
 
-    fn build_radar_payload(&self, record: &SensorRecord) -> Option<SensorPayload> {
-        let data_file = record.data_file.as_ref()?;
-        let data = self.read_binary_file(data_file)?;
-
-        Some(SensorPayload::Radar(RadarData {
-            num_detections: record.num_detections.unwrap_or(0),
-            data,
-        }))
+    /// Follow a clock shared with other replay sensors.
+    ///
+    /// The sensor's lane is reserved right away, so in fast mode sensors
+    /// that start listening first cannot run ahead of it.
+    pub fn with_clock(mut self, clock: ReplayClock) -> Self {
+        self.clock.leave(self.lane);
+        self.lane = clock.reserve();
+        self.clock = clock;
+        self
     }
+}
 
-    fn read_binary_file(&self, relative_path: &str) -> Option<Bytes> {
-        let path = self.replay_path.join(relative_path);
-        match std::fs::read(&path) {
-            Ok(data) => Some(Bytes::from(data)),
-            Err(e) => {
-                warn!(path = %path.display(), error = %e, "Failed to read binary file");
-                None
-            }
+impl Drop for ReplaySensor {
+    fn drop(&mut self) {
+        // A running replay thread releases the lane when it ends
+        if !self.is_listening() {
+            self.clock.leave(self.lane);
         }
     }
 }
 
 impl SensorSource for ReplaySensor {
     fn sensor_id(&self) -> &str {
-        &self.sensor_id
+        self.track.sensor_id()
     }
 
     fn sensor_type(&self) -> SensorType {
-        self.sensor_type
+        self.track.sensor_type()
     }
 
     fn listen(&self, callback: SensorDataCallback) {
@@ -261,63 +179,63 @@ impl SensorSource for ReplaySensor {
         }
 
         let listening = self.listening.clone();
-        let sensor_id = self.sensor_id.clone();
-        let records = self.records.clone();
-        let replay_path = self.replay_path.clone();
-        let sensor_type = self.sensor_type;
-        let speed = self.config.speed_multiplier.max(0.1);
-        let loop_playback = self.config.loop_playback;
+        let track = self.track.clone();
+        let clock = self.clock.clone();
+        let lane = self.lane;
 
         let handle = thread::spawn(move || {
+            let sensor_id = track.sensor_id().to_string();
             debug!(sensor_id = %sensor_id, "Replay thread started");
+            // Binary payloads are read ahead so the timing loop never waits on disk
+            let prefetcher = Prefetcher::spawn(track.clone(), DEFAULT_PREFETCH_DEPTH);
 
-            loop {
-                if records.is_empty() {
-                    warn!(sensor_id = %sensor_id, "No records to replay");
-                    break;
-                }
-
-                let start_time = Instant::now();
-                let first_timestamp = records[0].timestamp;
+            let (mut epoch, position) = clock.join(lane);
+            let origin = clock.origin();
+            let mut next = track.locate(origin + position);
 
-                for record in &records {
-                    if !listening.load(Ordering::Relaxed) {
-                        debug!(sensor_id = %sensor_id, "Replay stopped");
-                        return;
+            loop {
+                let offset = (next < track.len())
+                    .then(|| track.timestamp(next) - origin)
+                    .filter(|offset| clock.in_range(*offset));
+                match clock.wait_turn(lane, offset, epoch, &listening) {
+                    Turn::Emit { shift } => {
+                        // Build and send packet, shifted onto the replay timeline
+                        let timestamp = SimTime::try_from_secs_f64(track.timestamp(next) + shift);
+                        match (prefetcher.packet(next), timestamp) {
+                            (Some(mut packet), Some(timestamp)) => {
+                                packet.timestamp = timestamp;
+                                callback(packet);
+                            }
+                            (Some(_), None) => {
+                                warn!(
+                                    sensor_id = %sensor_id,
+                                    index = next,
+                                    "Skipping replay packet with non-finite timestamp"
+                                );
+                            }
+                            (None, _) => {}
+                        }
+                        next += 1;
                     }
-
-                    // Calculate wait time
-                    let record_offset = record.timestamp - first_timestamp;
-                    let target_elapsed = Duration::from_secs_f64(record_offset / speed);
-                    let actual_elapsed = start_time.elapsed();
-
-                    if target_elapsed > actual_elapsed {
-                        thread::sleep(target_elapsed - actual_elapsed);
+                    Turn::Relocate {
+                        epoch: jumped,
+                        position,
+                    } => {
+                        debug!(sensor_id = %sensor_id, position, "Replay relocated");
+                        epoch = jumped;
+                        next = track.locate(origin + position);
                     }
-
-                    // Build and send packet
-                    let replay_sensor = ReplaySensor {
-                        sensor_id: sensor_id.clone(),
-                        sensor_type,
-                        replay_path: replay_path.clone(),
-                        records: vec![],
-                        config: ReplayConfig::default(),
-                        listening: Arc::new(AtomicBool::new(false)),
-                        thread_handle: std::sync::Mutex::new(None),
-                    };
-
-                    if let Some(packet) = replay_sensor.build_packet(record) {
-                        callback(packet);
+                    Turn::Finished => {
+                        info!(sensor_id = %sensor_id, "Replay completed");
+                        break;
+                    }
+                    Turn::Stopped => {
+                        debug!(sensor_id = %sensor_id, "Replay stopped");
+                        break;
                     }
                 }
-
-                if !loop_playback {
-                    info!(sensor_id = %sensor_id, "Replay completed");
-                    break;
-                }
-
-                debug!(sensor_id = %sensor_id, "Looping replay");
             }
+            clock.leave(lane);
 
             listening.store(false, Ordering::SeqCst);
         });
@@ -339,27 +257,84 @@ impl SensorSource for ReplaySensor {
     }
 }
 
-// Allow SensorRecord to Clone for use in threads
-impl Clone for SensorRecord {
-    fn clone(&self) -> Self {
-        Self {
-            sensor_id: self.sensor_id.clone(),
-            sensor_type: self.sensor_type.clone(),
-            timestamp: self.timestamp,
-            frame_id: self.frame_id,
-            data_file: self.data_file.clone(),
-            width: self.width,
-            height: self.height,
-            format: self.format.clone(),
-            num_points: self.num_points,
-            point_stride: self.point_stride,
-            accelerometer: self.accelerometer,
-            gyroscope: self.gyroscope,
-            compass: self.compass,
-            latitude: self.latitude,
-            longitude: self.longitude,
-            altitude: self.altitude,
-            num_detections: self.num_detections,
+/// Load every packet of the given sensors from a recording directory.
+///
+/// Packets are returned sorted by timestamp. Records whose payload cannot be
+/// built (e.g. missing binary files) are skipped with a warning.
+pub fn load_recording_packets(
+    replay_path: &Path,
+    sensors: &HashMap<String, SensorType>,
+) -> std::io::Result<Vec<SensorPacket>> {
+    let recording = RecordingReader::open(replay_path)?;
+    let mut packets: Vec<SensorPacket> = sensors
+        .iter()
+        .flat_map(|(id, sensor_type)| {
+            let track = recording.track(id, *sensor_type);
+            (0..track.len()).filter_map(move |i| track.packet(i))
+        })
+        .collect();
+    packets.sort_by_key(|packet| packet.timestamp);
+
+    info!(
+        path = %replay_path.display(),
+        packets = packets.len(),
+        "Loaded recording packets"
+    );
+
+    Ok(packets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_unused_sensor_does_not_hold_fast_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = std::fs::File::create(dir.path().join("sensors.jsonl")).unwrap();
+        for (sensor_id, timestamp) in [
+            ("gnss_a", 0.0),
+            ("gnss_b", 0.05),
+            ("gnss_a", 0.1),
+            ("gnss_a", 0.2),
+        ] {
+            writeln!(
+                file,
+                r#"{{"sensor_id":"{sensor_id}","sensor_type":"gnss","timestamp":{timestamp},"frame_id":1,"latitude":0.0,"longitude":0.0,"altitude":0.0}}"#
+            )
+            .unwrap();
         }
+
+        let config = ReplayConfig {
+            speed_multiplier: 1.0,
+            fast: true,
+            ..Default::default()
+        };
+        let recording = RecordingReader::open(dir.path()).unwrap();
+        let clock = ReplayClock::new(&config, recording.timestamps());
+        let sensor = |sensor_id| {
+            ReplaySensor::from_recording(&recording, sensor_id, SensorType::Gnss, &config)
+                .with_clock(clock.clone())
+        };
+        let used = sensor("gnss_a");
+        // Created, never listened to: dropping it must release its lane
+        drop(sensor("gnss_b"));
+
+        let (tx, rx) = mpsc::channel();
+        let tx = std::sync::Mutex::new(tx);
+        used.listen(Arc::new(move |packet| {
+            let _ = tx.lock().unwrap().send(packet.timestamp.as_secs_f64());
+        }));
+        let timestamps: Vec<f64> = (0..3)
+            .map(|_| {
+                rx.recv_timeout(Duration::from_secs(5))
+                    .expect("replay stalled")
+            })
+            .collect();
+        used.stop();
+        assert_eq!(timestamps, vec![0.0, 0.1, 0.2]);
     }
 }
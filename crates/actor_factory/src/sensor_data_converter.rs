@@ -8,22 +8,12 @@ use carla::sensor::data::{
     GnssMeasurement, Image, ImuMeasurement, LidarMeasurement, RadarMeasurement,
 };
 use carla::sensor::{SensorData, SensorDataBase};
+use contracts::carla_convert::{lidar_to_payload, radar_to_payload};
 use contracts::{
-    GnssData, ImageData, ImageFormat, ImuData, PointCloudData, RadarData, SensorPacket,
-    SensorPayload, SensorType, Vector3,
+    GnssData, ImageData, ImageFormat, ImuData, SensorPacket, SensorPayload, SensorType, SimTime,
+    Vector3,
 };
 
-/// Convert POD slice to bytes::Bytes
-///
-/// # Safety
-/// Caller must ensure T is a POD type
-#[inline]
-unsafe fn pod_slice_to_bytes_unchecked<T>(slice: &[T]) -> Bytes {
-    let ptr = slice.as_ptr() as *const u8;
-    let len = std::mem::size_of_val(slice);
-    Bytes::copy_from_slice(std::slice::from_raw_parts(ptr, len))
-}
-
 /// Convert CARLA Image to SensorPayload
 fn image_to_payload(image: &Image) -> SensorPayload {
     let data = Bytes::copy_from_slice(image.as_raw_bytes());
@@ -35,17 +25,6 @@ fn image_to_payload(image: &Image) -> SensorPayload {
     })
 }
 
-/// Convert CARLA LidarMeasurement to SensorPayload
-fn lidar_to_payload(lidar: &LidarMeasurement) -> SensorPayload {
-    let points = lidar.as_slice();
-    let data = unsafe { pod_slice_to_bytes_unchecked(points) };
-    SensorPayload::PointCloud(PointCloudData {
-        num_points: points.len() as u32,
-        point_stride: 16, // x, y, z, intensity: f32 each
-        data,
-    })
-}
-
 /// Convert CARLA ImuMeasurement to SensorPayload
 fn imu_to_payload(imu: &ImuMeasurement) -> SensorPayload {
     let accel = imu.accelerometer();
@@ -74,26 +53,17 @@ fn gnss_to_payload(gnss: &GnssMeasurement) -> SensorPayload {
     })
 }
 
-/// Convert CARLA RadarMeasurement to SensorPayload
-fn radar_to_payload(radar: &RadarMeasurement) -> SensorPayload {
-    let detections = radar.as_slice();
-    let data = unsafe { pod_slice_to_bytes_unchecked(detections) };
-    SensorPayload::Radar(RadarData {
-        num_detections: detections.len() as u32,
-        data,
-    })
-}
-
 /// Convert CARLA sensor data to SensorPacket
 ///
 /// Automatically selects appropriate conversion function based on sensor type.
-/// Returns None if data type doesn't match sensor type.
+/// Returns None if data type doesn't match sensor type or the timestamp is
+/// not finite.
 pub fn convert_sensor_data(
     sensor_id: &str,
     sensor_type: SensorType,
     data: &SensorData,
 ) -> Option<SensorPacket> {
-    let timestamp = data.timestamp();
+    let timestamp = SimTime::try_from_secs_f64(data.timestamp())?;
     let frame_id = data.frame() as u64;
 
     let payload = match sensor_type {
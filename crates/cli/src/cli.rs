@@ -48,19 +48,20 @@ pub enum Commands {
 
     /// Display configuration information
     Info(InfoArgs),
+
+    /// Synchronize a recorded session offline with globally optimal matching
+    BatchSync(BatchSyncArgs),
+
+    /// Record raw sensor packets for later replay
+    Record(RecordArgs),
 }
 
 /// Arguments for the `run` command
 #[derive(Parser, Debug, Clone)]
 pub struct RunArgs {
-    /// Path to configuration file (TOML or JSON)
-    #[arg(
-        short,
-        long,
-        default_value = "config.toml",
-        env = "CARLA_SYNCER_CONFIG"
-    )]
-    pub config: PathBuf,
+    /// Path to configuration file (TOML or JSON) [default: config.toml]
+    #[arg(short, long, env = "CARLA_SYNCER_CONFIG")]
+    pub config: Option<PathBuf>,
 
     /// Override CARLA server host from configuration
     #[arg(long, env = "CARLA_HOST")]
@@ -90,7 +91,9 @@ pub struct RunArgs {
     #[arg(long, default_value = "9000", env = "CARLA_SYNCER_METRICS_PORT")]
     pub metrics_port: u16,
 
-    /// Replay recorded sensor data from directory (mock mode only)
+    /// Replay recorded sensor data from directory (mock mode only).
+    /// Without --config and no ./config.toml the blueprint is derived from
+    /// the recording; otherwise the configuration is checked against it
     #[arg(long, env = "CARLA_SYNCER_REPLAY")]
     pub replay: Option<PathBuf>,
 
@@ -101,6 +104,90 @@ pub struct RunArgs {
     /// Loop replay when finished
     #[arg(long)]
     pub replay_loop: bool,
+
+    /// Start replay this many seconds into the recording
+    #[arg(long)]
+    pub replay_start: Option<f64>,
+
+    /// Stop replay this many seconds into the recording
+    #[arg(long)]
+    pub replay_end: Option<f64>,
+
+    /// Start replay paused (resume or step it with --replay-control)
+    #[arg(long)]
+    pub replay_paused: bool,
+
+    /// Replay as fast as the pipeline accepts instead of in real time
+    #[arg(long)]
+    pub replay_fast: bool,
+
+    /// Read replay commands from stdin: pause, resume, seek <s>, step <s>,
+    /// next [n], speed <x>, fast on|off, status
+    #[arg(long)]
+    pub replay_control: bool,
+
+    /// Sync state checkpoint, loaded at startup and saved at shutdown
+    /// (overrides `sync.engine.checkpoint_path`)
+    #[arg(long, env = "CARLA_SYNCER_CHECKPOINT")]
+    pub checkpoint: Option<PathBuf>,
+}
+
+/// Arguments for the `batch-sync` command
+#[derive(Parser, Debug, Clone)]
+pub struct BatchSyncArgs {
+    /// Path to configuration file (TOML or JSON)
+    #[arg(
+        short,
+        long,
+        default_value = "config.toml",
+        env = "CARLA_SYNCER_CONFIG"
+    )]
+    pub config: PathBuf,
+
+    /// Recording directory (manifest.json + sensors.jsonl)
+    #[arg(short, long)]
+    pub recording: PathBuf,
+
+    /// Maximum number of synced frames to dispatch (0 = unlimited)
+    #[arg(long, default_value = "0")]
+    pub max_frames: u64,
+
+    /// Channel buffer size towards the dispatcher
+    #[arg(long, default_value = "100")]
+    pub buffer_size: usize,
+}
+
+/// Arguments for the `record` command
+#[derive(Parser, Debug, Clone)]
+pub struct RecordArgs {
+    /// Path to configuration file (TOML or JSON)
+    #[arg(
+        short,
+        long,
+        default_value = "config.toml",
+        env = "CARLA_SYNCER_CONFIG"
+    )]
+    pub config: PathBuf,
+
+    /// Recording directory to create (manifest.json + sensors.jsonl)
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Recording length in seconds (0 = until Ctrl+C)
+    #[arg(short, long, default_value = "10")]
+    pub duration: f64,
+
+    /// Override CARLA server host from configuration
+    #[arg(long, env = "CARLA_HOST")]
+    pub host: Option<String>,
+
+    /// Override CARLA server port from configuration
+    #[arg(long, env = "CARLA_PORT")]
+    pub port: Option<u16>,
+
+    /// Channel buffer size for ingestion queues
+    #[arg(long, default_value = "100", env = "CARLA_SYNCER_BUFFER_SIZE")]
+    pub buffer_size: usize,
 }
 
 /// Arguments for the `validate` command
@@ -0,0 +1,133 @@
+//! `batch-sync` command implementation.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use contracts::{SensorType, SyncedFrame};
+use sync_engine::{BatchOutput, BatchSynchronizer};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::cli::BatchSyncArgs;
+
+/// Execute the `batch-sync` command
+pub async fn run_batch_sync(args: &BatchSyncArgs) -> Result<()> {
+    info!(
+        config = %args.config.display(),
+        recording = %args.recording.display(),
+        "Loading configuration for batch sync"
+    );
+
+    if !args.config.exists() {
+        anyhow::bail!("Configuration file not found: {}", args.config.display());
+    }
+    if !args.recording.is_dir() {
+        anyhow::bail!(
+            "Recording directory not found: {}",
+            args.recording.display()
+        );
+    }
+
+    let blueprint = config_loader::ConfigLoader::load_from_path(&args.config)
+        .with_context(|| format!("Failed to load config from {}", args.config.display()))?;
+
+    let sensors: HashMap<String, SensorType> = blueprint
+        .vehicles
+        .iter()
+        .flat_map(|vehicle| vehicle.sensors.iter())
+        .map(|sensor| (sensor.id.clone(), sensor.sensor_type))
+        .collect();
+
+    let recording = args.recording.clone();
+    let packets = tokio::task::spawn_blocking(move || {
+        actor_factory::load_recording_packets(&recording, &sensors)
+    })
+    .await
+    .context("Recording loader panicked")?
+    .with_context(|| format!("Failed to read recording {}", args.recording.display()))?;
+
+    info!(
+        packets = packets.len(),
+        "Recording loaded, solving batch sync"
+    );
+
+    let sync_config = blueprint.to_sync_engine_config();
+    let output = BatchSynchronizer::new(sync_config).synchronize(packets);
+
+    print_summary(&output);
+
+    let limit = match args.max_frames {
+        0 => usize::MAX,
+        max => max as usize,
+    };
+
+    if blueprint.sinks.is_empty() {
+        warn!("No sinks configured - synced frames will be dropped");
+    }
+
+    // Offline runs must not drop frames: wait for slow sinks instead
+    let (tx, rx) = mpsc::channel::<SyncedFrame>(args.buffer_size.max(1));
+    let config = dispatcher::DispatcherConfig {
+        sinks: blueprint.sinks.clone(),
+    };
+    let dispatcher = dispatcher::DispatcherBuilder::new(config, rx)
+        .with_backpressure()
+        .build()
+        .await
+        .context("Failed to create dispatcher")?;
+    let dispatcher_handle = dispatcher.spawn();
+
+    for frame in output.frames.into_iter().take(limit) {
+        if tx.send(frame).await.is_err() {
+            warn!("Dispatcher channel closed");
+            break;
+        }
+    }
+    drop(tx);
+
+    dispatcher_handle.await.context("Dispatcher task failed")?;
+
+    info!("Batch sync finished");
+    Ok(())
+}
+
+fn print_summary(output: &BatchOutput) {
+    println!("\n=== Batch Sync Summary ===\n");
+    println!("  Frames:         {}", output.frames.len());
+    println!("  Dropped frames: {}", output.dropped_frames);
+
+    let mut residuals: HashMap<&str, (f64, usize)> = HashMap::new();
+    for frame in &output.frames {
+        for (sensor_id, residual) in &frame.sync_meta.kf_residuals {
+            let entry = residuals.entry(sensor_id).or_default();
+            entry.0 += residual.abs();
+            entry.1 += 1;
+        }
+    }
+
+    let mut sensor_ids: Vec<&str> = residuals.keys().copied().collect();
+    sensor_ids.sort_unstable();
+    if !sensor_ids.is_empty() {
+        println!("\n  Mean |residual| per sensor:");
+        for sensor_id in sensor_ids {
+            let (sum, count) = residuals[sensor_id];
+            println!(
+                "    {:<20} {:>8.3} ms ({} frames)",
+                sensor_id,
+                sum / count as f64 * 1000.0,
+                count
+            );
+        }
+    }
+
+    let mut unassigned: Vec<_> = output.unassigned.iter().filter(|(_, n)| **n > 0).collect();
+    unassigned.sort_by(|a, b| a.0.cmp(b.0));
+    if !unassigned.is_empty() {
+        println!("\n  Unassigned packets:");
+        for (sensor_id, count) in unassigned {
+            println!("    {:<20} {}", sensor_id, count);
+        }
+    }
+
+    println!();
+}
@@ -1,9 +1,13 @@
 //! Command implementations.
 
+mod batch_sync;
 mod info;
+mod record;
 mod run;
 mod validate;
 
+pub use batch_sync::run_batch_sync;
 pub use info::run_info;
+pub use record::run_record;
 pub use run::run_pipeline;
 pub use validate::run_validate;
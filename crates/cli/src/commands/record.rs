@@ -0,0 +1,73 @@
+//! `record` command implementation.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+
+use super::run::setup_shutdown_signal;
+use crate::cli::RecordArgs;
+use crate::pipeline::{Recorder, RecorderConfig, RecordingStats};
+
+/// Execute the `record` command
+pub async fn run_record(args: &RecordArgs) -> Result<()> {
+    info!(config = %args.config.display(), "Loading configuration for recording");
+
+    if !args.config.exists() {
+        anyhow::bail!("Configuration file not found: {}", args.config.display());
+    }
+    if args.output.join("sensors.jsonl").exists() {
+        anyhow::bail!(
+            "Recording already exists in {}, choose another --output",
+            args.output.display()
+        );
+    }
+    if !(args.duration >= 0.0 && args.duration.is_finite()) {
+        anyhow::bail!("--duration must be >= 0, got {}", args.duration);
+    }
+
+    let mut blueprint = config_loader::ConfigLoader::load_from_path(&args.config)
+        .with_context(|| format!("Failed to load config from {}", args.config.display()))?;
+    if let Some(ref host) = args.host {
+        blueprint.world.carla_host = host.clone();
+    }
+    if let Some(port) = args.port {
+        blueprint.world.carla_port = port;
+    }
+
+    let recorder = Recorder::new(RecorderConfig {
+        blueprint,
+        output: args.output.clone(),
+        duration: (args.duration > 0.0).then(|| Duration::from_secs_f64(args.duration)),
+        buffer_size: args.buffer_size,
+    });
+
+    let shutdown = recorder.shutdown_handle();
+    tokio::spawn(async move {
+        setup_shutdown_signal().await;
+        warn!("Received shutdown signal, finishing recording...");
+        shutdown.notify_one();
+    });
+
+    let stats = recorder.run().await.context("Recording failed")?;
+    print_summary(&stats, &args.output);
+    Ok(())
+}
+
+/// Print per-sensor packet counts
+fn print_summary(stats: &RecordingStats, output: &std::path::Path) {
+    println!("\n=== Recording Summary ===\n");
+    println!("  Output:    {}", output.display());
+    println!("  Packets:   {}", stats.packets());
+    println!("  Sim time:  {:.3} s", stats.duration_sec);
+    println!("  Wall time: {:.3} s", stats.elapsed.as_secs_f64());
+
+    if !stats.frame_counts.is_empty() {
+        println!("\n  Packets per sensor:");
+        for (sensor_id, count) in &stats.frame_counts {
+            println!("    {:<20} {}", sensor_id, count);
+        }
+    }
+
+    println!();
+}
@@ -1,24 +1,64 @@
 //! `run` command implementation.
 
 use anyhow::{Context, Result};
+use std::path::PathBuf;
 use std::time::Duration;
 use tracing::{info, warn};
 
 use crate::cli::RunArgs;
 use crate::pipeline::{Pipeline, PipelineConfig};
 
+/// Configuration file used when `--config` is not given
+const DEFAULT_CONFIG: &str = "config.toml";
+
 /// Execute the `run` command
 pub async fn run_pipeline(args: &RunArgs) -> Result<()> {
-    info!(config = %args.config.display(), "Loading configuration");
+    let recording = args
+        .replay
+        .as_deref()
+        .map(actor_factory::RecordingSummary::load)
+        .transpose()?;
+
+    // An explicit --config must exist; the default one is optional when
+    // replaying
+    let config_path = args
+        .config
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG));
+
+    let mut blueprint = match &recording {
+        // Without a configuration file, replay everything the recording holds
+        Some(recording) if args.config.is_none() && !config_path.exists() => {
+            info!(
+                recording = %recording.root.display(),
+                "No configuration file, deriving blueprint from recording"
+            );
+            let blueprint = recording.to_blueprint()?;
+            config_loader::ConfigLoader::validate(&blueprint)
+                .context("Blueprint derived from recording is invalid")?;
+            blueprint
+        }
+        _ => {
+            info!(config = %config_path.display(), "Loading configuration");
 
-    // Validate config path
-    if !args.config.exists() {
-        anyhow::bail!("Configuration file not found: {}", args.config.display());
-    }
+            // Validate config path
+            if !config_path.exists() {
+                anyhow::bail!("Configuration file not found: {}", config_path.display());
+            }
+
+            // Load and parse configuration
+            let blueprint = config_loader::ConfigLoader::load_from_path(&config_path)
+                .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
 
-    // Load and parse configuration
-    let mut blueprint = config_loader::ConfigLoader::load_from_path(&args.config)
-        .with_context(|| format!("Failed to load config from {}", args.config.display()))?;
+            // Replaying needs the configured sensors to exist in the recording
+            if let Some(recording) = &recording {
+                for warning in recording.check(&blueprint)? {
+                    warn!(%warning, "Configuration differs from recording");
+                }
+            }
+            blueprint
+        }
+    };
 
     // Apply CLI overrides
     if let Some(ref host) = args.host {
@@ -46,6 +86,24 @@ pub async fn run_pipeline(args: &RunArgs) -> Result<()> {
         return Ok(());
     }
 
+    if let Some(start) = args.replay_start.filter(|s| s.is_nan() || *s < 0.0) {
+        anyhow::bail!("--replay-start must be >= 0, got {}", start);
+    }
+    if let (Some(start), Some(end)) = (args.replay_start, args.replay_end) {
+        if end <= start {
+            anyhow::bail!(
+                "--replay-end ({}) must be after --replay-start ({})",
+                end,
+                start
+            );
+        }
+    }
+
+    let checkpoint_path = args
+        .checkpoint
+        .clone()
+        .or_else(|| blueprint.sync.engine.checkpoint_path.clone());
+
     // Build pipeline configuration
     let pipeline_config = PipelineConfig {
         blueprint,
@@ -68,48 +126,46 @@ pub async fn run_pipeline(args: &RunArgs) -> Result<()> {
         replay_path: args.replay.clone(),
         replay_speed: args.replay_speed,
         replay_loop: args.replay_loop,
+        replay_start: args.replay_start,
+        replay_end: args.replay_end,
+        replay_paused: args.replay_paused,
+        replay_fast: args.replay_fast,
+        replay_control: args.replay_control,
+        checkpoint_path,
     };
 
     // Create and run pipeline
     let pipeline = Pipeline::new(pipeline_config);
 
-    // Setup graceful shutdown handler
-    let shutdown_signal = setup_shutdown_signal();
+    // Setup graceful shutdown handler: the pipeline drains and saves its
+    // checkpoint before returning
+    let shutdown = pipeline.shutdown_handle();
+    tokio::spawn(async move {
+        setup_shutdown_signal().await;
+        warn!("Received shutdown signal, stopping pipeline...");
+        shutdown.notify_one();
+    });
 
     info!("Starting pipeline...");
 
-    // Run pipeline with shutdown signal
-    tokio::select! {
-        result = pipeline.run() => {
-            match result {
-                Ok(stats) => {
-                    info!(
-                        frames_synced = stats.frames_synced,
-                        frames_dropped = stats.frames_dropped,
-                        duration_secs = stats.duration.as_secs_f64(),
-                        fps = format!("{:.2}", stats.fps()),
-                        "Pipeline completed successfully"
-                    );
-
-                    // Print detailed statistics
-                    stats.print_summary();
-                }
-                Err(e) => {
-                    return Err(e).context("Pipeline execution failed");
-                }
-            }
-        }
-        _ = shutdown_signal => {
-            warn!("Received shutdown signal, stopping pipeline...");
-        }
-    }
+    let stats = pipeline.run().await.context("Pipeline execution failed")?;
+    info!(
+        frames_synced = stats.frames_synced,
+        frames_dropped = stats.frames_dropped,
+        duration_secs = stats.duration.as_secs_f64(),
+        fps = format!("{:.2}", stats.fps()),
+        "Pipeline completed successfully"
+    );
+
+    // Print detailed statistics
+    stats.print_summary();
 
     info!("CARLA Syncer finished");
     Ok(())
 }
 
 /// Setup Ctrl+C and SIGTERM signal handlers
-async fn setup_shutdown_signal() {
+pub(super) async fn setup_shutdown_signal() {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -18,7 +18,7 @@ use tracing::info;
 use tracing_subscriber::Layer;
 
 use cli::{Cli, Commands};
-use commands::{run_info, run_pipeline, run_validate};
+use commands::{run_batch_sync, run_info, run_pipeline, run_record, run_validate};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -40,6 +40,8 @@ async fn main() -> Result<()> {
         Commands::Run(args) => run_pipeline(args).await,
         Commands::Validate(args) => run_validate(args),
         Commands::Info(args) => run_info(args),
+        Commands::BatchSync(args) => run_batch_sync(args).await,
+        Commands::Record(args) => run_record(args).await,
     };
 
     if let Err(ref e) = result {
@@ -1,7 +1,9 @@
 //! Pipeline orchestration module.
 
 mod orchestrator;
+mod recorder;
 mod stats;
 
 pub use orchestrator::{Pipeline, PipelineConfig};
+pub use recorder::{Recorder, RecorderConfig, RecordingStats};
 pub use stats::PipelineStats;
@@ -3,17 +3,26 @@
 //! Supports both real CARLA and mock modes via feature flags.
 //! When `real-carla` feature is disabled, runs in mock mode.
 
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use actor_factory::{ActorFactory, CarlaClient};
+use actor_factory::{ActorFactory, CarlaClient, ReplayClock};
 use anyhow::{Context, Result};
-use contracts::{RuntimeGraph, SensorConfig, SyncedFrame, WorldBlueprint};
-use observability::record_sync_metrics;
-use tokio::sync::mpsc;
-use tracing::{info, warn};
+use contracts::{
+    GroundTruthLog, MemoryAccountant, MemoryStage, RuntimeGraph, SensorConfig, SimTime,
+    SyncedFrame, WorldBlueprint,
+};
+use observability::{record_memory_usage, record_sync_metrics};
+use tokio::sync::{mpsc, Notify};
+use tracing::{debug, info, warn};
 
 use super::PipelineStats;
 
+/// Time allowed for sync groups to stop during shutdown
+const GROUP_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Pipeline configuration
 #[derive(Debug, Clone)]
 pub struct PipelineConfig {
@@ -43,17 +52,49 @@ pub struct PipelineConfig {
     /// Loop replay when finished
     #[cfg_attr(feature = "real-carla", allow(dead_code))]
     pub replay_loop: bool,
+
+    /// Replay range start (s into the recording)
+    #[cfg_attr(feature = "real-carla", allow(dead_code))]
+    pub replay_start: Option<f64>,
+
+    /// Replay range end (s into the recording)
+    #[cfg_attr(feature = "real-carla", allow(dead_code))]
+    pub replay_end: Option<f64>,
+
+    /// Start replay paused
+    #[cfg_attr(feature = "real-carla", allow(dead_code))]
+    pub replay_paused: bool,
+
+    /// Replay as fast as downstream accepts
+    #[cfg_attr(feature = "real-carla", allow(dead_code))]
+    pub replay_fast: bool,
+
+    /// Read replay transport commands from stdin
+    #[cfg_attr(feature = "real-carla", allow(dead_code))]
+    pub replay_control: bool,
+
+    /// Sync state checkpoint loaded at startup and saved at shutdown
+    pub checkpoint_path: Option<PathBuf>,
 }
 
 /// Main pipeline orchestrator
 pub struct Pipeline {
     config: PipelineConfig,
+    shutdown: Arc<Notify>,
 }
 
 impl Pipeline {
     /// Create a new pipeline with the given configuration
     pub fn new(config: PipelineConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Handle that stops the pipeline gracefully when notified
+    pub fn shutdown_handle(&self) -> Arc<Notify> {
+        self.shutdown.clone()
     }
 
     /// Run the pipeline to completion
@@ -115,7 +156,7 @@ impl Pipeline {
 
         // Run common pipeline logic
         let stats = self
-            .run_pipeline_common(&client, &factory, &runtime_graph, start_time)
+            .run_pipeline_common(&client, &factory, &runtime_graph, start_time, None, None)
             .await?;
 
         // Cleanup
@@ -138,13 +179,39 @@ impl Pipeline {
             info!("Metrics endpoint available on port {}", port);
         }
 
+        // Simulated sensor clocks; ground truth is tracked when any is set
+        let sensor_clocks = sensor_clocks(blueprint);
+        let ground_truth = (!sensor_clocks.is_empty()).then(GroundTruthLog::new);
+        if ground_truth.is_some() {
+            info!(
+                sensors = sensor_clocks.len(),
+                "Simulated sensor clocks enabled, evaluating sync accuracy"
+            );
+        }
+
+        // Injected stream faults (mock and replay sources)
+        let sensor_faults = sensor_faults(blueprint);
+        if !sensor_faults.is_empty() {
+            info!(
+                sensors = sensor_faults.len(),
+                "Sensor fault injection enabled"
+            );
+        }
+
         // Configure mock client with optional replay
         let mock_config = MockConfig {
             replay_config: ReplayConfig {
                 replay_path: self.config.replay_path.clone(),
                 speed_multiplier: self.config.replay_speed,
                 loop_playback: self.config.replay_loop,
+                start_s: self.config.replay_start,
+                end_s: self.config.replay_end,
+                start_paused: self.config.replay_paused,
+                fast: self.config.replay_fast,
             },
+            sensor_clocks,
+            sensor_faults,
+            ground_truth: ground_truth.clone(),
             ..Default::default()
         };
 
@@ -168,6 +235,14 @@ impl Pipeline {
 
         info!("Mock CARLA client initialized");
 
+        let replay_clock = client.replay_clock().cloned();
+        if let Some(clock) = &replay_clock {
+            info!(status = %clock.status(), "Replay transport ready");
+            if self.config.replay_control {
+                spawn_replay_control(clock.clone());
+            }
+        }
+
         // Spawn Actors (Mock)
         info!("Spawning actors from blueprint (mock)...");
         let factory = ActorFactory::new(client.clone());
@@ -184,7 +259,14 @@ impl Pipeline {
 
         // Run common pipeline logic
         let stats = self
-            .run_pipeline_common(&client, &factory, &runtime_graph, start_time)
+            .run_pipeline_common(
+                &client,
+                &factory,
+                &runtime_graph,
+                start_time,
+                ground_truth,
+                replay_clock,
+            )
             .await?;
 
         // Cleanup
@@ -200,12 +282,29 @@ impl Pipeline {
         _factory: &ActorFactory<C>,
         runtime_graph: &RuntimeGraph,
         start_time: Instant,
+        ground_truth: Option<GroundTruthLog>,
+        replay_clock: Option<ReplayClock>,
     ) -> Result<PipelineStats> {
         let blueprint = &self.config.blueprint;
 
+        // Payload byte budgets shared by ingestion queues, sync buffers and sinks
+        let memory = MemoryAccountant::new(&blueprint.memory);
+        for stage in MemoryStage::ALL {
+            if let Some(limit) = memory.limit(stage) {
+                info!(
+                    stage = stage.as_str(),
+                    limit_bytes = limit,
+                    "Memory budget enabled"
+                );
+            }
+        }
+
         // Setup Ingestion Pipeline
         info!("Setting up ingestion pipeline...");
-        let mut ingestion = ingestion::IngestionPipeline::new(self.config.buffer_size);
+        let mut ingestion = ingestion::IngestionPipeline::with_config(
+            ingestion::BackpressureConfig::new(self.config.buffer_size, blueprint.sync.drop_policy),
+        )
+        .with_memory(memory.clone());
         let mut active_sensors = 0usize;
 
         for (sensor_config_id, actor_id) in &runtime_graph.sensors {
@@ -216,7 +315,7 @@ impl Pipeline {
                     sensor_config_id.clone(),
                     sensor_config.sensor_type,
                 ) {
-                    ingestion.register_sensor_source(sensor_config_id.clone(), sensor_source, None);
+                    register_source(&mut ingestion, sensor_config, sensor_source);
                     active_sensors += 1;
                 } else {
                     warn!(sensor_id = %sensor_config_id, "Failed to get sensor source");
@@ -224,18 +323,107 @@ impl Pipeline {
             }
         }
 
+        // Sensors streamed by external processes (no CARLA actor)
+        let external_sensors = blueprint.vehicles.iter().flat_map(|v| v.sensors.iter());
+        for sensor_config in external_sensors {
+            let contracts::SensorSourceKind::External { endpoint, encoding } =
+                &sensor_config.source
+            else {
+                continue;
+            };
+            let source = actor_factory::ExternalSensorSource::bind(
+                sensor_config.id.clone(),
+                sensor_config.sensor_type,
+                endpoint.clone(),
+                *encoding,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to bind external sensor '{}' on {}",
+                    sensor_config.id, endpoint
+                )
+            })?;
+            register_source(&mut ingestion, sensor_config, Box::new(source));
+            active_sensors += 1;
+        }
+
         info!(active_sensors, "Ingestion pipeline configured");
 
-        // Setup Sync Engine
-        info!("Configuring sync engine...");
-        let sync_config = blueprint.to_sync_engine_config();
-        let mut sync_engine = sync_engine::SyncEngine::new(sync_config.clone());
+        // Fast replay holds back while any sensor queue is over half full
+        if let Some(clock) = &replay_clock {
+            let backlog = ingestion.backlog();
+            clock.set_gate(move || backlog.max_fill() < 0.5);
+        }
+
+        // Setup Sync Engines (one per sync group)
+        info!("Configuring sync engines...");
+        let group_configs = blueprint.to_sync_group_configs();
+        let router =
+            sync_engine::GroupRouter::new(&group_configs, &runtime_graph.sensor_to_vehicle);
+        let mut joiner = blueprint.sync.cross_vehicle.as_ref().map(|join| {
+            let vehicle_ids = group_configs
+                .iter()
+                .filter_map(|config| config.vehicle_id.clone())
+                .collect();
+            (
+                sync_engine::FrameJoiner::new(vehicle_ids, join.tolerance_s),
+                join.forward_vehicle_frames,
+            )
+        });
+
+        let checkpoint = self.load_checkpoint();
+
+        let (group_frame_tx, mut group_frame_rx) =
+            mpsc::channel::<SyncedFrame>(self.config.buffer_size);
+        let mut group_packet_txs = Vec::with_capacity(group_configs.len());
+        let mut group_tasks = Vec::with_capacity(group_configs.len());
+
+        for sync_config in group_configs {
+            info!(
+                vehicle_id = ?sync_config.vehicle_id,
+                reference_sensor = %sync_config.reference_sensor_id,
+                required_sensors = ?sync_config.required_sensors,
+                latency_budget_s = ?sync_config.latency_budget_s,
+                output_clock = ?sync_config.output_clock,
+                liveness = ?sync_config.liveness,
+                "Sync engine configured"
+            );
+
+            let vehicle_id = sync_config.vehicle_id.clone();
+            let mut engine = sync_engine::SyncEngine::new(sync_config).with_memory(memory.clone());
+            if let Some(saved) = checkpoint
+                .as_ref()
+                .and_then(|file| file.find(vehicle_id.as_deref()))
+            {
+                match engine.restore(saved) {
+                    Ok(summary) => info!(
+                        vehicle_id = ?vehicle_id,
+                        sensors = summary.restored,
+                        ignored = summary.ignored,
+                        "Sync engine warm-started"
+                    ),
+                    Err(e) => {
+                        warn!(vehicle_id = ?vehicle_id, error = %e, "Checkpoint ignored, cold start")
+                    }
+                }
+            }
 
-        info!(
-            reference_sensor = %sync_config.reference_sensor_id,
-            required_sensors = ?sync_config.required_sensors,
-            "Sync engine configured"
-        );
+            let (packet_tx, packet_rx) = mpsc::channel(self.config.buffer_size);
+            // Deadlines follow the replay transport (speed, pause, fast
+            // mode); live runs use the configured deadline clock
+            let mut driver = sync_engine::DeadlineDriver::new(engine);
+            if let Some(clock) = &replay_clock {
+                let clock = clock.clone();
+                driver = driver.with_clock(sync_engine::SimClock::External(Arc::new(move || {
+                    // A non-finite transport time falls back to the newest
+                    // packet time in the driver
+                    clock.sim_time().and_then(SimTime::try_from_secs_f64)
+                })));
+            }
+            group_tasks.push(tokio::spawn(driver.run(packet_rx, group_frame_tx.clone())));
+            group_packet_txs.push(packet_tx);
+        }
+        drop(group_frame_tx);
 
         // Setup Dispatcher
         info!("Setting up dispatcher...");
@@ -245,9 +433,16 @@ impl Pipeline {
             warn!("No sinks configured - synced frames will be dropped");
         }
 
-        let dispatcher = dispatcher::create_dispatcher(blueprint.sinks.clone(), sync_rx)
-            .await
-            .context("Failed to create dispatcher")?;
+        let dispatcher = dispatcher::DispatcherBuilder::new(
+            dispatcher::DispatcherConfig {
+                sinks: blueprint.sinks.clone(),
+            },
+            sync_rx,
+        )
+        .with_memory(memory.clone())
+        .build()
+        .await
+        .context("Failed to create dispatcher")?;
 
         let active_sinks = blueprint.sinks.len();
         let dispatcher_handle = dispatcher.spawn();
@@ -257,12 +452,36 @@ impl Pipeline {
         // Start Pipeline
         info!("Starting sensor data ingestion...");
         ingestion.start_all();
-        let ingestion_rx = ingestion
+        let mut ingestion_rx = ingestion
             .take_receiver()
             .context("Failed to get ingestion receiver")?;
 
+        // Route packets to the sync group owning each sensor
+        let packets_received = Arc::new(AtomicU64::new(0));
+        let router_task = {
+            let packets_received = packets_received.clone();
+            tokio::spawn(async move {
+                while let Ok(packet) = ingestion_rx.recv().await {
+                    packets_received.fetch_add(1, Ordering::Relaxed);
+                    match router.route(&packet.sensor_id) {
+                        Some(idx) => {
+                            if group_packet_txs[idx].send(packet).await.is_err() {
+                                break;
+                            }
+                        }
+                        None => {
+                            debug!(sensor_id = %packet.sensor_id, "No sync group for sensor, packet dropped");
+                        }
+                    }
+                }
+            })
+        };
+
         let max_frames = self.config.max_frames;
         let sync_tx_clone = sync_tx;
+        let shutdown = self.shutdown.clone();
+        let mut evaluator = ground_truth.map(sync_engine::SyncEvaluator::new);
+        let pipeline_memory = memory.clone();
 
         #[cfg(feature = "real-carla")]
         info!(max_frames = ?max_frames, "Pipeline running (CARLA mode)");
@@ -278,20 +497,43 @@ impl Pipeline {
                 ..Default::default()
             };
 
-            while let Ok(packet) = ingestion_rx.recv().await {
-                stats.packets_received += 1;
+            'frames: loop {
+                let frame = tokio::select! {
+                    frame = group_frame_rx.recv() => match frame {
+                        Some(frame) => frame,
+                        None => break 'frames,
+                    },
+                    _ = shutdown.notified() => break 'frames,
+                };
+
+                let outputs = match joiner.as_mut() {
+                    None => vec![frame],
+                    Some((joiner, forward_vehicle_frames)) => {
+                        let mut outputs = Vec::with_capacity(2);
+                        if *forward_vehicle_frames {
+                            outputs.push(frame.clone());
+                        }
+                        outputs.extend(joiner.push(frame));
+                        outputs
+                    }
+                };
 
-                if let Some(frame) = sync_engine.push(packet) {
+                for frame in outputs {
                     stats.frames_synced += 1;
+                    if let Some(evaluator) = evaluator.as_mut() {
+                        evaluator.observe(&frame);
+                    }
 
                     // Record metrics from SyncMeta
                     record_sync_metrics(&frame.sync_meta, frame.frame_id);
+                    record_memory_usage(&pipeline_memory);
                     stats.sync_metrics.update(&frame.sync_meta);
 
                     // Update dropped count from sync meta
                     stats.frames_dropped += frame.sync_meta.dropped_count as u64;
 
                     info!(
+                        vehicle_id = ?frame.vehicle_id,
                         frame_id = frame.frame_id,
                         t_sync = format!("{:.3}", frame.t_sync),
                         sensors = frame.frames.len(),
@@ -303,19 +545,20 @@ impl Pipeline {
 
                     if sync_tx_clone.send(frame).await.is_err() {
                         warn!("Dispatcher channel closed");
-                        break;
+                        break 'frames;
                     }
 
                     // Check max frames limit
                     if let Some(max) = max_frames {
                         if stats.frames_synced >= max {
                             info!(frames = stats.frames_synced, "Reached max frames limit");
-                            break;
+                            break 'frames;
                         }
                     }
                 }
             }
 
+            stats.sync_evaluation = evaluator.map(|evaluator| evaluator.report());
             stats
         };
 
@@ -335,11 +578,34 @@ impl Pipeline {
         // Shutdown
         info!("Shutting down pipeline...");
         ingestion.stop_all();
+        router_task.abort();
+        let _ = router_task.await;
+
+        // Dropping the router closes every group's packet channel, so the
+        // drivers return their engines for checkpointing
+        let mut engines = Vec::with_capacity(group_tasks.len());
+        for task in group_tasks {
+            match tokio::time::timeout(GROUP_DRAIN_TIMEOUT, task).await {
+                Ok(Ok(engine)) => engines.push(engine),
+                Ok(Err(e)) => warn!(error = %e, "Sync group task failed"),
+                Err(_) => warn!("Sync group did not stop in time"),
+            }
+        }
+        self.save_checkpoint(&engines);
 
         // Wait for dispatcher to flush
         let _ = tokio::time::timeout(Duration::from_secs(5), dispatcher_handle).await;
 
+        for stage in MemoryStage::ALL {
+            info!(
+                stage = stage.as_str(),
+                peak_bytes = memory.peak(stage),
+                "Memory high-water mark"
+            );
+        }
+
         let mut final_stats = stats;
+        final_stats.packets_received = packets_received.load(Ordering::Relaxed);
         final_stats.duration = start_time.elapsed();
 
         info!(
@@ -351,6 +617,41 @@ impl Pipeline {
         Ok(final_stats)
     }
 
+    /// Load the configured checkpoint, if present and readable
+    fn load_checkpoint(&self) -> Option<sync_engine::CheckpointFile> {
+        let path = self.config.checkpoint_path.as_ref()?;
+        if !path.exists() {
+            info!(path = %path.display(), "No checkpoint found, cold start");
+            return None;
+        }
+        match sync_engine::CheckpointFile::load(path) {
+            Ok(file) => {
+                info!(path = %path.display(), engines = file.engines.len(), "Checkpoint loaded");
+                Some(file)
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Failed to load checkpoint, cold start");
+                None
+            }
+        }
+    }
+
+    /// Save the learned state of every sync engine
+    fn save_checkpoint(&self, engines: &[sync_engine::SyncEngine]) {
+        let Some(path) = self.config.checkpoint_path.as_ref() else {
+            return;
+        };
+        if engines.is_empty() {
+            return;
+        }
+        let file =
+            sync_engine::CheckpointFile::new(engines.iter().map(|e| e.checkpoint()).collect());
+        match file.save(path) {
+            Ok(()) => info!(path = %path.display(), "Checkpoint saved"),
+            Err(e) => warn!(path = %path.display(), error = %e, "Failed to save checkpoint"),
+        }
+    }
+
     /// Cleanup actors
     async fn cleanup<C: CarlaClient>(
         &self,
@@ -363,11 +664,90 @@ impl Pipeline {
     }
 }
 
+/// Register a sensor source with its queue and pre-processing settings
+fn register_source(
+    ingestion: &mut ingestion::IngestionPipeline,
+    sensor_config: &SensorConfig,
+    source: Box<dyn contracts::SensorSource>,
+) {
+    let queue_config = queue_config(ingestion, sensor_config);
+    ingestion.register_sensor_source_with_preprocess(
+        sensor_config.id.clone(),
+        source,
+        queue_config,
+        sensor_config.preprocess.clone(),
+    );
+}
+
+/// Per-sensor queue settings (None = pipeline defaults)
+pub(super) fn queue_config(
+    ingestion: &ingestion::IngestionPipeline,
+    sensor_config: &SensorConfig,
+) -> Option<ingestion::BackpressureConfig> {
+    sensor_config.ingestion.map(|queue| {
+        let defaults = ingestion.default_config();
+        ingestion::BackpressureConfig::new(
+            queue.capacity.unwrap_or(defaults.channel_capacity),
+            queue.drop_policy.unwrap_or(defaults.drop_policy),
+        )
+        .with_weight(queue.weight)
+    })
+}
+
+/// Simulated clock error per sensor ID
+#[cfg(not(feature = "real-carla"))]
+pub(super) fn sensor_clocks(
+    blueprint: &WorldBlueprint,
+) -> std::collections::HashMap<String, contracts::ClockModel> {
+    blueprint
+        .vehicles
+        .iter()
+        .flat_map(|vehicle| vehicle.sensors.iter())
+        .filter_map(|sensor| sensor.mock_clock.map(|clock| (sensor.id.clone(), clock)))
+        .collect()
+}
+
+/// Injected stream faults per sensor ID
+#[cfg(not(feature = "real-carla"))]
+pub(super) fn sensor_faults(
+    blueprint: &WorldBlueprint,
+) -> std::collections::HashMap<String, contracts::FaultModel> {
+    blueprint
+        .vehicles
+        .iter()
+        .flat_map(|vehicle| vehicle.sensors.iter())
+        .filter_map(|sensor| sensor.mock_faults.map(|faults| (sensor.id.clone(), faults)))
+        .collect()
+}
+
 /// Find a sensor configuration by ID in the blueprint
-fn find_sensor<'a>(blueprint: &'a WorldBlueprint, sensor_id: &str) -> Option<&'a SensorConfig> {
+pub(super) fn find_sensor<'a>(
+    blueprint: &'a WorldBlueprint,
+    sensor_id: &str,
+) -> Option<&'a SensorConfig> {
     blueprint
         .vehicles
         .iter()
         .flat_map(|vehicle| vehicle.sensors.iter())
         .find(|sensor| sensor.id == sensor_id)
 }
+
+/// Read replay transport commands from stdin, one per line
+#[cfg(not(feature = "real-carla"))]
+fn spawn_replay_control(clock: ReplayClock) {
+    use std::io::BufRead;
+
+    info!("Replay control on stdin: pause | resume | seek <s> | step <s> | next [n] | speed <x> | fast on|off | status");
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(|line| line.ok()) {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match line.parse::<actor_factory::ReplayCommand>() {
+                Ok(cmd) => info!(status = %clock.apply(cmd), "Replay"),
+                Err(e) => warn!(error = %e, "Invalid replay command"),
+            }
+        }
+    });
+}
@@ -0,0 +1,309 @@
+//! Recorder - writes raw sensor packets for later replay.
+//!
+//! Spawns the blueprint's actors like the pipeline does, but stores every
+//! packet leaving ingestion, unsynchronized and without pre-processing, in the
+//! layout read by `--replay`. Sensors fed by external processes are not
+//! recorded. In mock mode the clean stream is recorded: `mock_faults` are
+//! left for replay to apply.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actor_factory::{ActorFactory, CarlaClient, RecordingWriter};
+use anyhow::{Context, Result};
+use contracts::{RuntimeGraph, WorldBlueprint};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+use super::orchestrator::{find_sensor, queue_config};
+
+/// Recorder configuration
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    /// The world blueprint configuration
+    pub blueprint: WorldBlueprint,
+
+    /// Recording directory (must not hold a recording yet)
+    pub output: PathBuf,
+
+    /// Recording length (None = until shutdown)
+    pub duration: Option<Duration>,
+
+    /// Channel buffer size
+    pub buffer_size: usize,
+}
+
+/// Outcome of a recording session
+#[derive(Debug, Default)]
+pub struct RecordingStats {
+    /// Packets written per sensor ID
+    pub frame_counts: Vec<(String, u64)>,
+
+    /// Recorded simulation time span (seconds)
+    pub duration_sec: f64,
+
+    /// Wall-clock recording time
+    pub elapsed: Duration,
+}
+
+impl RecordingStats {
+    /// Total packets written
+    pub fn packets(&self) -> u64 {
+        self.frame_counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Records the raw packets of every spawned sensor
+pub struct Recorder {
+    config: RecorderConfig,
+    shutdown: Arc<Notify>,
+}
+
+impl Recorder {
+    /// Create a recorder with the given configuration
+    pub fn new(config: RecorderConfig) -> Self {
+        Self {
+            config,
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Handle that ends the recording gracefully when notified
+    pub fn shutdown_handle(&self) -> Arc<Notify> {
+        self.shutdown.clone()
+    }
+
+    /// Record until the duration elapses or shutdown is requested
+    pub async fn run(self) -> Result<RecordingStats> {
+        let blueprint = &self.config.blueprint;
+
+        #[cfg(feature = "real-carla")]
+        let (mut client, carla_version) = (actor_factory::RealCarlaClient::new(), "unknown");
+
+        #[cfg(not(feature = "real-carla"))]
+        let (mut client, carla_version) = {
+            use super::orchestrator::sensor_clocks;
+
+            info!("Recording in MOCK mode (no CARLA server required)");
+            let mock_config = actor_factory::MockConfig {
+                sensor_clocks: sensor_clocks(blueprint),
+                ..Default::default()
+            };
+            (
+                actor_factory::MockCarlaClient::with_config(mock_config),
+                "mock",
+            )
+        };
+
+        client
+            .connect(&blueprint.world.carla_host, blueprint.world.carla_port)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to connect to CARLA at {}:{}",
+                    blueprint.world.carla_host, blueprint.world.carla_port
+                )
+            })?;
+
+        let factory = ActorFactory::new(client.clone());
+        let runtime_graph = factory
+            .spawn_from_blueprint(blueprint)
+            .await
+            .context("Failed to spawn actors")?;
+
+        info!(
+            vehicles = runtime_graph.vehicles.len(),
+            sensors = runtime_graph.sensors.len(),
+            "Actors spawned successfully"
+        );
+
+        let result = self.record(&client, &runtime_graph, carla_version).await;
+
+        if let Err(e) = factory.teardown(&runtime_graph).await {
+            warn!(error = %e, "Error during actor teardown");
+        }
+
+        result
+    }
+
+    /// Pump ingestion into the recording writer
+    async fn record<C: CarlaClient>(
+        &self,
+        client: &C,
+        runtime_graph: &RuntimeGraph,
+        carla_version: &str,
+    ) -> Result<RecordingStats> {
+        let blueprint = &self.config.blueprint;
+        let start_time = Instant::now();
+
+        let mut writer =
+            RecordingWriter::create(&self.config.output, carla_version).with_context(|| {
+                format!(
+                    "Failed to create recording in {}",
+                    self.config.output.display()
+                )
+            })?;
+
+        let mut ingestion = ingestion::IngestionPipeline::with_config(
+            ingestion::BackpressureConfig::new(self.config.buffer_size, blueprint.sync.drop_policy),
+        );
+        for (sensor_id, actor_id) in &runtime_graph.sensors {
+            let Some(sensor_config) = find_sensor(blueprint, sensor_id) else {
+                continue;
+            };
+            match client.get_sensor_source(*actor_id, sensor_id.clone(), sensor_config.sensor_type)
+            {
+                // Raw packets: queue settings apply, pre-processing does not
+                Some(source) => ingestion.register_sensor_source(
+                    sensor_id.clone(),
+                    source,
+                    queue_config(&ingestion, sensor_config),
+                ),
+                None => warn!(sensor_id = %sensor_id, "Failed to get sensor source"),
+            }
+        }
+
+        let mut packets = ingestion
+            .take_receiver()
+            .context("Failed to get ingestion receiver")?;
+
+        info!(
+            sensors = ingestion.sensor_count(),
+            output = %self.config.output.display(),
+            duration = ?self.config.duration,
+            "Recording started"
+        );
+        ingestion.start_all();
+
+        let deadline = async {
+            match self.config.duration {
+                Some(duration) => tokio::time::sleep(duration).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(deadline);
+
+        let recorded: Result<()> = async {
+            loop {
+                let packet = tokio::select! {
+                    packet = packets.recv() => match packet {
+                        Ok(packet) => packet,
+                        Err(_) => return Ok(()),
+                    },
+                    _ = &mut deadline => return Ok(()),
+                    _ = self.shutdown.notified() => return Ok(()),
+                };
+                tokio::task::block_in_place(|| writer.write(&packet)).with_context(|| {
+                    format!("Failed to record packet of '{}'", packet.sensor_id)
+                })?;
+            }
+        }
+        .await;
+
+        // Sources stop and the manifest is written even when a write failed,
+        // so what was recorded stays replayable
+        ingestion.stop_all();
+        let recorded = recorded.and_then(|()| {
+            // Keep what is already queued
+            while let Some(packet) = packets.try_recv() {
+                writer.write(&packet).with_context(|| {
+                    format!("Failed to record packet of '{}'", packet.sensor_id)
+                })?;
+            }
+            Ok(())
+        });
+
+        let frame_counts = writer
+            .frame_counts()
+            .map(|(sensor_id, count)| (sensor_id.to_string(), count))
+            .collect();
+        let finished = writer.finish().context("Failed to finish recording");
+        recorded?;
+        let manifest = finished?;
+
+        Ok(RecordingStats {
+            frame_counts,
+            duration_sec: manifest.duration_sec,
+            elapsed: start_time.elapsed(),
+        })
+    }
+}
+
+#[cfg(all(test, not(feature = "real-carla")))]
+mod tests {
+    use super::*;
+    use actor_factory::RecordingReader;
+    use config_loader::{ConfigFormat, ConfigLoader};
+    use contracts::SensorType;
+
+    const CONFIG: &str = r#"
+[world]
+map = "Town01"
+
+[[vehicles]]
+id = "ego"
+blueprint = "vehicle.tesla.model3"
+[vehicles.spawn_point.location]
+x = 0.0
+y = 0.0
+z = 0.0
+[vehicles.spawn_point.rotation]
+pitch = 0.0
+yaw = 0.0
+roll = 0.0
+
+[[vehicles.sensors]]
+id = "front_camera"
+sensor_type = "camera"
+frequency_hz = 20.0
+[vehicles.sensors.transform.location]
+x = 2.0
+y = 0.0
+z = 1.5
+[vehicles.sensors.transform.rotation]
+pitch = 0.0
+yaw = 0.0
+roll = 0.0
+[vehicles.sensors.mock_faults]
+drop_prob = 1.0
+
+[sync]
+primary_sensor_id = "front_camera"
+
+[[sinks]]
+name = "log_sink"
+sink_type = "log"
+"#;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_mock_recording_replays_clean_stream() {
+        let output = tempfile::tempdir().unwrap();
+        let recorder = Recorder::new(RecorderConfig {
+            blueprint: ConfigLoader::load_from_str(CONFIG, ConfigFormat::Toml).unwrap(),
+            output: output.path().to_path_buf(),
+            duration: Some(Duration::from_millis(500)),
+            buffer_size: 64,
+        });
+        let stats = recorder.run().await.unwrap();
+
+        // Every packet would be dropped if mock faults were recorded
+        assert_eq!(stats.frame_counts.len(), 1);
+        let (sensor_id, count) = &stats.frame_counts[0];
+        assert_eq!(sensor_id, "front_camera");
+        assert!(*count > 0);
+
+        let reader = RecordingReader::open(output.path()).unwrap();
+        let track = reader.track("front_camera", SensorType::Camera);
+        assert_eq!(track.len() as u64, *count);
+        let replayed: Vec<_> = (0..track.len())
+            .map(|position| track.packet(position).expect("recorded packet"))
+            .collect();
+        assert!(replayed
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+        assert!(replayed
+            .iter()
+            .all(|packet| packet.sensor_type == SensorType::Camera));
+    }
+}
@@ -3,6 +3,7 @@
 use std::time::Duration;
 
 use observability::SyncMetricsAggregator;
+use sync_engine::EvaluationReport;
 
 /// Statistics from a pipeline run
 #[derive(Debug, Clone, Default)]
@@ -27,6 +28,9 @@ pub struct PipelineStats {
 
     /// Sync engine metrics aggregator
     pub sync_metrics: SyncMetricsAggregator,
+
+    /// Accuracy against simulated ground-truth clocks (mock mode only)
+    pub sync_evaluation: Option<EvaluationReport>,
 }
 
 impl PipelineStats {
@@ -83,6 +87,34 @@ impl PipelineStats {
             }
         }
 
+        if let Some(evaluation) = &self.sync_evaluation {
+            println!("\n🎯 Sync Accuracy (ground truth)");
+            println!(
+                "   ├─ Complete frames: {}/{} (slot completeness {:.2}%)",
+                evaluation.complete_frames,
+                evaluation.frames,
+                evaluation.completeness * 100.0
+            );
+
+            let mut sensors: Vec<_> = evaluation.sensors.iter().collect();
+            sensors.sort_by(|a, b| a.0.cmp(b.0));
+            for (sensor, result) in sensors {
+                let convergence = result
+                    .convergence_time_s
+                    .map(|t| format!("converged after {:.2}s", t))
+                    .unwrap_or_else(|| "not converged".to_string());
+                println!(
+                    "   ├─ {}: mean {:.2}ms, max {:.2}ms, rms {:.2}ms, {}",
+                    sensor,
+                    result.mean_abs_error_s * 1000.0,
+                    result.max_abs_error_s * 1000.0,
+                    result.rms_error_s * 1000.0,
+                    convergence
+                );
+            }
+            println!("   └─ Unmatched packets: {}", evaluation.unmatched_packets);
+        }
+
         println!();
     }
 }
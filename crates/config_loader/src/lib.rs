@@ -59,6 +59,11 @@ impl ConfigLoader {
         Self::parse_and_validate(content, format)
     }
 
+    /// Validate a blueprint built in code
+    pub fn validate(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+        validator::validate(blueprint)
+    }
+
     /// Serialize WorldBlueprint to TOML string
     pub fn to_toml(blueprint: &WorldBlueprint) -> Result<String, ContractError> {
         toml::to_string_pretty(blueprint)
@@ -167,6 +172,42 @@ sink_type = "log"
         assert_eq!(bp.world.map, bp2.world.map);
     }
 
+    #[test]
+    fn test_sync_quality_section() {
+        let content = format!(
+            r#"{}
+[sync.engine.quality]
+target_accept_rate = 0.9
+
+[sync.engine.quality.by_type.camera]
+jitter_budget_s = 0.3
+
+[sync.engine.quality.by_sensor.front_camera]
+threshold = 0.01
+"#,
+            MINIMAL_TOML
+        );
+        let bp = ConfigLoader::load_from_str(&content, ConfigFormat::Toml).unwrap();
+        let config = bp.to_sync_engine_config();
+        assert_eq!(config.quality.target_accept_rate, 0.9);
+        assert_eq!(
+            config
+                .quality
+                .jitter_budget("front_camera", contracts::SensorType::Camera),
+            0.3
+        );
+        assert_eq!(
+            config
+                .quality
+                .threshold("front_camera", contracts::SensorType::Camera),
+            0.01
+        );
+
+        let json = ConfigLoader::to_json(&bp).unwrap();
+        let bp2 = ConfigLoader::load_from_str(&json, ConfigFormat::Json).unwrap();
+        assert!(bp2.sync.engine.quality.is_some());
+    }
+
     #[test]
     fn test_validation_runs_after_parse() {
         // Duplicate sensor id should fail validation
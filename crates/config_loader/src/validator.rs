@@ -9,10 +9,21 @@
 //! - frequency_hz > 0 (handled by validator derive)
 //! - min_window_sec <= max_window_sec (handled by validator schema)
 //! - sink required fields must be present (handled by validator derive)
+//! - sync.engine.quality values must be in range and reference known sensors
+//! - sync.engine.output_clock rate must be positive
+//! - sync.engine.deadline_clock rate must be positive
+//! - sync.groups must reference existing vehicles and their own sensors
+//! - sync.engine.deskew must reference LiDAR sensors and a GNSS sensor
+//! - preprocess stages must have valid parameters and match the sensor type
+//! - external sensors must not share an endpoint
+//! - memory budgets must be positive; sync buffers cannot block
 
 use std::collections::HashSet;
 
-use contracts::{ContractError, WorldBlueprint};
+use contracts::{
+    ContractError, DeadlineClock, ExceedPolicy, ImageFormat, PreprocessStage, SensorQualityTuning,
+    SensorSourceKind, SensorType, WorldBlueprint,
+};
 use validator::Validate;
 
 /// Validate WorldBlueprint configuration
@@ -28,6 +39,19 @@ pub fn validate(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
     validate_unique_vehicle_ids(blueprint)?;
     validate_unique_sensor_ids(blueprint)?;
     validate_primary_sensor_exists(blueprint)?;
+    validate_sync_quality(blueprint)?;
+    validate_latency_budget(blueprint)?;
+    validate_output_clock(blueprint)?;
+    validate_liveness(blueprint)?;
+    validate_window_policy(blueprint)?;
+    validate_sync_groups(blueprint)?;
+    validate_deskew(blueprint)?;
+    validate_mock_clocks(blueprint)?;
+    validate_ingestion_queues(blueprint)?;
+    validate_preprocess(blueprint)?;
+    validate_mock_faults(blueprint)?;
+    validate_external_sources(blueprint)?;
+    validate_memory_budgets(blueprint)?;
 
     Ok(())
 }
@@ -85,13 +109,516 @@ fn validate_primary_sensor_exists(blueprint: &WorldBlueprint) -> Result<(), Cont
     Ok(())
 }
 
+/// Validate sync.engine.quality tuning
+fn validate_sync_quality(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let Some(quality) = &blueprint.sync.engine.quality else {
+        return Ok(());
+    };
+
+    if !(quality.target_accept_rate > 0.0 && quality.target_accept_rate <= 1.0) {
+        return Err(ContractError::config_validation(
+            "sync.engine.quality.target_accept_rate",
+            "must be in (0, 1]",
+        ));
+    }
+
+    for (sensor_type, tuning) in &quality.by_type {
+        let field = format!("sync.engine.quality.by_type.{:?}", sensor_type).to_lowercase();
+        validate_quality_tuning(&field, tuning)?;
+    }
+
+    let all_sensor_ids: HashSet<_> = blueprint
+        .vehicles
+        .iter()
+        .flat_map(|v| v.sensors.iter().map(|s| s.id.as_str()))
+        .collect();
+
+    for (sensor_id, tuning) in &quality.by_sensor {
+        let field = format!("sync.engine.quality.by_sensor.{}", sensor_id);
+        if !all_sensor_ids.contains(&**sensor_id) {
+            return Err(ContractError::config_validation(
+                field,
+                format!("sensor '{}' not found in any vehicle sensors", sensor_id),
+            ));
+        }
+        validate_quality_tuning(&field, tuning)?;
+    }
+
+    Ok(())
+}
+
+/// Validate sync.engine.latency_budget_s
+fn validate_latency_budget(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    if blueprint
+        .sync
+        .engine
+        .latency_budget_s
+        .is_some_and(|budget| !(budget > 0.0 && budget.is_finite()))
+    {
+        return Err(ContractError::config_validation(
+            "sync.engine.latency_budget_s",
+            "must be a positive duration",
+        ));
+    }
+
+    if let Some(DeadlineClock::WallClock { rate }) = blueprint.sync.engine.deadline_clock {
+        if !(rate > 0.0 && rate.is_finite()) {
+            return Err(ContractError::config_validation(
+                "sync.engine.deadline_clock.rate",
+                "must be a positive rate",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate sync.engine.output_clock
+fn validate_output_clock(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let rate = blueprint
+        .sync
+        .engine
+        .output_clock
+        .and_then(|clock| clock.rate_hz());
+
+    if rate.is_some_and(|rate_hz| rate_hz <= 0.0 || !rate_hz.is_finite()) {
+        return Err(ContractError::config_validation(
+            "sync.engine.output_clock.rate_hz",
+            "must be a positive frequency",
+        ));
+    }
+    // The grid period is kept in whole nanoseconds
+    if rate.is_some_and(|rate_hz| rate_hz > 1e9) {
+        return Err(ContractError::config_validation(
+            "sync.engine.output_clock.rate_hz",
+            "must not exceed 1e9 Hz",
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_window_policy(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let Some(window) = &blueprint.sync.engine.window else {
+        return Ok(());
+    };
+
+    if !(window.target_percentile > 0.0 && window.target_percentile <= 1.0) {
+        return Err(ContractError::config_validation(
+            "sync.engine.window.target_percentile",
+            "must be in (0, 1]",
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_liveness(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    if blueprint
+        .sync
+        .engine
+        .liveness
+        .is_some_and(|liveness| liveness.missed_periods == 0)
+    {
+        return Err(ContractError::config_validation(
+            "sync.engine.liveness.missed_periods",
+            "must be at least 1",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate sync.groups and sync.cross_vehicle
+fn validate_sync_groups(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let mut seen = HashSet::with_capacity(blueprint.sync.groups.len());
+
+    for group in &blueprint.sync.groups {
+        let field = format!("sync.groups[vehicle_id={}]", group.vehicle_id);
+        if !seen.insert(group.vehicle_id.as_str()) {
+            return Err(ContractError::config_validation(
+                field,
+                "duplicate sync group for vehicle",
+            ));
+        }
+
+        let Some(vehicle) = blueprint
+            .vehicles
+            .iter()
+            .find(|vehicle| vehicle.id == group.vehicle_id)
+        else {
+            return Err(ContractError::config_validation(
+                field,
+                format!("vehicle '{}' not found", group.vehicle_id),
+            ));
+        };
+
+        let group_sensors = group
+            .reference_sensor_id
+            .iter()
+            .chain(group.required_sensor_ids.iter())
+            .chain(group.imu_sensor_id.iter());
+        for sensor_id in group_sensors {
+            if !vehicle.sensors.iter().any(|sensor| &sensor.id == sensor_id) {
+                return Err(ContractError::config_validation(
+                    field,
+                    format!(
+                        "sensor '{}' is not mounted on vehicle '{}'",
+                        sensor_id, vehicle.id
+                    ),
+                ));
+            }
+        }
+    }
+
+    if blueprint.sync.cross_vehicle.is_some() && blueprint.sync.groups.len() < 2 {
+        return Err(ContractError::config_validation(
+            "sync.cross_vehicle",
+            "requires at least two sync.groups",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate sync.engine.deskew
+fn validate_deskew(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let Some(deskew) = &blueprint.sync.engine.deskew else {
+        return Ok(());
+    };
+
+    let find_sensor = |sensor_id: &str| {
+        blueprint
+            .vehicles
+            .iter()
+            .flat_map(|v| v.sensors.iter())
+            .find(|sensor| sensor.id == sensor_id)
+    };
+
+    for sensor_id in &deskew.sensors {
+        match find_sensor(sensor_id) {
+            Some(sensor) if sensor.sensor_type == SensorType::Lidar => {}
+            Some(_) => {
+                return Err(ContractError::config_validation(
+                    "sync.engine.deskew.sensors",
+                    format!("sensor '{}' is not a LiDAR", sensor_id),
+                ))
+            }
+            None => {
+                return Err(ContractError::config_validation(
+                    "sync.engine.deskew.sensors",
+                    format!("sensor '{}' not found in any vehicle sensors", sensor_id),
+                ))
+            }
+        }
+    }
+
+    if let Some(gnss_id) = &deskew.gnss_sensor_id {
+        if !find_sensor(gnss_id).is_some_and(|sensor| sensor.sensor_type == SensorType::Gnss) {
+            return Err(ContractError::config_validation(
+                "sync.engine.deskew.gnss_sensor_id",
+                format!("'{}' is not a GNSS sensor", gnss_id),
+            ));
+        }
+    }
+
+    if deskew
+        .sweep_duration_s
+        .is_some_and(|v| v <= 0.0 || !v.is_finite())
+    {
+        return Err(ContractError::config_validation(
+            "sync.engine.deskew.sweep_duration_s",
+            "must be a positive number of seconds",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate simulated sensor clocks
+fn validate_mock_clocks(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let clocks = blueprint
+        .vehicles
+        .iter()
+        .flat_map(|v| v.sensors.iter())
+        .filter_map(|sensor| sensor.mock_clock.map(|clock| (sensor, clock)));
+
+    for (sensor, clock) in clocks {
+        let field = format!("sensors.{}.mock_clock", sensor.id);
+        if !clock.offset_s.is_finite() || !clock.drift.is_finite() {
+            return Err(ContractError::config_validation(
+                field,
+                "offset_s and drift must be finite",
+            ));
+        }
+        let seconds = [
+            ("jitter_s", clock.jitter_s),
+            ("latency_s", clock.latency_s),
+            ("latency_jitter_s", clock.latency_jitter_s),
+        ];
+        for (name, value) in seconds {
+            if value < 0.0 || !value.is_finite() {
+                return Err(ContractError::config_validation(
+                    format!("{}.{}", field, name),
+                    "must be a non-negative number of seconds",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate injected sensor faults
+fn validate_mock_faults(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let faults = blueprint
+        .vehicles
+        .iter()
+        .flat_map(|v| v.sensors.iter())
+        .filter_map(|sensor| sensor.mock_faults.map(|faults| (sensor, faults)));
+
+    for (sensor, faults) in faults {
+        let field = format!("sensors.{}.mock_faults", sensor.id);
+        let probabilities = [
+            ("drop_prob", faults.drop_prob),
+            ("duplicate_prob", faults.duplicate_prob),
+            ("reorder_prob", faults.reorder_prob),
+            ("burst_prob", faults.burst_prob),
+            ("blackout_prob", faults.blackout_prob),
+        ];
+        for (name, prob) in probabilities {
+            if !(0.0..=1.0).contains(&prob) {
+                return Err(ContractError::config_validation(
+                    format!("{}.{}", field, name),
+                    "must be within [0, 1]",
+                ));
+            }
+        }
+        let durations = [
+            ("latency_s", faults.latency_s),
+            ("jitter_s", faults.jitter_s),
+            ("blackout_s", faults.blackout_s),
+        ];
+        for (name, secs) in durations {
+            if secs < 0.0 || !secs.is_finite() {
+                return Err(ContractError::config_validation(
+                    format!("{}.{}", field, name),
+                    "must be a non-negative number of seconds",
+                ));
+            }
+        }
+        if !faults.skew_s.is_finite() {
+            return Err(ContractError::config_validation(
+                format!("{}.skew_s", field),
+                "must be finite",
+            ));
+        }
+        if faults.burst_prob > 0.0 && faults.burst_len < 2 {
+            return Err(ContractError::config_validation(
+                format!("{}.burst_len", field),
+                "must be at least 2 when burst_prob is set",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that external sensors do not share an endpoint
+fn validate_external_sources(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let mut endpoints = HashSet::new();
+    let sensors = blueprint.vehicles.iter().flat_map(|v| v.sensors.iter());
+
+    for sensor in sensors {
+        if let SensorSourceKind::External { endpoint, .. } = &sensor.source {
+            if !endpoints.insert(endpoint) {
+                return Err(ContractError::config_validation(
+                    format!("sensors.{}.source.endpoint", sensor.id),
+                    format!("endpoint {} is already used by another sensor", endpoint),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate per-stage memory budgets
+fn validate_memory_budgets(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let memory = &blueprint.memory;
+    let stages = [
+        ("ingestion", memory.ingestion),
+        ("sync_buffer", memory.sync_buffer),
+        ("sinks", memory.sinks),
+    ];
+
+    for (stage, budget) in stages {
+        let Some(budget) = budget else { continue };
+        let field = format!("memory.{}", stage);
+        if budget.limit_mb == 0 {
+            return Err(ContractError::config_validation(
+                format!("{}.limit_mb", field),
+                "must be at least 1",
+            ));
+        }
+        if budget.on_exceed != ExceedPolicy::Block {
+            continue;
+        }
+        if stage == "sync_buffer" {
+            return Err(ContractError::config_validation(
+                format!("{}.on_exceed", field),
+                "sync buffers cannot block; they evict the oldest packet",
+            ));
+        }
+        if budget.block_timeout_ms == 0 {
+            return Err(ContractError::config_validation(
+                format!("{}.block_timeout_ms", field),
+                "must be at least 1 when on_exceed = \"block\"",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate per-sensor ingestion queues
+fn validate_ingestion_queues(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let queues = blueprint
+        .vehicles
+        .iter()
+        .flat_map(|v| v.sensors.iter())
+        .filter_map(|sensor| sensor.ingestion.map(|queue| (sensor, queue)));
+
+    for (sensor, queue) in queues {
+        let field = format!("sensors.{}.ingestion", sensor.id);
+        if queue.capacity == Some(0) {
+            return Err(ContractError::config_validation(
+                format!("{}.capacity", field),
+                "must be at least 1",
+            ));
+        }
+        if queue.weight == 0 {
+            return Err(ContractError::config_validation(
+                format!("{}.weight", field),
+                "must be at least 1",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate per-sensor pre-processing stages
+fn validate_preprocess(blueprint: &WorldBlueprint) -> Result<(), ContractError> {
+    let sensors = blueprint.vehicles.iter().flat_map(|v| v.sensors.iter());
+
+    for sensor in sensors {
+        for (i, stage) in sensor.preprocess.iter().enumerate() {
+            let field = format!("sensors.{}.preprocess[{}]", sensor.id, i);
+            let (sensor_type, message) = match *stage {
+                PreprocessStage::Resize { width, height } if width == 0 || height == 0 => {
+                    return Err(ContractError::config_validation(
+                        field,
+                        "resize dimensions must be positive",
+                    ));
+                }
+                PreprocessStage::Crop { width, height, .. } if width == 0 || height == 0 => {
+                    return Err(ContractError::config_validation(
+                        field,
+                        "crop dimensions must be positive",
+                    ));
+                }
+                PreprocessStage::ConvertFormat {
+                    format: ImageFormat::Depth | ImageFormat::SemanticSeg,
+                } => {
+                    return Err(ContractError::config_validation(
+                        field,
+                        "format must be rgb8, rgba8 or bgra8",
+                    ));
+                }
+                PreprocessStage::Resize { .. }
+                | PreprocessStage::Crop { .. }
+                | PreprocessStage::ConvertFormat { .. } => {
+                    (SensorType::Camera, "image stage requires a camera sensor")
+                }
+                PreprocessStage::RangeCrop { min_m, max_m } => {
+                    if !(min_m >= 0.0 && max_m > min_m && max_m.is_finite()) {
+                        return Err(ContractError::config_validation(
+                            field,
+                            "range_crop requires 0 <= min_m < max_m",
+                        ));
+                    }
+                    (
+                        SensorType::Lidar,
+                        "point cloud stage requires a lidar sensor",
+                    )
+                }
+                PreprocessStage::VoxelDownsample { voxel_m } => {
+                    if !(voxel_m > 0.0 && voxel_m.is_finite()) {
+                        return Err(ContractError::config_validation(
+                            field,
+                            "voxel_m must be positive",
+                        ));
+                    }
+                    (
+                        SensorType::Lidar,
+                        "point cloud stage requires a lidar sensor",
+                    )
+                }
+                PreprocessStage::Decimate { rate_hz } => {
+                    if !(rate_hz > 0.0 && rate_hz.is_finite()) {
+                        return Err(ContractError::config_validation(
+                            field,
+                            "rate_hz must be positive",
+                        ));
+                    }
+                    continue;
+                }
+            };
+            if sensor.sensor_type != sensor_type {
+                return Err(ContractError::config_validation(field, message));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_quality_tuning(field: &str, tuning: &SensorQualityTuning) -> Result<(), ContractError> {
+    let unit_range = |value: Option<f64>| value.is_none_or(|v| (0.0..=1.0).contains(&v));
+
+    if !unit_range(tuning.bias) {
+        return Err(ContractError::config_validation(
+            format!("{}.bias", field),
+            "must be in [0, 1]",
+        ));
+    }
+    if !unit_range(tuning.threshold) {
+        return Err(ContractError::config_validation(
+            format!("{}.threshold", field),
+            "must be in [0, 1]",
+        ));
+    }
+    if tuning
+        .jitter_budget_s
+        .is_some_and(|v| v <= 0.0 || !v.is_finite())
+    {
+        return Err(ContractError::config_validation(
+            format!("{}.jitter_budget_s", field),
+            "must be a positive number of seconds",
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use contracts::{
         ConfigVersion, DropPolicy, Location, MissingFramePolicy, Rotation, SensorConfig,
-        SensorType, SinkConfig, SinkType, SyncConfig, SyncEngineOverrides, Transform,
-        VehicleConfig, WorldConfig,
+        SinkConfig, SinkType, SyncConfig, SyncEngineOverrides, Transform, VehicleConfig,
+        WorldConfig,
     };
 
     fn minimal_blueprint() -> WorldBlueprint {
@@ -135,6 +662,11 @@ mod tests {
                     },
                     frequency_hz: 20.0,
                     attributes: Default::default(),
+                    mock_clock: None,
+                    ingestion: None,
+                    preprocess: Vec::new(),
+                    mock_faults: None,
+                    source: Default::default(),
                 }],
             }],
             sync: SyncConfig {
@@ -144,6 +676,8 @@ mod tests {
                 missing_frame_policy: MissingFramePolicy::Drop,
                 drop_policy: DropPolicy::DropOldest,
                 engine: SyncEngineOverrides::default(),
+                groups: Vec::new(),
+                cross_vehicle: None,
             },
             sinks: vec![SinkConfig {
                 name: "log".into(),
@@ -151,6 +685,7 @@ mod tests {
                 queue_capacity: 100,
                 params: Default::default(),
             }],
+            memory: Default::default(),
         }
     }
 
@@ -211,6 +746,277 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_quality_unknown_sensor() {
+        let mut bp = minimal_blueprint();
+        let mut quality = contracts::QualityConfig::default();
+        quality
+            .by_sensor
+            .insert("ghost".into(), SensorQualityTuning::default());
+        bp.sync.engine.quality = Some(quality);
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("ghost"));
+    }
+
+    #[test]
+    fn test_quality_out_of_range() {
+        let mut bp = minimal_blueprint();
+        let mut quality = contracts::QualityConfig::default();
+        quality.by_type.insert(
+            SensorType::Radar,
+            SensorQualityTuning {
+                jitter_budget_s: Some(-1.0),
+                ..Default::default()
+            },
+        );
+        bp.sync.engine.quality = Some(quality.clone());
+        assert!(validate(&bp).is_err());
+
+        quality.by_type.clear();
+        quality.target_accept_rate = 1.5;
+        bp.sync.engine.quality = Some(quality);
+        assert!(validate(&bp).is_err());
+    }
+
+    #[test]
+    fn test_latency_budget_positive() {
+        let mut bp = minimal_blueprint();
+        for budget in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            bp.sync.engine.latency_budget_s = Some(budget);
+            assert!(validate(&bp).is_err(), "budget {budget} should be rejected");
+        }
+
+        bp.sync.engine.latency_budget_s = Some(0.05);
+        assert!(validate(&bp).is_ok());
+
+        bp.sync.engine.deadline_clock = Some(DeadlineClock::WallClock { rate: 0.0 });
+        assert!(validate(&bp).is_err());
+        bp.sync.engine.deadline_clock = Some(DeadlineClock::WallClock { rate: 1.0 });
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_output_clock_rate() {
+        let mut bp = minimal_blueprint();
+        bp.sync.engine.output_clock = Some(contracts::OutputClock::FixedRate { rate_hz: 0.0 });
+        assert!(validate(&bp).is_err());
+
+        bp.sync.engine.output_clock = Some(contracts::OutputClock::FixedRate { rate_hz: 10.0 });
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_window_target_percentile() {
+        let mut bp = minimal_blueprint();
+        bp.sync.engine.window = Some(contracts::WindowConfig {
+            policy: contracts::WindowPolicy::Jitter,
+            target_percentile: 1.5,
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("target_percentile"));
+    }
+
+    #[test]
+    fn test_liveness_missed_periods() {
+        let mut bp = minimal_blueprint();
+        bp.sync.engine.liveness = Some(contracts::LivenessConfig {
+            missed_periods: 0,
+            drop_degraded: true,
+        });
+        assert!(validate(&bp).is_err());
+
+        bp.sync.engine.liveness = Some(Default::default());
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_sync_group_sensor_ownership() {
+        let mut bp = minimal_blueprint();
+        bp.sync.groups = vec![contracts::SyncGroupConfig {
+            vehicle_id: "ego".into(),
+            reference_sensor_id: Some("ghost".into()),
+            required_sensor_ids: Vec::new(),
+            imu_sensor_id: None,
+        }];
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("ghost"));
+
+        bp.sync.groups[0].reference_sensor_id = None;
+        assert!(validate(&bp).is_ok());
+
+        bp.sync.cross_vehicle = Some(contracts::CrossVehicleConfig {
+            tolerance_s: 0.05,
+            forward_vehicle_frames: false,
+        });
+        assert!(validate(&bp).is_err());
+    }
+
+    #[test]
+    fn test_deskew_requires_lidar() {
+        let mut bp = minimal_blueprint();
+        bp.sync.engine.deskew = Some(contracts::DeskewConfig {
+            sensors: vec!["cam1".into()],
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("not a LiDAR"));
+
+        bp.sync.engine.deskew = Some(Default::default());
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_mock_clock_jitter_non_negative() {
+        let mut bp = minimal_blueprint();
+        bp.vehicles[0].sensors[0].mock_clock = Some(contracts::ClockModel {
+            jitter_s: -0.001,
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("jitter_s"));
+
+        bp.vehicles[0].sensors[0].mock_clock = Some(contracts::ClockModel {
+            offset_s: 0.01,
+            drift: 1e-5,
+            jitter_s: 0.001,
+            latency_s: 0.02,
+            latency_jitter_s: 0.005,
+            seed: 3,
+        });
+        assert!(validate(&bp).is_ok());
+
+        bp.vehicles[0].sensors[0].mock_clock = Some(contracts::ClockModel {
+            latency_jitter_s: f64::NAN,
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("latency_jitter_s"));
+    }
+
+    #[test]
+    fn test_ingestion_queue_limits() {
+        let mut bp = minimal_blueprint();
+        bp.vehicles[0].sensors[0].ingestion = Some(contracts::IngestionQueueConfig {
+            capacity: Some(0),
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("capacity"));
+
+        bp.vehicles[0].sensors[0].ingestion = Some(contracts::IngestionQueueConfig {
+            capacity: Some(200),
+            drop_policy: Some(contracts::DropPolicy::DropOldest),
+            weight: 0,
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("weight"));
+
+        bp.vehicles[0].sensors[0].ingestion.as_mut().unwrap().weight = 2;
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_preprocess_stages() {
+        let mut bp = minimal_blueprint();
+        bp.vehicles[0].sensors[0].preprocess = vec![PreprocessStage::Decimate { rate_hz: 5.0 }];
+        assert!(validate(&bp).is_ok());
+
+        bp.vehicles[0].sensors[0].preprocess = vec![PreprocessStage::Decimate { rate_hz: 0.0 }];
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("rate_hz"));
+
+        // Point cloud stage on a camera
+        bp.vehicles[0].sensors[0].preprocess =
+            vec![PreprocessStage::VoxelDownsample { voxel_m: 0.2 }];
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("lidar"));
+
+        bp.vehicles[0].sensors[0].preprocess = vec![PreprocessStage::ConvertFormat {
+            format: ImageFormat::Rgb8,
+        }];
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_mock_fault_ranges() {
+        let mut bp = minimal_blueprint();
+        bp.vehicles[0].sensors[0].mock_faults = Some(contracts::FaultModel {
+            drop_prob: 1.5,
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("drop_prob"));
+
+        bp.vehicles[0].sensors[0].mock_faults = Some(contracts::FaultModel {
+            burst_prob: 0.1,
+            burst_len: 1,
+            ..Default::default()
+        });
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("burst_len"));
+
+        bp.vehicles[0].sensors[0].mock_faults = Some(contracts::FaultModel {
+            latency_s: 0.02,
+            drop_prob: 0.05,
+            burst_prob: 0.1,
+            burst_len: 4,
+            skew_s: -0.01,
+            seed: 9,
+            ..Default::default()
+        });
+        assert!(validate(&bp).is_ok());
+    }
+
+    #[test]
+    fn test_external_endpoints_unique() {
+        let mut bp = minimal_blueprint();
+        let mut second = bp.vehicles[0].sensors[0].clone();
+        second.id = "ext_imu_2".to_string();
+        bp.vehicles[0].sensors.push(second);
+
+        let external = SensorSourceKind::External {
+            endpoint: contracts::ExternalEndpoint::Tcp("127.0.0.1:7000".into()),
+            encoding: Default::default(),
+        };
+        bp.vehicles[0].sensors[0].source = external.clone();
+        assert!(validate(&bp).is_ok());
+
+        bp.vehicles[0].sensors[1].source = external;
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("already used"));
+    }
+
+    #[test]
+    fn test_memory_budgets() {
+        let budget = |limit_mb, on_exceed| {
+            Some(contracts::StageBudget {
+                limit_mb,
+                on_exceed,
+                block_timeout_ms: 100,
+            })
+        };
+        let mut bp = minimal_blueprint();
+        bp.memory.ingestion = budget(256, ExceedPolicy::Block);
+        bp.memory.sinks = budget(512, ExceedPolicy::Drop);
+        assert!(validate(&bp).is_ok());
+
+        bp.memory.sinks = budget(0, ExceedPolicy::Drop);
+        let result = validate(&bp);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("memory.sinks.limit_mb"));
+
+        bp.memory.sinks = None;
+        bp.memory.sync_buffer = budget(256, ExceedPolicy::Block);
+        let result = validate(&bp);
+        assert!(result.unwrap_err().to_string().contains("cannot block"));
+    }
+
     #[test]
     fn test_empty_sink_name() {
         let mut bp = minimal_blueprint();
@@ -4,9 +4,14 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use validator::Validate;
 
-use crate::{AdaKFConfig, BufferConfig, MissingDataStrategy, SyncEngineConfig, WindowConfig};
+use crate::{
+    AdaKFConfig, BufferConfig, ClockModel, DeadlineClock, DeskewConfig, ImageFormat,
+    LivenessConfig, MissingDataStrategy, OutputClock, QualityConfig, SyncEngineConfig,
+    WindowConfig,
+};
 
 /// Configuration version
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -37,6 +42,10 @@ pub struct WorldBlueprint {
     /// Output routing configuration
     #[validate(nested)]
     pub sinks: Vec<SinkConfig>,
+
+    /// Payload byte budgets per pipeline stage
+    #[serde(default)]
+    pub memory: MemoryConfig,
 }
 
 /// World configuration: map, weather, etc.
@@ -60,6 +69,18 @@ pub struct WorldConfig {
     pub carla_port: u16,
 }
 
+impl WorldConfig {
+    /// World on `map` with the default CARLA address
+    pub fn new(map: impl Into<String>) -> Self {
+        Self {
+            map: map.into(),
+            weather: None,
+            carla_host: default_carla_host(),
+            carla_port: default_carla_port(),
+        }
+    }
+}
+
 fn default_carla_host() -> String {
     "localhost".to_string()
 }
@@ -152,6 +173,229 @@ pub struct SensorConfig {
     /// Sensor-specific attributes
     #[serde(default)]
     pub attributes: HashMap<String, String>,
+
+    /// Simulated clock error applied by mock sources
+    #[serde(default)]
+    pub mock_clock: Option<ClockModel>,
+
+    /// Ingestion queue settings (None = pipeline defaults)
+    #[serde(default)]
+    pub ingestion: Option<IngestionQueueConfig>,
+
+    /// Pre-processing stages applied in order during ingestion, before sync
+    #[serde(default)]
+    pub preprocess: Vec<PreprocessStage>,
+
+    /// Faults injected into mock and replay sources
+    #[serde(default)]
+    pub mock_faults: Option<FaultModel>,
+
+    /// Where the sensor's data comes from
+    #[serde(default)]
+    pub source: SensorSourceKind,
+}
+
+/// Origin of a sensor's data stream
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SensorSourceKind {
+    /// Sensor actor spawned in CARLA (or its mock/replay stand-in)
+    #[default]
+    Carla,
+
+    /// Foreign process streaming length-prefixed packets to a local socket;
+    /// no actor is spawned
+    External {
+        /// `tcp://host:port` or `unix:///path/to.sock`
+        endpoint: ExternalEndpoint,
+        /// Packet encoding on the wire
+        #[serde(default)]
+        encoding: PacketEncoding,
+    },
+}
+
+impl SensorSourceKind {
+    /// Whether the sensor is fed by an external process
+    pub fn is_external(&self) -> bool {
+        matches!(self, SensorSourceKind::External { .. })
+    }
+}
+
+/// Local socket an external sensor process connects to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ExternalEndpoint {
+    /// TCP listen address (`host:port`)
+    Tcp(String),
+    /// Unix domain socket path
+    Unix(PathBuf),
+}
+
+impl TryFrom<String> for ExternalEndpoint {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if let Some(addr) = value.strip_prefix("tcp://") {
+            if addr
+                .rsplit_once(':')
+                .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+            {
+                return Ok(ExternalEndpoint::Tcp(addr.to_string()));
+            }
+        } else if let Some(path) = value.strip_prefix("unix://") {
+            if !path.is_empty() {
+                return Ok(ExternalEndpoint::Unix(PathBuf::from(path)));
+            }
+        }
+        Err(format!(
+            "invalid endpoint '{}', expected tcp://host:port or unix:///path",
+            value
+        ))
+    }
+}
+
+impl From<ExternalEndpoint> for String {
+    fn from(endpoint: ExternalEndpoint) -> Self {
+        endpoint.to_string()
+    }
+}
+
+impl std::fmt::Display for ExternalEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalEndpoint::Tcp(addr) => write!(f, "tcp://{}", addr),
+            ExternalEndpoint::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
+}
+
+/// Wire encoding of external `SensorPacket`s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacketEncoding {
+    /// JSON (human-readable, larger)
+    #[default]
+    Json,
+    /// Bincode (binary, compact)
+    Bincode,
+}
+
+/// Faults injected into a sensor stream for robustness testing
+///
+/// Probabilities are per packet; everything defaults to off.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct FaultModel {
+    /// Fixed delivery latency (seconds of wall time)
+    #[serde(default)]
+    pub latency_s: f64,
+
+    /// Extra delivery delay, uniform in `[0, jitter_s]`; may reorder packets
+    #[serde(default)]
+    pub jitter_s: f64,
+
+    /// Probability of dropping a packet
+    #[serde(default)]
+    pub drop_prob: f64,
+
+    /// Probability of delivering a packet twice
+    #[serde(default)]
+    pub duplicate_prob: f64,
+
+    /// Probability of holding a packet back until after the next one
+    #[serde(default)]
+    pub reorder_prob: f64,
+
+    /// Probability of starting a burst
+    #[serde(default)]
+    pub burst_prob: f64,
+
+    /// Packets held and then released together by a burst
+    #[serde(default)]
+    pub burst_len: u32,
+
+    /// Constant offset added to packet timestamps (seconds)
+    #[serde(default)]
+    pub skew_s: f64,
+
+    /// Probability of starting a blackout
+    #[serde(default)]
+    pub blackout_prob: f64,
+
+    /// Blackout length in simulation seconds; all packets are lost meanwhile
+    #[serde(default)]
+    pub blackout_s: f64,
+
+    /// Seed for the fault sequence
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Ingestion pre-processing stage
+///
+/// Image stages apply to camera payloads, point cloud stages to LiDAR;
+/// `decimate` applies to any sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum PreprocessStage {
+    /// Nearest-neighbour resize to `width` x `height` pixels
+    Resize { width: u32, height: u32 },
+
+    /// Crop a pixel region, clamped to the image bounds
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+
+    /// Convert between `rgb8`, `rgba8` and `bgra8` pixel formats
+    ConvertFormat { format: ImageFormat },
+
+    /// Keep points whose distance from the sensor lies in `[min_m, max_m]`
+    RangeCrop {
+        #[serde(default)]
+        min_m: f32,
+        max_m: f32,
+    },
+
+    /// Keep one point per voxel of edge `voxel_m` meters
+    VoxelDownsample { voxel_m: f32 },
+
+    /// Drop packets to at most `rate_hz` (by simulation time)
+    Decimate { rate_hz: f64 },
+}
+
+/// Per-sensor ingestion queue settings
+///
+/// Each sensor has its own bounded queue; queues are merged into the sync
+/// loop by a weighted round-robin scheduler.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct IngestionQueueConfig {
+    /// Queue capacity in packets (None = pipeline default)
+    #[serde(default)]
+    pub capacity: Option<usize>,
+
+    /// Policy when the queue is full (None = `sync.drop_policy`)
+    #[serde(default)]
+    pub drop_policy: Option<DropPolicy>,
+
+    /// Packets taken per scheduling round relative to other sensors
+    #[serde(default = "default_ingestion_weight")]
+    pub weight: u32,
+}
+
+impl Default for IngestionQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity: None,
+            drop_policy: None,
+            weight: default_ingestion_weight(),
+        }
+    }
+}
+
+fn default_ingestion_weight() -> u32 {
+    1
 }
 
 /// Sensor type
@@ -194,6 +438,73 @@ pub struct SyncConfig {
     /// Additional sync engine tuning parameters
     #[serde(default)]
     pub engine: SyncEngineOverrides,
+
+    /// Per-vehicle sync groups (empty = single group around primary_sensor_id)
+    #[serde(default)]
+    #[validate(nested)]
+    pub groups: Vec<SyncGroupConfig>,
+
+    /// Join per-vehicle frames into cross-vehicle frames on sim time
+    #[serde(default)]
+    #[validate(nested)]
+    pub cross_vehicle: Option<CrossVehicleConfig>,
+}
+
+impl SyncConfig {
+    /// Default sync policy around a primary clock sensor
+    pub fn new(primary_sensor_id: impl Into<String>) -> Self {
+        Self {
+            primary_sensor_id: primary_sensor_id.into(),
+            min_window_sec: default_min_window(),
+            max_window_sec: default_max_window(),
+            missing_frame_policy: MissingFramePolicy::default(),
+            drop_policy: DropPolicy::default(),
+            engine: SyncEngineOverrides::default(),
+            groups: Vec::new(),
+            cross_vehicle: None,
+        }
+    }
+}
+
+/// Sync group for a single vehicle
+///
+/// Each group runs its own sync engine; engine tuning is shared from
+/// `sync.engine`.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SyncGroupConfig {
+    /// Vehicle whose sensors form this group
+    #[validate(length(min = 1, message = "vehicle_id cannot be empty"))]
+    pub vehicle_id: String,
+
+    /// Reference clock sensor (defaults to primary_sensor_id when mounted on
+    /// this vehicle, else the vehicle's first camera or first sensor)
+    #[serde(default)]
+    pub reference_sensor_id: Option<String>,
+
+    /// Sensors required in each frame (defaults to all vehicle sensors)
+    #[serde(default)]
+    pub required_sensor_ids: Vec<String>,
+
+    /// IMU used for adaptive windowing (defaults to the vehicle's first IMU)
+    #[serde(default)]
+    pub imu_sensor_id: Option<String>,
+}
+
+/// Cross-vehicle frame join
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CrossVehicleConfig {
+    /// Maximum t_sync spread (seconds) between joined vehicle frames
+    #[serde(default = "default_join_tolerance")]
+    #[validate(range(exclusive_min = 0.0, message = "tolerance_s must be > 0"))]
+    pub tolerance_s: f64,
+
+    /// Also forward per-vehicle frames to sinks
+    #[serde(default)]
+    pub forward_vehicle_frames: bool,
+}
+
+fn default_join_tolerance() -> f64 {
+    0.05
 }
 
 /// Validate sync window (min <= max)
@@ -234,6 +545,34 @@ pub struct SyncEngineOverrides {
     /// Expected interval per sensor (seconds)
     #[serde(default)]
     pub sensor_intervals: HashMap<String, f64>,
+
+    /// Latency budget (seconds) before emitting frames with missing slots
+    #[serde(default)]
+    pub latency_budget_s: Option<f64>,
+
+    /// Sim time estimate between packets for the latency budget
+    #[serde(default)]
+    pub deadline_clock: Option<DeadlineClock>,
+
+    /// Quality scoring and jitter budget tuning
+    #[serde(default)]
+    pub quality: Option<QualityConfig>,
+
+    /// Output frame clock (reference cadence or fixed-rate grid)
+    #[serde(default)]
+    pub output_clock: Option<OutputClock>,
+
+    /// LiDAR motion compensation
+    #[serde(default)]
+    pub deskew: Option<DeskewConfig>,
+
+    /// Sensor liveness tracking and degradation
+    #[serde(default)]
+    pub liveness: Option<LivenessConfig>,
+
+    /// Learned-state checkpoint loaded at startup and saved at shutdown
+    #[serde(default)]
+    pub checkpoint_path: Option<PathBuf>,
 }
 
 fn default_min_window() -> f64 {
@@ -303,6 +642,65 @@ pub enum SinkType {
     Network,
 }
 
+/// Payload byte budgets per pipeline stage (None = unlimited)
+///
+/// Queue and buffer capacities count packets; these limits bound the
+/// payload bytes a stage may hold regardless of packet size.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct MemoryConfig {
+    /// Per-sensor ingestion queues, shared budget; never waits, the sensor
+    /// queue's drop policy makes room instead
+    #[serde(default)]
+    pub ingestion: Option<StageBudget>,
+
+    /// Sync engine buffers, shared budget; always evicts the sensor's oldest packet
+    #[serde(default)]
+    pub sync_buffer: Option<StageBudget>,
+
+    /// Sink queues, shared budget; a frame counts once however many sinks
+    /// queue it
+    #[serde(default)]
+    pub sinks: Option<StageBudget>,
+}
+
+/// Byte budget of one pipeline stage
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StageBudget {
+    /// Payload bytes the stage may hold (MiB)
+    pub limit_mb: u64,
+
+    /// Behaviour when an item does not fit
+    #[serde(default)]
+    pub on_exceed: ExceedPolicy,
+
+    /// Longest wait for space under `block` (milliseconds)
+    #[serde(default = "default_block_timeout_ms")]
+    pub block_timeout_ms: u64,
+}
+
+impl StageBudget {
+    /// Limit in bytes
+    pub fn limit_bytes(&self) -> u64 {
+        self.limit_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+fn default_block_timeout_ms() -> u64 {
+    100
+}
+
+/// What a stage does when its byte budget is exhausted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExceedPolicy {
+    /// Drop the item
+    #[default]
+    Drop,
+    /// Wait up to `block_timeout_ms` for space, then drop. Ingestion never
+    /// waits, so a simulator callback is not stalled; it drops instead
+    Block,
+}
+
 impl WorldBlueprint {
     /// Build a SyncEngineConfig using blueprint data and optional overrides
     pub fn to_sync_engine_config(&self) -> SyncEngineConfig {
@@ -332,9 +730,98 @@ impl WorldBlueprint {
                     .map(|s| SensorId::from(s.id.as_str()))
             });
 
+        self.engine_config_for(
+            SensorId::from(self.sync.primary_sensor_id.as_str()),
+            required_sensors,
+            imu_sensor_id,
+            None,
+        )
+    }
+
+    /// Build one sync engine config per sync group.
+    ///
+    /// Without `sync.groups` this is the single untagged config from
+    /// [`WorldBlueprint::to_sync_engine_config`].
+    pub fn to_sync_group_configs(&self) -> Vec<SyncEngineConfig> {
+        use crate::SensorId;
+
+        if self.sync.groups.is_empty() {
+            return vec![self.to_sync_engine_config()];
+        }
+
+        self.sync
+            .groups
+            .iter()
+            .map(|group| {
+                let vehicle_sensors: &[SensorConfig] = self
+                    .vehicles
+                    .iter()
+                    .find(|vehicle| vehicle.id == group.vehicle_id)
+                    .map(|vehicle| vehicle.sensors.as_slice())
+                    .unwrap_or_default();
+
+                let reference = group
+                    .reference_sensor_id
+                    .clone()
+                    .or_else(|| {
+                        vehicle_sensors
+                            .iter()
+                            .find(|s| s.id == self.sync.primary_sensor_id)
+                            .or_else(|| {
+                                vehicle_sensors
+                                    .iter()
+                                    .find(|s| s.sensor_type == SensorType::Camera)
+                            })
+                            .or_else(|| vehicle_sensors.first())
+                            .map(|s| s.id.clone())
+                    })
+                    .unwrap_or_default();
+
+                let required: Vec<SensorId> = if group.required_sensor_ids.is_empty() {
+                    vehicle_sensors
+                        .iter()
+                        .map(|s| SensorId::from(s.id.as_str()))
+                        .collect()
+                } else {
+                    group
+                        .required_sensor_ids
+                        .iter()
+                        .map(|s| SensorId::from(s.as_str()))
+                        .collect()
+                };
+
+                let imu = group.imu_sensor_id.clone().or_else(|| {
+                    vehicle_sensors
+                        .iter()
+                        .find(|s| s.sensor_type == SensorType::Imu)
+                        .map(|s| s.id.clone())
+                });
+
+                self.engine_config_for(
+                    SensorId::from(reference.as_str()),
+                    required,
+                    imu.map(|id| SensorId::from(id.as_str())),
+                    Some(group.vehicle_id.clone()),
+                )
+            })
+            .collect()
+    }
+
+    fn engine_config_for(
+        &self,
+        reference_sensor_id: crate::SensorId,
+        required_sensors: Vec<crate::SensorId>,
+        imu_sensor_id: Option<crate::SensorId>,
+        vehicle_id: Option<String>,
+    ) -> SyncEngineConfig {
+        use crate::SensorId;
+
+        let overrides = &self.sync.engine;
+
         let mut window = overrides.window.clone().unwrap_or(WindowConfig {
             min_ms: self.sync.min_window_sec * 1000.0,
             max_ms: self.sync.max_window_sec * 1000.0,
+            ..Default::default()
         });
         if window.min_ms > window.max_ms {
             std::mem::swap(&mut window.min_ms, &mut window.max_ms);
@@ -357,8 +844,41 @@ impl WorldBlueprint {
             }
         }
 
+        let deskew = overrides.deskew.clone().map(|mut deskew| {
+            // A group only sees its own vehicle's GNSS and mounts
+            let owned: Vec<&SensorConfig> = match &vehicle_id {
+                Some(id) => self
+                    .vehicles
+                    .iter()
+                    .filter(|vehicle| &vehicle.id == id)
+                    .flat_map(|vehicle| vehicle.sensors.iter())
+                    .collect(),
+                None => self.all_sensors().collect(),
+            };
+            deskew.gnss_sensor_id = deskew.gnss_sensor_id.take().and_then(|gnss| {
+                if owned.iter().any(|sensor| sensor.id == gnss.as_str()) {
+                    Some(gnss)
+                } else {
+                    owned
+                        .iter()
+                        .find(|sensor| sensor.sensor_type == SensorType::Gnss)
+                        .map(|sensor| SensorId::from(sensor.id.as_str()))
+                }
+            });
+            deskew.mounts = owned
+                .iter()
+                .map(|sensor| {
+                    (
+                        SensorId::from(sensor.id.as_str()),
+                        sensor.transform.rotation,
+                    )
+                })
+                .collect();
+            deskew
+        });
+
         SyncEngineConfig {
-            reference_sensor_id: SensorId::from(self.sync.primary_sensor_id.as_str()),
+            reference_sensor_id,
             required_sensors,
             imu_sensor_id,
             window,
@@ -366,6 +886,13 @@ impl WorldBlueprint {
             adakf,
             missing_strategy: MissingDataStrategy::from(self.sync.missing_frame_policy),
             sensor_intervals,
+            latency_budget_s: overrides.latency_budget_s,
+            deadline_clock: overrides.deadline_clock.unwrap_or_default(),
+            quality: overrides.quality.clone().unwrap_or_default(),
+            output_clock: overrides.output_clock.unwrap_or_default(),
+            vehicle_id,
+            deskew,
+            liveness: overrides.liveness,
         }
     }
 
@@ -419,6 +946,11 @@ mod tests {
             },
             frequency_hz,
             attributes: HashMap::new(),
+            mock_clock: None,
+            ingestion: None,
+            preprocess: Vec::new(),
+            mock_faults: None,
+            source: Default::default(),
         }
     }
 
@@ -448,8 +980,11 @@ mod tests {
                 missing_frame_policy: MissingFramePolicy::Drop,
                 drop_policy: DropPolicy::DropOldest,
                 engine: SyncEngineOverrides::default(),
+                groups: Vec::new(),
+                cross_vehicle: None,
             },
             sinks: vec![],
+            memory: MemoryConfig::default(),
         }
     }
 
@@ -473,6 +1008,7 @@ mod tests {
         blueprint.sync.engine.window = Some(WindowConfig {
             min_ms: 10.0,
             max_ms: 80.0,
+            ..Default::default()
         });
         blueprint.sync.engine.buffer = Some(BufferConfig {
             max_size: 256,
@@ -496,4 +1032,115 @@ mod tests {
         assert_eq!(config.required_sensors.len(), 2);
         assert_eq!(config.sensor_intervals.get("lidar_top").copied(), Some(0.1));
     }
+
+    #[test]
+    fn sync_group_configs_per_vehicle() {
+        let mut blueprint = sample_blueprint();
+        assert_eq!(blueprint.to_sync_group_configs()[0].vehicle_id, None);
+
+        blueprint.vehicles.push(VehicleConfig {
+            id: "peer".into(),
+            blueprint: "vehicle.test".into(),
+            spawn_point: None,
+            sensors: vec![
+                sample_sensor("peer_lidar", SensorType::Lidar, 10.0),
+                sample_sensor("peer_cam", SensorType::Camera, 20.0),
+            ],
+        });
+        blueprint.sync.groups = vec![
+            SyncGroupConfig {
+                vehicle_id: "ego".into(),
+                reference_sensor_id: None,
+                required_sensor_ids: Vec::new(),
+                imu_sensor_id: None,
+            },
+            SyncGroupConfig {
+                vehicle_id: "peer".into(),
+                reference_sensor_id: None,
+                required_sensor_ids: Vec::new(),
+                imu_sensor_id: None,
+            },
+        ];
+
+        let configs = blueprint.to_sync_group_configs();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].vehicle_id.as_deref(), Some("ego"));
+        assert_eq!(configs[0].reference_sensor_id, "cam_main");
+        assert_eq!(configs[0].imu_sensor_id.as_deref(), Some("imu_sensor"));
+        assert_eq!(configs[1].reference_sensor_id, "peer_cam");
+        assert_eq!(configs[1].required_sensors.len(), 2);
+        assert_eq!(configs[1].imu_sensor_id, None);
+    }
+
+    #[test]
+    fn sync_group_deskew_uses_own_vehicle_sensors() {
+        let mut blueprint = sample_blueprint();
+        blueprint.vehicles[0]
+            .sensors
+            .push(sample_sensor("gnss", SensorType::Gnss, 10.0));
+        blueprint.vehicles.push(VehicleConfig {
+            id: "peer".into(),
+            blueprint: "vehicle.test".into(),
+            spawn_point: None,
+            sensors: vec![
+                sample_sensor("peer_lidar", SensorType::Lidar, 10.0),
+                sample_sensor("peer_gnss", SensorType::Gnss, 10.0),
+                sample_sensor("peer_imu", SensorType::Imu, 100.0),
+            ],
+        });
+        blueprint.sync.engine.deskew = Some(DeskewConfig {
+            gnss_sensor_id: Some("gnss".into()),
+            ..Default::default()
+        });
+        blueprint.sync.groups = ["ego", "peer"]
+            .into_iter()
+            .map(|vehicle_id| SyncGroupConfig {
+                vehicle_id: vehicle_id.into(),
+                reference_sensor_id: None,
+                required_sensor_ids: Vec::new(),
+                imu_sensor_id: None,
+            })
+            .collect();
+
+        let configs = blueprint.to_sync_group_configs();
+        let ego = configs[0].deskew.as_ref().unwrap();
+        assert_eq!(ego.gnss_sensor_id.as_deref(), Some("gnss"));
+        assert_eq!(ego.mounts.len(), 4);
+        assert!(!ego.mounts.contains_key("peer_lidar"));
+
+        let peer = configs[1].deskew.as_ref().unwrap();
+        assert_eq!(configs[1].imu_sensor_id.as_deref(), Some("peer_imu"));
+        assert_eq!(peer.gnss_sensor_id.as_deref(), Some("peer_gnss"));
+        assert_eq!(peer.mounts.len(), 3);
+        assert!(!peer.mounts.contains_key("lidar_top"));
+
+        // The single untagged config keeps the configured GNSS sensor
+        blueprint.sync.groups.clear();
+        let config = blueprint.to_sync_engine_config();
+        let deskew = config.deskew.unwrap();
+        assert_eq!(deskew.gnss_sensor_id.as_deref(), Some("gnss"));
+        assert_eq!(deskew.mounts.len(), 7);
+    }
+
+    #[test]
+    fn external_source_endpoint_parsing() {
+        let source: SensorSourceKind = serde_json::from_str(
+            r#"{"kind": "external", "endpoint": "tcp://127.0.0.1:7000", "encoding": "bincode"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            source,
+            SensorSourceKind::External {
+                endpoint: ExternalEndpoint::Tcp("127.0.0.1:7000".into()),
+                encoding: PacketEncoding::Bincode,
+            }
+        );
+
+        let unix = ExternalEndpoint::try_from("unix:///tmp/imu.sock".to_string()).unwrap();
+        assert_eq!(unix, ExternalEndpoint::Unix("/tmp/imu.sock".into()));
+        assert_eq!(unix.to_string(), "unix:///tmp/imu.sock");
+        assert!(ExternalEndpoint::try_from("tcp://localhost".to_string()).is_err());
+        assert!(ExternalEndpoint::try_from("udp://0.0.0.0:1".to_string()).is_err());
+        assert!(!SensorSourceKind::default().is_external());
+    }
 }
@@ -0,0 +1,38 @@
+//! CARLA LiDAR and radar conversion
+//!
+//! Shared by the ingestion adapters and the actor factory converter.
+//! Only compiled when `real-carla` feature is enabled.
+
+use carla::sensor::data::{LidarMeasurement, RadarMeasurement};
+
+use crate::{LidarPoint, PointCloudData, RadarData, RadarDetection, SensorPayload};
+
+/// Convert CARLA LidarMeasurement to SensorPayload
+pub fn lidar_to_payload(lidar: &LidarMeasurement) -> SensorPayload {
+    let points: Vec<LidarPoint> = lidar
+        .as_slice()
+        .iter()
+        .map(|p| LidarPoint {
+            x: p.point.x,
+            y: p.point.y,
+            z: p.point.z,
+            intensity: p.intensity,
+        })
+        .collect();
+    SensorPayload::PointCloud(PointCloudData::from_points_vec(points))
+}
+
+/// Convert CARLA RadarMeasurement to SensorPayload
+pub fn radar_to_payload(radar: &RadarMeasurement) -> SensorPayload {
+    let detections: Vec<RadarDetection> = radar
+        .as_slice()
+        .iter()
+        .map(|d| RadarDetection {
+            velocity: d.velocity,
+            azimuth: d.azimuth,
+            altitude: d.altitude,
+            depth: d.depth,
+        })
+        .collect();
+    SensorPayload::Radar(RadarData::from_detections_vec(detections))
+}
@@ -0,0 +1,230 @@
+//! Ground-truth capture times for simulated sensor clocks.
+//!
+//! Mock sources stamp packets with a distorted clock ([`ClockModel`]) and
+//! record the true capture time in a shared [`GroundTruthLog`], so sync
+//! accuracy can be measured without changing the packet format.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SensorId;
+
+/// Capture records kept per sensor before the oldest are discarded
+const MAX_RECORDS_PER_SENSOR: usize = 100_000;
+
+/// Simulated sensor clock error relative to true capture time, and the
+/// delay before a captured packet is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct ClockModel {
+    /// Constant clock offset (seconds)
+    #[serde(default)]
+    pub offset_s: f64,
+
+    /// Linear drift (seconds of error per second of true time)
+    #[serde(default)]
+    pub drift: f64,
+
+    /// Timestamp jitter amplitude, uniform in `±jitter_s` (seconds)
+    #[serde(default)]
+    pub jitter_s: f64,
+
+    /// Mean delay between capture and delivery (seconds)
+    #[serde(default)]
+    pub latency_s: f64,
+
+    /// Delivery delay jitter amplitude, uniform in `±latency_jitter_s`
+    /// (seconds). Packets are delivered in arrival order, which may differ
+    /// from capture order.
+    #[serde(default)]
+    pub latency_jitter_s: f64,
+
+    /// Seed for the jitter sequence
+    #[serde(default)]
+    pub seed: u64,
+}
+
+impl ClockModel {
+    /// Deterministic clock error at a true time (excludes jitter)
+    pub fn offset_at(&self, true_time: f64) -> f64 {
+        self.offset_s + self.drift * true_time
+    }
+
+    /// Reported timestamp for a true capture time.
+    ///
+    /// `jitter_unit` is a sample in `[-1, 1]` scaled by `jitter_s`.
+    pub fn reported_time(&self, true_time: f64, jitter_unit: f64) -> f64 {
+        true_time + self.offset_at(true_time) + self.jitter_s * jitter_unit.clamp(-1.0, 1.0)
+    }
+
+    /// Whether reported timestamps are error-free (delivery delay aside)
+    pub fn is_ideal(&self) -> bool {
+        self.offset_s == 0.0 && self.drift == 0.0 && self.jitter_s == 0.0
+    }
+
+    /// Delay between capture and delivery (seconds, never negative).
+    ///
+    /// `jitter_unit` is a sample in `[-1, 1]` scaled by `latency_jitter_s`.
+    pub fn delivery_delay(&self, jitter_unit: f64) -> f64 {
+        (self.latency_s + self.latency_jitter_s * jitter_unit.clamp(-1.0, 1.0)).max(0.0)
+    }
+
+    /// Whether packets are delivered later than they are captured
+    pub fn delays_delivery(&self) -> bool {
+        self.latency_s > 0.0 || self.latency_jitter_s > 0.0
+    }
+}
+
+/// True and reported capture time of one packet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureRecord {
+    /// True capture time (seconds since the log epoch)
+    pub true_time: f64,
+    /// Timestamp carried by the packet
+    pub reported_time: f64,
+}
+
+#[derive(Debug, Default)]
+struct GroundTruthInner {
+    clocks: HashMap<SensorId, ClockModel>,
+    captures: HashMap<SensorId, BTreeMap<u64, CaptureRecord>>,
+}
+
+/// Shared log of true capture times, keyed by sensor and packet `frame_id`
+#[derive(Debug, Clone)]
+pub struct GroundTruthLog {
+    epoch: Instant,
+    inner: Arc<Mutex<GroundTruthInner>>,
+}
+
+impl Default for GroundTruthLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroundTruthLog {
+    /// Create an empty log whose epoch is now
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            inner: Arc::new(Mutex::new(GroundTruthInner::default())),
+        }
+    }
+
+    /// True time elapsed since the epoch (seconds)
+    pub fn now(&self) -> f64 {
+        self.epoch.elapsed().as_secs_f64()
+    }
+
+    /// Register the clock model used by a sensor
+    pub fn register_clock(&self, sensor_id: impl Into<SensorId>, clock: ClockModel) {
+        self.lock().clocks.insert(sensor_id.into(), clock);
+    }
+
+    /// Clock model of a sensor (ideal if unregistered)
+    pub fn clock(&self, sensor_id: &str) -> ClockModel {
+        self.lock()
+            .clocks
+            .get(sensor_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Record a packet capture
+    pub fn record(&self, sensor_id: impl Into<SensorId>, frame_id: u64, record: CaptureRecord) {
+        let mut inner = self.lock();
+        let captures = inner.captures.entry(sensor_id.into()).or_default();
+        captures.insert(frame_id, record);
+        if captures.len() > MAX_RECORDS_PER_SENSOR {
+            captures.pop_first();
+        }
+    }
+
+    /// Capture record of a packet
+    pub fn lookup(&self, sensor_id: &str, frame_id: u64) -> Option<CaptureRecord> {
+        self.lock()
+            .captures
+            .get(sensor_id)
+            .and_then(|captures| captures.get(&frame_id))
+            .copied()
+    }
+
+    /// Total capture records held
+    pub fn len(&self) -> usize {
+        self.lock().captures.values().map(BTreeMap::len).sum()
+    }
+
+    /// Whether no captures have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, GroundTruthInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_model_applies_offset_drift_and_jitter() {
+        let clock = ClockModel {
+            offset_s: 0.01,
+            drift: 1e-3,
+            jitter_s: 0.002,
+            seed: 0,
+            ..Default::default()
+        };
+        assert!((clock.reported_time(10.0, 0.0) - 10.02).abs() < 1e-12);
+        assert!((clock.reported_time(10.0, 1.0) - 10.022).abs() < 1e-12);
+        assert!((clock.reported_time(10.0, -5.0) - 10.018).abs() < 1e-12);
+        assert!(ClockModel::default().is_ideal());
+        assert!(!clock.delays_delivery());
+    }
+
+    #[test]
+    fn test_clock_model_delivery_delay() {
+        let clock = ClockModel {
+            latency_s: 0.02,
+            latency_jitter_s: 0.03,
+            ..Default::default()
+        };
+        assert!(clock.delays_delivery() && clock.is_ideal());
+        assert!((clock.delivery_delay(0.0) - 0.02).abs() < 1e-12);
+        assert!((clock.delivery_delay(1.0) - 0.05).abs() < 1e-12);
+        // Delivery never precedes capture
+        assert_eq!(clock.delivery_delay(-5.0), 0.0);
+    }
+
+    #[test]
+    fn test_log_shared_between_clones() {
+        let log = GroundTruthLog::new();
+        let writer = log.clone();
+        writer.register_clock(
+            "cam",
+            ClockModel {
+                offset_s: 0.5,
+                ..Default::default()
+            },
+        );
+        writer.record(
+            "cam",
+            7,
+            CaptureRecord {
+                true_time: 1.0,
+                reported_time: 1.5,
+            },
+        );
+
+        assert_eq!(log.clock("cam").offset_s, 0.5);
+        assert_eq!(log.clock("lidar"), ClockModel::default());
+        assert_eq!(log.lookup("cam", 7).unwrap().reported_time, 1.5);
+        assert!(log.lookup("cam", 8).is_none());
+        assert_eq!(log.len(), 1);
+    }
+}
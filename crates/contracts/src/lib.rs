@@ -4,25 +4,37 @@
 //! All business crates can only depend on this crate, reverse dependencies are prohibited.
 //!
 //! ## Time Model
-//! - Uses CARLA simulation timestamp (seconds, f64) as primary clock
+//! - Uses CARLA simulation timestamp ([`SimTime`], integer nanoseconds) as primary clock
 //! - `frame_id` is optional, used for ordering/diagnostics
 
 mod blueprint;
+#[cfg(feature = "real-carla")]
+pub mod carla_convert;
 mod error;
+mod ground_truth;
+mod memory;
+mod point;
 mod runtime;
 mod sensor;
 mod sensor_id;
 mod sensor_source;
+mod sim_time;
 mod sink;
 mod sync;
 mod sync_engine_config;
 
 pub use blueprint::*;
 pub use error::*;
+pub use ground_truth::{CaptureRecord, ClockModel, GroundTruthLog};
+pub use memory::{MemoryAccountant, MemoryStage};
+pub use point::{
+    LidarPoint, PointField, PointFieldType, PointRecord, RadarDetection, SemanticLidarPoint,
+};
 pub use runtime::*;
 pub use sensor::*;
 pub use sensor_id::SensorId;
-pub use sensor_source::{SensorDataCallback, SensorSource};
+pub use sensor_source::{OrderingTolerance, SensorDataCallback, SensorSource};
+pub use sim_time::SimTime;
 pub use sink::*;
 pub use sync::*;
 pub use sync_engine_config::*;
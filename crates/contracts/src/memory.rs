@@ -0,0 +1,246 @@
+//! Payload byte accounting across pipeline stages.
+//!
+//! Queue and buffer capacities count packets, which does not bound memory
+//! when payloads are large images or point clouds. A shared
+//! [`MemoryAccountant`] tracks the payload bytes each stage holds and
+//! enforces the per-stage limits of a [`MemoryConfig`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{ExceedPolicy, MemoryConfig, StageBudget};
+
+/// Pipeline stage holding payload bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryStage {
+    /// Per-sensor ingestion queues
+    Ingestion,
+    /// Sync engine buffers
+    SyncBuffer,
+    /// Sink queues
+    Sinks,
+}
+
+impl MemoryStage {
+    /// All stages, in pipeline order
+    pub const ALL: [MemoryStage; 3] = [
+        MemoryStage::Ingestion,
+        MemoryStage::SyncBuffer,
+        MemoryStage::Sinks,
+    ];
+
+    /// Metric label for this stage
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemoryStage::Ingestion => "ingestion",
+            MemoryStage::SyncBuffer => "sync_buffer",
+            MemoryStage::Sinks => "sinks",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Default)]
+struct StageState {
+    budget: Option<StageBudget>,
+    used: Mutex<u64>,
+    freed: Condvar,
+    peak: AtomicU64,
+}
+
+impl StageState {
+    fn lock(&self) -> std::sync::MutexGuard<'_, u64> {
+        self.used.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether `bytes` more fit; a stage holding nothing admits any item,
+    /// so a single oversized payload cannot stall it forever
+    fn fits(&self, used: u64, bytes: u64) -> bool {
+        match self.budget {
+            None => true,
+            Some(budget) => used == 0 || used.saturating_add(bytes) <= budget.limit_bytes(),
+        }
+    }
+
+    fn add(&self, used: &mut u64, bytes: u64) {
+        *used = used.saturating_add(bytes);
+        self.peak.fetch_max(*used, Ordering::Relaxed);
+    }
+}
+
+/// Shared payload byte counters with per-stage limits.
+///
+/// Cloning is cheap; all clones share the same counters. Every successful
+/// reservation must be matched by a [`release`](Self::release) of the same
+/// size once the stage no longer holds the item.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAccountant {
+    stages: Arc<[StageState; 3]>,
+}
+
+impl MemoryAccountant {
+    /// Create an accountant enforcing the configured budgets
+    pub fn new(config: &MemoryConfig) -> Self {
+        let stage = |budget| StageState {
+            budget,
+            ..Default::default()
+        };
+        Self {
+            stages: Arc::new([
+                stage(config.ingestion),
+                stage(config.sync_buffer),
+                stage(config.sinks),
+            ]),
+        }
+    }
+
+    /// Accountant that tracks usage without limits
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    fn stage(&self, stage: MemoryStage) -> &StageState {
+        &self.stages[stage.index()]
+    }
+
+    /// Byte limit of a stage (None = unlimited)
+    pub fn limit(&self, stage: MemoryStage) -> Option<u64> {
+        self.stage(stage).budget.map(|b| b.limit_bytes())
+    }
+
+    /// How long a stage may wait for space (None = drop immediately)
+    pub fn block_timeout(&self, stage: MemoryStage) -> Option<Duration> {
+        self.stage(stage)
+            .budget
+            .filter(|b| b.on_exceed == ExceedPolicy::Block)
+            .map(|b| Duration::from_millis(b.block_timeout_ms))
+    }
+
+    /// Reserve `bytes` if they fit, without waiting
+    pub fn try_reserve(&self, stage: MemoryStage, bytes: u64) -> bool {
+        let state = self.stage(stage);
+        let mut used = state.lock();
+        if !state.fits(*used, bytes) {
+            return false;
+        }
+        state.add(&mut used, bytes);
+        true
+    }
+
+    /// Reserve `bytes`, waiting for space if the stage blocks on exceed.
+    ///
+    /// Returns `false` if the item should be dropped.
+    pub fn reserve(&self, stage: MemoryStage, bytes: u64) -> bool {
+        let Some(timeout) = self.block_timeout(stage) else {
+            return self.try_reserve(stage, bytes);
+        };
+        let state = self.stage(stage);
+        let deadline = Instant::now() + timeout;
+        let mut used = state.lock();
+        while !state.fits(*used, bytes) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return false;
+            }
+            used = state
+                .freed
+                .wait_timeout(used, left)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        state.add(&mut used, bytes);
+        true
+    }
+
+    /// Reserve `bytes` even if the stage is over budget
+    pub fn force_reserve(&self, stage: MemoryStage, bytes: u64) {
+        let state = self.stage(stage);
+        state.add(&mut state.lock(), bytes);
+    }
+
+    /// Return bytes previously reserved
+    pub fn release(&self, stage: MemoryStage, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        let state = self.stage(stage);
+        let mut used = state.lock();
+        *used = used.saturating_sub(bytes);
+        drop(used);
+        state.freed.notify_all();
+    }
+
+    /// Bytes currently held by a stage
+    pub fn in_use(&self, stage: MemoryStage) -> u64 {
+        *self.stage(stage).lock()
+    }
+
+    /// Highest number of bytes a stage has held
+    pub fn peak(&self, stage: MemoryStage) -> u64 {
+        self.stage(stage).peak.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn accountant(on_exceed: ExceedPolicy) -> MemoryAccountant {
+        MemoryAccountant::new(&MemoryConfig {
+            ingestion: Some(StageBudget {
+                limit_mb: 1,
+                on_exceed,
+                block_timeout_ms: 500,
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_limits_apply_per_stage() {
+        let memory = accountant(ExceedPolicy::Drop);
+        assert!(memory.try_reserve(MemoryStage::Ingestion, MIB / 2));
+        assert!(memory.try_reserve(MemoryStage::Ingestion, MIB / 2));
+        assert!(!memory.reserve(MemoryStage::Ingestion, 1));
+        assert_eq!(memory.in_use(MemoryStage::Ingestion), MIB);
+
+        // Unlimited stage is only counted
+        assert!(memory.try_reserve(MemoryStage::Sinks, 10 * MIB));
+        assert_eq!(memory.limit(MemoryStage::Sinks), None);
+
+        memory.release(MemoryStage::Ingestion, MIB);
+        assert_eq!(memory.in_use(MemoryStage::Ingestion), 0);
+        assert_eq!(memory.peak(MemoryStage::Ingestion), MIB);
+
+        // An empty stage admits an oversized item
+        assert!(memory.try_reserve(MemoryStage::Ingestion, 3 * MIB));
+    }
+
+    #[test]
+    fn test_block_waits_for_release() {
+        let memory = accountant(ExceedPolicy::Block);
+        assert!(memory.try_reserve(MemoryStage::Ingestion, MIB));
+
+        let releaser = memory.clone();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            releaser.release(MemoryStage::Ingestion, MIB);
+        });
+        assert!(memory.reserve(MemoryStage::Ingestion, MIB / 2));
+        handle.join().unwrap();
+        assert_eq!(memory.in_use(MemoryStage::Ingestion), MIB / 2);
+
+        // Times out when nothing is released
+        assert!(memory.try_reserve(MemoryStage::Ingestion, MIB / 2));
+        let start = Instant::now();
+        assert!(!memory.reserve(MemoryStage::Ingestion, MIB / 2));
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,347 @@
+//! Typed views over point cloud and radar payloads.
+//!
+//! Payloads stay opaque `Bytes` on the wire; these POD records give
+//! consumers safe, zero-copy access when the layout matches, and
+//! stride-aware iterators when it does not (e.g. unaligned buffers or
+//! sensors with extra per-point fields).
+
+use bytemuck::{Pod, Zeroable};
+use bytes::Bytes;
+
+use crate::{PointCloudData, RadarData};
+
+/// Scalar type of a point field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointFieldType {
+    /// 32-bit float
+    F32,
+    /// 32-bit unsigned integer
+    U32,
+}
+
+/// One named field inside a point record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointField {
+    /// Field name (PLY/PCD property name)
+    pub name: &'static str,
+    /// Byte offset within the point
+    pub offset: u32,
+    /// Scalar type
+    pub datatype: PointFieldType,
+}
+
+const fn f32_field(name: &'static str, offset: u32) -> PointField {
+    PointField {
+        name,
+        offset,
+        datatype: PointFieldType::F32,
+    }
+}
+
+const fn u32_field(name: &'static str, offset: u32) -> PointField {
+    PointField {
+        name,
+        offset,
+        datatype: PointFieldType::U32,
+    }
+}
+
+/// Point record layout stored in a [`PointCloudData`]
+pub trait PointRecord: Pod {
+    /// Fields in memory order
+    const FIELDS: &'static [PointField];
+
+    /// Record size in bytes
+    const STRIDE: u32 = std::mem::size_of::<Self>() as u32;
+}
+
+/// CARLA LiDAR point (`sensor.lidar.ray_cast`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Pod, Zeroable)]
+pub struct LidarPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub intensity: f32,
+}
+
+impl PointRecord for LidarPoint {
+    const FIELDS: &'static [PointField] = &[
+        f32_field("x", 0),
+        f32_field("y", 4),
+        f32_field("z", 8),
+        f32_field("intensity", 12),
+    ];
+}
+
+/// CARLA semantic LiDAR point (`sensor.lidar.ray_cast_semantic`)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Pod, Zeroable)]
+pub struct SemanticLidarPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Cosine of the incidence angle
+    pub cos_inc_angle: f32,
+    /// Index of the hit actor
+    pub object_idx: u32,
+    /// Semantic tag of the hit object
+    pub object_tag: u32,
+}
+
+impl PointRecord for SemanticLidarPoint {
+    const FIELDS: &'static [PointField] = &[
+        f32_field("x", 0),
+        f32_field("y", 4),
+        f32_field("z", 8),
+        f32_field("cos_inc_angle", 12),
+        u32_field("object_idx", 16),
+        u32_field("object_tag", 20),
+    ];
+}
+
+/// Coordinates shared by every point layout
+const XYZ_FIELDS: &[PointField] = &[f32_field("x", 0), f32_field("y", 4), f32_field("z", 8)];
+
+/// Bytes of the x, y, z prefix every point starts with
+const XYZ_BYTES: usize = 12;
+
+/// Records kept in their own allocation, so byte views stay aligned for them
+struct RecordBuffer<P>(Vec<P>);
+
+impl<P: Pod> AsRef<[u8]> for RecordBuffer<P> {
+    fn as_ref(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.0)
+    }
+}
+
+/// CARLA radar detection
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Pod, Zeroable)]
+pub struct RadarDetection {
+    /// Velocity towards the sensor (m/s)
+    pub velocity: f32,
+    /// Azimuth angle (rad)
+    pub azimuth: f32,
+    /// Altitude angle (rad)
+    pub altitude: f32,
+    /// Distance (m)
+    pub depth: f32,
+}
+
+impl RadarDetection {
+    /// Size of one detection in bytes
+    pub const STRIDE: usize = std::mem::size_of::<RadarDetection>();
+}
+
+impl PointCloudData {
+    /// Build a cloud by copying typed points into a buffer aligned for `P`
+    pub fn from_points<P: PointRecord + Send>(points: &[P]) -> Self {
+        Self::from_points_vec(points.to_vec())
+    }
+
+    /// Build a cloud that owns `points` without copying them; the buffer is
+    /// aligned for `P`, so [`points`](Self::points) views it in place
+    pub fn from_points_vec<P: PointRecord + Send>(points: Vec<P>) -> Self {
+        Self {
+            num_points: points.len() as u32,
+            point_stride: P::STRIDE,
+            data: Bytes::from_owner(RecordBuffer(points)),
+        }
+    }
+
+    /// Zero-copy view as `P` records.
+    ///
+    /// `None` if the stride or length does not match `P`, or the buffer is
+    /// not aligned for it; [`iter_points`](Self::iter_points) works regardless.
+    pub fn points<P: PointRecord>(&self) -> Option<&[P]> {
+        let len = self.num_points as usize * P::STRIDE as usize;
+        if self.point_stride != P::STRIDE || self.data.len() != len {
+            return None;
+        }
+        bytemuck::try_cast_slice(&self.data).ok()
+    }
+
+    /// Iterate points as `P`, reading the leading bytes of each stride
+    pub fn iter_points<P: PointRecord>(&self) -> impl Iterator<Item = P> + '_ {
+        let size = std::mem::size_of::<P>();
+        self.chunks()
+            .filter(move |point| point.len() >= size)
+            .map(move |point| bytemuck::pod_read_unaligned(&point[..size]))
+    }
+
+    /// Iterate point coordinates; empty if the stride cannot hold x, y, z
+    pub fn xyz(&self) -> impl Iterator<Item = [f32; 3]> + '_ {
+        self.chunks()
+            .filter(|point| point.len() >= XYZ_BYTES)
+            .map(|point| bytemuck::pod_read_unaligned(&point[..XYZ_BYTES]))
+    }
+
+    /// Field layout inferred from the stride.
+    ///
+    /// Unknown strides expose only x, y, z; `None` if the stride is too
+    /// small to hold them.
+    pub fn fields(&self) -> Option<&'static [PointField]> {
+        match self.point_stride {
+            LidarPoint::STRIDE => Some(LidarPoint::FIELDS),
+            SemanticLidarPoint::STRIDE => Some(SemanticLidarPoint::FIELDS),
+            stride if stride as usize >= XYZ_BYTES => Some(XYZ_FIELDS),
+            _ => None,
+        }
+    }
+
+    /// Keep points whose coordinates match `keep`, preserving other fields
+    pub fn retain_points(&mut self, mut keep: impl FnMut([f32; 3]) -> bool) {
+        if (self.point_stride as usize) < XYZ_BYTES {
+            return;
+        }
+        let mut data = Vec::with_capacity(self.data.len());
+        for point in self.chunks() {
+            if keep(bytemuck::pod_read_unaligned(&point[..XYZ_BYTES])) {
+                data.extend_from_slice(point);
+            }
+        }
+        self.num_points = (data.len() / self.point_stride as usize) as u32;
+        self.data = Bytes::from(data);
+    }
+
+    /// Rewrite each point's coordinates, preserving other fields
+    pub fn map_xyz(&mut self, mut f: impl FnMut(usize, [f32; 3]) -> [f32; 3]) {
+        let stride = self.point_stride as usize;
+        if stride < XYZ_BYTES {
+            return;
+        }
+        let mut data = self.data.to_vec();
+        let points = data.chunks_exact_mut(stride).take(self.num_points as usize);
+        for (i, point) in points.enumerate() {
+            let xyz = f(i, bytemuck::pod_read_unaligned(&point[..XYZ_BYTES]));
+            point[..XYZ_BYTES].copy_from_slice(bytemuck::bytes_of(&xyz));
+        }
+        self.data = Bytes::from(data);
+    }
+
+    /// Complete points, at most `num_points`
+    fn chunks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let stride = (self.point_stride as usize).max(1);
+        self.data
+            .chunks_exact(stride)
+            .take(self.num_points as usize)
+    }
+}
+
+impl RadarData {
+    /// Build radar data by copying detections into an aligned buffer
+    pub fn from_detections(detections: &[RadarDetection]) -> Self {
+        Self::from_detections_vec(detections.to_vec())
+    }
+
+    /// Build radar data that owns `detections` without copying them
+    pub fn from_detections_vec(detections: Vec<RadarDetection>) -> Self {
+        Self {
+            num_detections: detections.len() as u32,
+            data: Bytes::from_owner(RecordBuffer(detections)),
+        }
+    }
+
+    /// Zero-copy view of the detections; `None` if the length does not
+    /// match or the buffer is unaligned
+    pub fn detections(&self) -> Option<&[RadarDetection]> {
+        if self.data.len() != self.num_detections as usize * RadarDetection::STRIDE {
+            return None;
+        }
+        bytemuck::try_cast_slice(&self.data).ok()
+    }
+
+    /// Iterate detections regardless of alignment
+    pub fn iter_detections(&self) -> impl Iterator<Item = RadarDetection> + '_ {
+        self.data
+            .chunks_exact(RadarDetection::STRIDE)
+            .take(self.num_detections as usize)
+            .map(bytemuck::pod_read_unaligned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lidar(i: f32) -> LidarPoint {
+        LidarPoint {
+            x: i,
+            y: -i,
+            z: 0.5,
+            intensity: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_point_cloud_round_trip() {
+        let points: Vec<_> = (0..4).map(|i| lidar(i as f32)).collect();
+        let cloud = PointCloudData::from_points(&points);
+        assert_eq!((cloud.num_points, cloud.point_stride), (4, 16));
+        assert_eq!(
+            cloud.iter_points::<LidarPoint>().collect::<Vec<_>>(),
+            points
+        );
+        assert_eq!(cloud.xyz().nth(2), Some([2.0, -2.0, 0.5]));
+        assert_eq!(cloud.fields().unwrap()[3].name, "intensity");
+        let view = cloud.points::<LidarPoint>().expect("zero-copy view");
+        assert_eq!(view, &points[..]);
+        assert!(cloud.points::<SemanticLidarPoint>().is_none());
+
+        // Unaligned buffer: no zero-copy view, iteration still works
+        let mut shifted = vec![0u8];
+        shifted.extend_from_slice(&cloud.data);
+        let unaligned = PointCloudData {
+            data: Bytes::from(shifted).slice(1..),
+            ..cloud
+        };
+        assert_eq!(unaligned.iter_points::<LidarPoint>().count(), 4);
+
+        // An owned Vec becomes the buffer itself
+        let address = points.as_ptr();
+        let owned = PointCloudData::from_points_vec(points);
+        assert_eq!(owned.points::<LidarPoint>().unwrap().as_ptr(), address);
+    }
+
+    #[test]
+    fn test_semantic_fields_and_xyz_edits() {
+        let semantic = [SemanticLidarPoint {
+            x: 1.0,
+            object_tag: 7,
+            ..Default::default()
+        }];
+        let mut cloud = PointCloudData::from_points(&semantic);
+        let fields = cloud.fields().unwrap();
+        assert_eq!(fields.last().unwrap().name, "object_tag");
+        assert_eq!(fields.last().unwrap().datatype, PointFieldType::U32);
+
+        cloud.map_xyz(|_, [x, y, z]| [x + 1.0, y, z]);
+        let point = cloud.iter_points::<SemanticLidarPoint>().next().unwrap();
+        assert_eq!((point.x, point.object_tag), (2.0, 7));
+
+        cloud.retain_points(|[x, _, _]| x < 2.0);
+        assert_eq!(cloud.num_points, 0);
+        assert!(cloud.data.is_empty());
+    }
+
+    #[test]
+    fn test_radar_detections() {
+        let detections = [RadarDetection {
+            velocity: -3.0,
+            azimuth: 0.1,
+            altitude: 0.0,
+            depth: 25.0,
+        }; 3];
+        let radar = RadarData::from_detections(&detections);
+        assert_eq!(radar.data.len(), 3 * RadarDetection::STRIDE);
+        assert_eq!(radar.iter_detections().last(), Some(detections[2]));
+        let view = radar.detections().expect("zero-copy view");
+        assert_eq!(view, &detections[..]);
+
+        let owned = detections.to_vec();
+        let address = owned.as_ptr();
+        let radar = RadarData::from_detections_vec(owned);
+        assert_eq!(radar.detections().unwrap().as_ptr(), address);
+    }
+}
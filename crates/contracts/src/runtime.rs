@@ -49,6 +49,11 @@ impl RuntimeGraph {
         self.sensors.insert(sensor_id, actor_id);
     }
 
+    /// Register a sensor fed by an external process (no actor)
+    pub fn register_external_sensor(&mut self, sensor_id: String, vehicle_id: String) {
+        self.sensor_to_vehicle.insert(sensor_id, vehicle_id);
+    }
+
     /// Get all actor handles (for teardown)
     pub fn all_actor_ids(&self) -> Vec<ActorId> {
         self.vehicles
@@ -5,7 +5,7 @@
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
-use crate::{SensorId, SensorType};
+use crate::{SensorId, SensorType, SimTime};
 
 /// Sensor data packet
 ///
@@ -18,8 +18,8 @@ pub struct SensorPacket {
     /// Sensor type
     pub sensor_type: SensorType,
 
-    /// CARLA simulation timestamp (seconds, f64) - primary clock
-    pub timestamp: f64,
+    /// CARLA simulation timestamp - primary clock
+    pub timestamp: SimTime,
 
     /// Optional frame sequence number (used for ordering/diagnostics)
     pub frame_id: Option<u64>,
@@ -28,6 +28,13 @@ pub struct SensorPacket {
     pub payload: SensorPayload,
 }
 
+impl SensorPacket {
+    /// Payload bytes held by this packet (used for memory budgets)
+    pub fn payload_bytes(&self) -> u64 {
+        self.payload.byte_len() as u64
+    }
+}
+
 /// Sensor data payload
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SensorPayload {
@@ -50,6 +57,20 @@ pub enum SensorPayload {
     Raw(Bytes),
 }
 
+impl SensorPayload {
+    /// Size of the payload data in bytes
+    pub fn byte_len(&self) -> usize {
+        match self {
+            SensorPayload::Image(image) => image.data.len(),
+            SensorPayload::PointCloud(cloud) => cloud.data.len(),
+            SensorPayload::Radar(radar) => radar.data.len(),
+            SensorPayload::Raw(data) => data.len(),
+            SensorPayload::Imu(_) => std::mem::size_of::<ImuData>(),
+            SensorPayload::Gnss(_) => std::mem::size_of::<GnssData>(),
+        }
+    }
+}
+
 /// Image data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageData {
@@ -77,6 +98,17 @@ pub enum ImageFormat {
     SemanticSeg,
 }
 
+impl ImageFormat {
+    /// Bytes per pixel (CARLA delivers depth and segmentation as BGRA)
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            ImageFormat::Rgb8 => 3,
+            ImageFormat::Rgba8 | ImageFormat::Bgra8 => 4,
+            ImageFormat::Depth | ImageFormat::SemanticSeg => 4,
+        }
+    }
+}
+
 /// LiDAR point cloud data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PointCloudData {
@@ -13,6 +13,29 @@ use crate::{SensorPacket, SensorType};
 /// Uses `Arc` to allow callback sharing across multiple contexts.
 pub type SensorDataCallback = Arc<dyn Fn(SensorPacket) + Send + Sync>;
 
+/// Stream irregularities a source produces on purpose (e.g. injected
+/// faults), which ingestion validation must let through.
+///
+/// The default is strict: timestamps never go backwards and frame_ids are
+/// not repeated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrderingTolerance {
+    /// Packets may arrive with a timestamp older than an earlier packet
+    pub out_of_order: bool,
+    /// The same frame_id may be delivered more than once
+    pub duplicates: bool,
+}
+
+impl OrderingTolerance {
+    /// Tolerate what either stream may produce
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            out_of_order: self.out_of_order || other.out_of_order,
+            duplicates: self.duplicates || other.duplicates,
+        }
+    }
+}
+
 /// Sensor data source trait
 ///
 /// Abstracts the common behavior of real CARLA sensors and Mock sensors.
@@ -58,4 +81,9 @@ pub trait SensorSource: Send + Sync {
 
     /// Check if currently listening
     fn is_listening(&self) -> bool;
+
+    /// Ordering irregularities this source may deliver (strict by default)
+    fn ordering_tolerance(&self) -> OrderingTolerance {
+        OrderingTolerance::default()
+    }
 }
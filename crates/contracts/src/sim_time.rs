@@ -0,0 +1,224 @@
+//! SimTime - Integer simulation timestamp
+//!
+//! Stores CARLA simulation time as signed integer nanoseconds so ordering,
+//! window bounds and equality are exact over arbitrarily long sessions.
+//! Serializes as f64 seconds, keeping the wire format unchanged.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+const NANOS_PER_SEC: f64 = 1e9;
+
+/// Simulation timestamp or time span in integer nanoseconds.
+///
+/// Arithmetic is exact; conversions to and from f64 seconds round to the
+/// nearest nanosecond.
+///
+/// # Examples
+/// ```
+/// use contracts::SimTime;
+///
+/// let t = SimTime::from_secs_f64(0.1) + SimTime::from_secs_f64(0.2);
+/// assert_eq!(t, SimTime::from_secs_f64(0.3));
+/// assert_eq!(t.as_nanos(), 300_000_000);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimTime(i64);
+
+impl SimTime {
+    /// Simulation start (or an empty span)
+    pub const ZERO: Self = Self(0);
+
+    /// Create from integer nanoseconds
+    #[inline]
+    pub const fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    /// Create from f64 seconds, rounding to the nearest nanosecond.
+    ///
+    /// Meant for spans derived from validated config. Infinite and
+    /// out-of-range input saturates; NaN maps to a zero span. Timestamps
+    /// read from sensors or recordings go through
+    /// [`try_from_secs_f64`](Self::try_from_secs_f64) instead.
+    #[inline]
+    pub fn from_secs_f64(secs: f64) -> Self {
+        Self((secs * NANOS_PER_SEC).round() as i64)
+    }
+
+    /// Create from f64 seconds, or `None` if `secs` is NaN or infinite
+    #[inline]
+    pub fn try_from_secs_f64(secs: f64) -> Option<Self> {
+        secs.is_finite()
+            .then(|| Self((secs * NANOS_PER_SEC).round() as i64))
+    }
+
+    /// Integer nanoseconds
+    #[inline]
+    pub const fn as_nanos(self) -> i64 {
+        self.0
+    }
+
+    /// Seconds as f64
+    #[inline]
+    pub fn as_secs_f64(self) -> f64 {
+        self.0 as f64 / NANOS_PER_SEC
+    }
+
+    /// Absolute value of a span
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+}
+
+impl From<f64> for SimTime {
+    #[inline]
+    fn from(secs: f64) -> Self {
+        Self::from_secs_f64(secs)
+    }
+}
+
+impl From<SimTime> for f64 {
+    #[inline]
+    fn from(time: SimTime) -> Self {
+        time.as_secs_f64()
+    }
+}
+
+impl Add for SimTime {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for SimTime {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl AddAssign for SimTime {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for SimTime {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for SimTime {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+// Display as seconds; honours precision, e.g. `{:.3}`
+impl fmt::Display for SimTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_secs_f64(), f)
+    }
+}
+
+impl fmt::Debug for SimTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SimTime({}ns)", self.0)
+    }
+}
+
+// Serde support - f64 seconds for compatibility with existing outputs
+impl Serialize for SimTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.as_secs_f64())
+    }
+}
+
+impl<'de> Deserialize<'de> for SimTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let secs = f64::deserialize(deserializer)?;
+        Self::try_from_secs_f64(secs)
+            .ok_or_else(|| serde::de::Error::custom(format!("non-finite time {}", secs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulation_is_exact() {
+        let period = SimTime::from_secs_f64(0.05);
+        let mut t = SimTime::ZERO;
+        for _ in 0..72_000 {
+            t += period;
+        }
+        assert_eq!(t, SimTime::from_secs_f64(3600.0));
+
+        let mut f = 0.0_f64;
+        for _ in 0..72_000 {
+            f += 0.05;
+        }
+        assert_ne!(f, 3600.0);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(SimTime::from_secs_f64(1.5).as_nanos(), 1_500_000_000);
+        assert_eq!(SimTime::from_secs_f64(-0.25).as_secs_f64(), -0.25);
+        assert_eq!(SimTime::from_secs_f64(f64::NAN), SimTime::ZERO);
+        assert_eq!(SimTime::from_secs_f64(f64::INFINITY).as_nanos(), i64::MAX);
+        assert_eq!(SimTime::from_secs_f64(-1e300).as_nanos(), i64::MIN);
+        assert_eq!(SimTime::try_from_secs_f64(f64::NAN), None);
+        assert_eq!(SimTime::try_from_secs_f64(f64::NEG_INFINITY), None);
+        assert_eq!(
+            SimTime::try_from_secs_f64(0.5),
+            Some(SimTime::from_nanos(500_000_000))
+        );
+        assert_eq!(
+            (SimTime::from(1.0) - SimTime::from(1.25)).abs(),
+            0.25.into()
+        );
+        assert_eq!(
+            format!("{:.3}", SimTime::from_nanos(1_234_567_890)),
+            "1.235"
+        );
+    }
+
+    #[test]
+    fn test_serde_as_seconds() {
+        let t = SimTime::from_secs_f64(12.345);
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(json, "12.345");
+        let back: SimTime = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, t);
+        let from_int: SimTime = serde_json::from_str("3").unwrap();
+        assert_eq!(from_int, SimTime::from_nanos(3_000_000_000));
+
+        // Binary formats can carry NaN; it must not decode as time zero
+        use serde::de::IntoDeserializer;
+        let nan: serde::de::value::F64Deserializer<serde::de::value::Error> =
+            f64::NAN.into_deserializer();
+        assert!(SimTime::deserialize(nan).is_err());
+    }
+}
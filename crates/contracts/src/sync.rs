@@ -5,19 +5,23 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::{SensorId, SensorPacket, SensorType};
+use crate::{SensorId, SensorPacket, SensorType, SimTime, Vector3};
 
 /// Synchronized frame
 ///
 /// Contains aligned multi-sensor data within the same time window.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncedFrame {
-    /// Sync timestamp (CARLA simulation time, seconds)
-    pub t_sync: f64,
+    /// Sync timestamp (CARLA simulation time)
+    pub t_sync: SimTime,
 
     /// Frame sequence number (monotonically increasing)
     pub frame_id: u64,
 
+    /// Vehicle the frame belongs to (None = ungrouped or cross-vehicle frame)
+    #[serde(default)]
+    pub vehicle_id: Option<String>,
+
     /// Sensor data packets (sensor_id -> packet)
     pub frames: HashMap<SensorId, SensorPacket>,
 
@@ -25,6 +29,13 @@ pub struct SyncedFrame {
     pub sync_meta: SyncMeta,
 }
 
+impl SyncedFrame {
+    /// Payload bytes of all packets in the frame
+    pub fn payload_bytes(&self) -> u64 {
+        self.frames.values().map(SensorPacket::payload_bytes).sum()
+    }
+}
+
 /// Sync metadata
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SyncMeta {
@@ -51,6 +62,33 @@ pub struct SyncMeta {
 
     /// Out-of-order packet count
     pub out_of_order_count: u32,
+
+    /// Motion compensation applied per LiDAR sensor
+    #[serde(default)]
+    pub deskew: HashMap<SensorId, DeskewCorrection>,
+
+    /// Required sensors currently degraded by liveness tracking
+    #[serde(default)]
+    pub degraded_sensors: Vec<SensorId>,
+}
+
+/// Motion compensation applied to a LiDAR sweep
+///
+/// Points are re-expressed in the sensor frame at `t_sync` assuming constant
+/// angular and linear velocity over the sweep.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct DeskewCorrection {
+    /// Sweep duration assumed when assigning per-point times (seconds)
+    pub sweep_duration_s: f64,
+
+    /// Angular velocity used (rad/s, sensor frame)
+    pub angular_velocity: Vector3,
+
+    /// Linear velocity used (m/s, sensor frame)
+    pub linear_velocity: Vector3,
+
+    /// Largest displacement applied to a single point (meters)
+    pub max_displacement_m: f64,
 }
 
 /// Synchronized data packet (single sensor)
@@ -60,7 +98,7 @@ pub struct SyncedPacket {
     pub packet: SensorPacket,
 
     /// Corrected timestamp
-    pub corrected_timestamp: f64,
+    pub corrected_timestamp: SimTime,
 
     /// Whether interpolated
     pub interpolated: bool,
@@ -79,8 +117,8 @@ pub struct BufferStats {
     pub total_packets: usize,
 
     /// Oldest timestamp
-    pub oldest_timestamp: Option<f64>,
+    pub oldest_timestamp: Option<SimTime>,
 
     /// Newest timestamp
-    pub newest_timestamp: Option<f64>,
+    pub newest_timestamp: Option<SimTime>,
 }
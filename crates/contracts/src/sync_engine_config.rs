@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::{MissingFramePolicy, SensorId};
+use crate::{MissingFramePolicy, Rotation, SensorId, SensorType};
 
 /// Sync engine configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +36,141 @@ pub struct SyncEngineConfig {
     /// Expected interval per sensor (seconds)
     #[serde(default)]
     pub sensor_intervals: HashMap<SensorId, f64>,
+
+    /// Maximum age (seconds) of the pending reference packet before a frame
+    /// is emitted with missing slots. `None` disables time-driven emission.
+    #[serde(default)]
+    pub latency_budget_s: Option<f64>,
+
+    /// How sim time advances between packets when checking the budget
+    #[serde(default)]
+    pub deadline_clock: DeadlineClock,
+
+    /// Quality gating and jitter budget tuning
+    #[serde(default)]
+    pub quality: QualityConfig,
+
+    /// Clock that decides when frames are emitted
+    #[serde(default)]
+    pub output_clock: OutputClock,
+
+    /// Vehicle this engine synchronizes (tags emitted frames)
+    #[serde(default)]
+    pub vehicle_id: Option<String>,
+
+    /// LiDAR motion compensation post-stage (None = pass sweeps through raw)
+    #[serde(default)]
+    pub deskew: Option<DeskewConfig>,
+
+    /// Sensor liveness tracking (None = a silent sensor stalls output)
+    #[serde(default)]
+    pub liveness: Option<LivenessConfig>,
+}
+
+/// How sim time is estimated between packets for latency deadlines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum DeadlineClock {
+    /// Newest packet timestamp; deadlines advance only with incoming data.
+    /// Required for synchronous simulation, where a slow tick does not
+    /// advance sim time
+    #[default]
+    Packets,
+    /// Newest packet timestamp plus the wall time since it arrived, for
+    /// asynchronous simulation running at a known rate
+    WallClock {
+        /// Sim seconds per wall second
+        rate: f64,
+    },
+}
+
+/// Sensor liveness configuration
+///
+/// A sensor is degraded once it has been silent for `missed_periods` of its
+/// expected interval, and re-admitted on its next packet. The reference
+/// sensor is never removed from the required set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LivenessConfig {
+    /// Missed periods before a sensor is marked degraded
+    #[serde(default = "default_missed_periods")]
+    pub missed_periods: u32,
+    /// Stop waiting for degraded sensors when assembling frames
+    #[serde(default = "default_drop_degraded")]
+    pub drop_degraded: bool,
+}
+
+fn default_missed_periods() -> u32 {
+    5
+}
+
+fn default_drop_degraded() -> bool {
+    true
+}
+
+impl Default for LivenessConfig {
+    fn default() -> Self {
+        Self {
+            missed_periods: default_missed_periods(),
+            drop_degraded: default_drop_degraded(),
+        }
+    }
+}
+
+/// LiDAR motion compensation (deskew) configuration
+///
+/// Rotation comes from the engine's IMU sensor; translation from the speed
+/// between consecutive GNSS fixes, applied along the vehicle's forward axis.
+/// Both are rotated into the LiDAR frame using the sensor mounts; the
+/// lever-arm velocity of an off-centre LiDAR is ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeskewConfig {
+    /// LiDAR sensors to deskew (empty = every LiDAR sensor)
+    #[serde(default)]
+    pub sensors: Vec<SensorId>,
+    /// Sweep duration in seconds (defaults to the sensor's expected interval)
+    #[serde(default)]
+    pub sweep_duration_s: Option<f64>,
+    /// GNSS sensor used to estimate ego speed (None = rotation only).
+    /// A sync group uses its own vehicle's GNSS sensor when this one is
+    /// mounted on another vehicle
+    #[serde(default)]
+    pub gnss_sensor_id: Option<SensorId>,
+    /// Mounting rotation of each sensor on its vehicle, filled from the
+    /// blueprint (missing = aligned with the vehicle axes)
+    #[serde(skip)]
+    pub mounts: HashMap<SensorId, Rotation>,
+}
+
+/// Output frame clock
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum OutputClock {
+    /// Emit one frame per reference sensor packet
+    #[default]
+    Reference,
+    /// Emit frames on a fixed sim-time grid, filling every slot with the
+    /// nearest (or interpolated) packet relative to the grid tick
+    FixedRate {
+        /// Output rate in Hz
+        rate_hz: f64,
+    },
+}
+
+impl OutputClock {
+    /// Grid rate in Hz when running on a fixed-rate clock
+    pub fn rate_hz(&self) -> Option<f64> {
+        match *self {
+            OutputClock::Reference => None,
+            OutputClock::FixedRate { rate_hz } => Some(rate_hz),
+        }
+    }
+
+    /// Grid period in whole nanoseconds, rounded once from the rate so every
+    /// tick is an exact multiple of it
+    pub fn period_ns(&self) -> Option<i64> {
+        self.rate_hz()
+            .map(|rate_hz| ((1e9 / rate_hz).round() as i64).max(1))
+    }
 }
 
 /// IMU adaptive window configuration
@@ -45,6 +180,16 @@ pub struct WindowConfig {
     pub min_ms: f64,
     /// Maximum window size in milliseconds (low motion)
     pub max_ms: f64,
+    /// How the window is sized between the bounds
+    #[serde(default)]
+    pub policy: WindowPolicy,
+    /// Fraction of timing errors the jitter policy window must cover
+    #[serde(default = "default_target_percentile")]
+    pub target_percentile: f64,
+}
+
+fn default_target_percentile() -> f64 {
+    0.95
 }
 
 impl Default for WindowConfig {
@@ -52,10 +197,25 @@ impl Default for WindowConfig {
         Self {
             min_ms: 20.0,
             max_ms: 100.0,
+            policy: WindowPolicy::default(),
+            target_percentile: default_target_percentile(),
         }
     }
 }
 
+/// Window sizing policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowPolicy {
+    /// IMU motion intensity fused with buffer pressure
+    #[default]
+    Motion,
+    /// Learned inter-arrival jitter and AdaKF residual percentiles
+    Jitter,
+    /// `Motion` when an IMU is configured, `Jitter` otherwise
+    Auto,
+}
+
 /// Buffer configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BufferConfig {
@@ -101,6 +261,100 @@ impl Default for AdaKFConfig {
     }
 }
 
+/// Quality scoring and jitter budget configuration
+///
+/// Values are resolved per sensor ID first, then per sensor type, then fall
+/// back to the built-in defaults of [`SensorQualityTuning::defaults_for`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityConfig {
+    /// Target fraction of required sensors accepted per frame
+    #[serde(default = "default_target_accept_rate")]
+    pub target_accept_rate: f64,
+    /// Tuning per sensor type
+    #[serde(default)]
+    pub by_type: HashMap<SensorType, SensorQualityTuning>,
+    /// Tuning per sensor ID (takes precedence over `by_type`)
+    #[serde(default)]
+    pub by_sensor: HashMap<SensorId, SensorQualityTuning>,
+}
+
+fn default_target_accept_rate() -> f64 {
+    0.95
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            target_accept_rate: default_target_accept_rate(),
+            by_type: HashMap::new(),
+            by_sensor: HashMap::new(),
+        }
+    }
+}
+
+impl QualityConfig {
+    /// Quality score bias for a sensor
+    pub fn bias(&self, sensor_id: &str, sensor_type: SensorType) -> f64 {
+        self.resolve(sensor_id, sensor_type, |t| t.bias)
+    }
+
+    /// Base quality threshold for a sensor (before adaptive scaling)
+    pub fn threshold(&self, sensor_id: &str, sensor_type: SensorType) -> f64 {
+        self.resolve(sensor_id, sensor_type, |t| t.threshold)
+    }
+
+    /// Jitter budget in seconds for a sensor
+    pub fn jitter_budget(&self, sensor_id: &str, sensor_type: SensorType) -> f64 {
+        self.resolve(sensor_id, sensor_type, |t| t.jitter_budget_s)
+    }
+
+    fn resolve(
+        &self,
+        sensor_id: &str,
+        sensor_type: SensorType,
+        field: impl Fn(&SensorQualityTuning) -> Option<f64>,
+    ) -> f64 {
+        self.by_sensor
+            .get(sensor_id)
+            .and_then(&field)
+            .or_else(|| self.by_type.get(&sensor_type).and_then(&field))
+            .or_else(|| field(&SensorQualityTuning::defaults_for(sensor_type)))
+            .unwrap_or_default()
+    }
+}
+
+/// Quality tuning for a single sensor or sensor type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SensorQualityTuning {
+    /// Multiplicative bias applied to the quality score (0..=1)
+    #[serde(default)]
+    pub bias: Option<f64>,
+    /// Base quality threshold below which a packet is rejected (0..=1)
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Maximum gap between emitted packets before warning (seconds)
+    #[serde(default)]
+    pub jitter_budget_s: Option<f64>,
+}
+
+impl SensorQualityTuning {
+    /// Built-in tuning for a sensor type
+    pub fn defaults_for(sensor_type: SensorType) -> Self {
+        let (bias, threshold, jitter_budget_s) = match sensor_type {
+            SensorType::Camera => (1.0, 0.05, 0.265),
+            SensorType::Lidar => (0.9, 0.04, 0.4),
+            SensorType::Imu => (0.8, 0.02, 0.12),
+            SensorType::Gnss => (0.95, 0.03, 0.5),
+            SensorType::Radar => (0.95, 0.03, 0.3),
+        };
+        Self {
+            bias: Some(bias),
+            threshold: Some(threshold),
+            jitter_budget_s: Some(jitter_budget_s),
+        }
+    }
+}
+
 /// Strategy for handling missing sensor data
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -123,3 +377,41 @@ impl From<MissingFramePolicy> for MissingDataStrategy {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_defaults_match_builtin() {
+        let quality = QualityConfig::default();
+        assert_eq!(quality.bias("cam", SensorType::Camera), 1.0);
+        assert_eq!(quality.threshold("lidar", SensorType::Lidar), 0.04);
+        assert_eq!(quality.jitter_budget("radar", SensorType::Radar), 0.3);
+    }
+
+    #[test]
+    fn test_quality_sensor_overrides_type() {
+        let mut quality = QualityConfig::default();
+        quality.by_type.insert(
+            SensorType::Radar,
+            SensorQualityTuning {
+                jitter_budget_s: Some(0.5),
+                ..Default::default()
+            },
+        );
+        quality.by_sensor.insert(
+            "front_radar".into(),
+            SensorQualityTuning {
+                jitter_budget_s: Some(0.2),
+                threshold: Some(0.01),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(quality.jitter_budget("front_radar", SensorType::Radar), 0.2);
+        assert_eq!(quality.jitter_budget("rear_radar", SensorType::Radar), 0.5);
+        assert_eq!(quality.threshold("front_radar", SensorType::Radar), 0.01);
+        assert_eq!(quality.bias("front_radar", SensorType::Radar), 0.95);
+    }
+}
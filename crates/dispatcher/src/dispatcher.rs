@@ -4,10 +4,10 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{debug, info, instrument};
 
-use contracts::{SinkConfig, SinkType, SyncedFrame};
+use contracts::{MemoryAccountant, SinkConfig, SinkType, SyncedFrame};
 
 use crate::error::DispatcherError;
-use crate::handle::SinkHandle;
+use crate::handle::{Charge, SinkHandle};
 use crate::metrics::MetricsSnapshot;
 use crate::sinks::{FileSink, LogSink, NetworkSink};
 
@@ -22,36 +22,60 @@ pub struct DispatcherConfig {
 pub struct DispatcherBuilder {
     config: DispatcherConfig,
     input_rx: mpsc::Receiver<SyncedFrame>,
+    memory: MemoryAccountant,
+    backpressure: bool,
 }
 
 impl DispatcherBuilder {
     /// Create a new DispatcherBuilder
     pub fn new(config: DispatcherConfig, input_rx: mpsc::Receiver<SyncedFrame>) -> Self {
-        Self { config, input_rx }
+        Self {
+            config,
+            input_rx,
+            memory: MemoryAccountant::unlimited(),
+            backpressure: false,
+        }
+    }
+
+    /// Charge queued frame payloads to a shared memory accountant
+    pub fn with_memory(mut self, memory: MemoryAccountant) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Wait for a free sink queue slot instead of dropping the frame.
+    ///
+    /// For offline runs only: a slow sink then stalls the whole dispatcher.
+    pub fn with_backpressure(mut self) -> Self {
+        self.backpressure = true;
+        self
     }
 
     /// Build and start the dispatcher
     #[instrument(name = "dispatcher_builder_build", skip(self))]
     pub async fn build(self) -> Result<Dispatcher, DispatcherError> {
-        let handles = Self::initialize_handles(&self.config).await?;
+        let handles = Self::initialize_handles(&self.config, &self.memory).await?;
 
         Ok(Dispatcher {
             handles,
             input_rx: self.input_rx,
+            memory: self.memory,
+            backpressure: self.backpressure,
         })
     }
 
     #[instrument(
         name = "dispatcher_initialize_handles",
-        skip(config),
+        skip(config, memory),
         fields(sink_count = config.sinks.len())
     )]
     async fn initialize_handles(
         config: &DispatcherConfig,
+        memory: &MemoryAccountant,
     ) -> Result<Vec<SinkHandle>, DispatcherError> {
         let mut handles = Vec::with_capacity(config.sinks.len());
         for sink_config in &config.sinks {
-            handles.push(create_sink_handle(sink_config).await?);
+            handles.push(create_sink_handle(sink_config, memory.clone()).await?);
         }
         Ok(handles)
     }
@@ -60,25 +84,40 @@ impl DispatcherBuilder {
 /// Create a SinkHandle from configuration
 #[instrument(
     name = "dispatcher_create_sink_handle",
-    skip(config),
+    skip(config, memory),
     fields(sink = %config.name, sink_type = ?config.sink_type)
 )]
-async fn create_sink_handle(config: &SinkConfig) -> Result<SinkHandle, DispatcherError> {
+async fn create_sink_handle(
+    config: &SinkConfig,
+    memory: MemoryAccountant,
+) -> Result<SinkHandle, DispatcherError> {
     match config.sink_type {
         SinkType::Log => {
             let sink = LogSink::new(&config.name);
-            Ok(SinkHandle::spawn(sink, config.queue_capacity))
+            Ok(SinkHandle::spawn_with_memory(
+                sink,
+                config.queue_capacity,
+                memory,
+            ))
         }
         SinkType::File => {
             let sink = FileSink::from_params(&config.name, &config.params)
                 .map_err(|e| DispatcherError::sink_creation(&config.name, e.to_string()))?;
-            Ok(SinkHandle::spawn(sink, config.queue_capacity))
+            Ok(SinkHandle::spawn_with_memory(
+                sink,
+                config.queue_capacity,
+                memory,
+            ))
         }
         SinkType::Network => {
             let sink = NetworkSink::from_params(&config.name, &config.params)
                 .await
                 .map_err(|e| DispatcherError::sink_creation(&config.name, e.to_string()))?;
-            Ok(SinkHandle::spawn(sink, config.queue_capacity))
+            Ok(SinkHandle::spawn_with_memory(
+                sink,
+                config.queue_capacity,
+                memory,
+            ))
         }
     }
 }
@@ -87,12 +126,33 @@ async fn create_sink_handle(config: &SinkConfig) -> Result<SinkHandle, Dispatche
 pub struct Dispatcher {
     handles: Vec<SinkHandle>,
     input_rx: mpsc::Receiver<SyncedFrame>,
+    /// Sink-stage payload byte budget shared by all handles
+    memory: MemoryAccountant,
+    /// Wait for full sink queues instead of dropping
+    backpressure: bool,
 }
 
 impl Dispatcher {
     /// Create a dispatcher with custom sink handles (for testing)
     pub fn with_handles(handles: Vec<SinkHandle>, input_rx: mpsc::Receiver<SyncedFrame>) -> Self {
-        Self { handles, input_rx }
+        Self {
+            handles,
+            input_rx,
+            memory: MemoryAccountant::unlimited(),
+            backpressure: false,
+        }
+    }
+
+    /// Charge dispatched frames to the accountant the handles were spawned with
+    pub fn with_memory(mut self, memory: MemoryAccountant) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Wait for a free sink queue slot instead of dropping the frame
+    pub fn with_backpressure(mut self) -> Self {
+        self.backpressure = true;
+        self
     }
 
     /// Get metrics for all sinks
@@ -115,7 +175,7 @@ impl Dispatcher {
 
         while let Some(frame) = self.input_rx.recv().await {
             frame_count += 1;
-            self.dispatch_frame(&frame);
+            self.dispatch_frame(&frame).await;
 
             if frame_count.is_multiple_of(100) {
                 debug!(frames = frame_count, "Dispatcher progress");
@@ -139,9 +199,27 @@ impl Dispatcher {
         })
     }
 
-    fn dispatch_frame(&self, frame: &SyncedFrame) {
+    /// Offer a frame to every sink.
+    ///
+    /// The sinks share the frame's payload buffers, so its bytes are charged
+    /// once; only that reservation may wait. Queuing itself never waits, so a
+    /// slow or full sink cannot delay the others, unless backpressure is
+    /// enabled.
+    async fn dispatch_frame(&self, frame: &SyncedFrame) {
+        let Some(charge) = Charge::reserve(&self.memory, frame.payload_bytes()).await else {
+            for handle in &self.handles {
+                handle.drop_over_budget(frame);
+            }
+            return;
+        };
         for handle in &self.handles {
-            handle.try_send(frame.clone());
+            if self.backpressure {
+                handle
+                    .send_charged_waiting(frame.clone(), charge.clone())
+                    .await;
+            } else {
+                handle.send_charged(frame.clone(), charge.clone());
+            }
         }
     }
 
@@ -167,8 +245,10 @@ pub async fn create_dispatcher(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::SyncMeta;
+    use contracts::{ContractError, DataSink, MemoryStage, SimTime, SyncMeta};
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
 
     #[tokio::test]
     async fn test_dispatcher_fanout() {
@@ -186,8 +266,9 @@ mod tests {
         // Send some frames
         for i in 0..5 {
             let frame = SyncedFrame {
-                t_sync: i as f64,
+                t_sync: SimTime::from_secs_f64(i as f64),
                 frame_id: i,
+                vehicle_id: None,
                 frames: HashMap::new(),
                 sync_meta: SyncMeta::default(),
             };
@@ -217,8 +298,9 @@ mod tests {
 
         // Send a frame
         let frame = SyncedFrame {
-            t_sync: 1.0,
+            t_sync: SimTime::from_secs_f64(1.0),
             frame_id: 1,
+            vehicle_id: None,
             frames: HashMap::new(),
             sync_meta: SyncMeta::default(),
         };
@@ -227,4 +309,109 @@ mod tests {
         drop(input_tx);
         handle.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_shared_payload_charged_once() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            sinks: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Drop,
+                block_timeout_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let handles = vec![
+            SinkHandle::spawn_with_memory(LogSink::new("sink1"), 10, memory.clone()),
+            SinkHandle::spawn_with_memory(LogSink::new("sink2"), 10, memory.clone()),
+        ];
+        let metrics: Vec<_> = handles.iter().map(|h| h.metrics().clone()).collect();
+
+        let (input_tx, input_rx) = mpsc::channel(10);
+        let handle = Dispatcher::with_handles(handles, input_rx)
+            .with_memory(memory.clone())
+            .spawn();
+
+        // Both sinks together fit the 1 MiB budget only if the payload is
+        // charged once
+        let packet = contracts::SensorPacket {
+            sensor_id: "cam".into(),
+            sensor_type: contracts::SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(0.0),
+            frame_id: Some(0),
+            payload: contracts::SensorPayload::Raw(vec![0u8; 600 * 1024].into()),
+        };
+        let frame = SyncedFrame {
+            t_sync: SimTime::from_secs_f64(0.0),
+            frame_id: 0,
+            vehicle_id: None,
+            frames: HashMap::from([("cam".into(), packet)]),
+            sync_meta: SyncMeta::default(),
+        };
+        input_tx.send(frame).await.unwrap();
+        drop(input_tx);
+        handle.await.unwrap();
+
+        for metrics in &metrics {
+            assert_eq!(metrics.dropped_count(), 0);
+            assert_eq!(metrics.write_count(), 1);
+        }
+        assert_eq!(memory.in_use(MemoryStage::Sinks), 0);
+    }
+
+    /// Sink that takes a while to write each frame
+    struct SlowSink {
+        writes: Arc<AtomicU64>,
+    }
+
+    impl DataSink for SlowSink {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        async fn write(&mut self, _frame: &SyncedFrame) -> Result<(), ContractError> {
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            self.writes.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+
+        async fn flush(&mut self) -> Result<(), ContractError> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), ContractError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_delivers_every_frame() {
+        let writes = Arc::new(AtomicU64::new(0));
+        let sink = SlowSink {
+            writes: writes.clone(),
+        };
+        let handles = vec![SinkHandle::spawn(sink, 1)];
+        let metrics = handles[0].metrics().clone();
+
+        let (input_tx, input_rx) = mpsc::channel(50);
+        for i in 0..50 {
+            input_tx
+                .send(SyncedFrame {
+                    t_sync: SimTime::from_secs_f64(i as f64),
+                    frame_id: i,
+                    vehicle_id: None,
+                    frames: HashMap::new(),
+                    sync_meta: SyncMeta::default(),
+                })
+                .await
+                .unwrap();
+        }
+        drop(input_tx);
+        Dispatcher::with_handles(handles, input_rx)
+            .with_backpressure()
+            .run()
+            .await;
+
+        assert_eq!(metrics.dropped_count(), 0);
+        assert_eq!(writes.load(Ordering::Relaxed), 50);
+    }
 }
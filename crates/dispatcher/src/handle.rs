@@ -1,22 +1,85 @@
 //! SinkHandle - manages a sink with isolated queue and worker task
+//!
+//! Queued frame payload bytes are charged to the shared sink memory budget
+//! until the worker has written them. A frame queued on several sinks shares
+//! its payload buffers and is charged once.
 
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tracing::{debug, error, instrument, warn};
 
-use contracts::{DataSink, SyncedFrame};
+use contracts::{DataSink, MemoryAccountant, MemoryStage, SyncedFrame};
 
 use crate::metrics::SinkMetrics;
 
+/// Interval between budget checks while blocked on the sink memory budget
+const BUDGET_POLL: Duration = Duration::from_millis(5);
+
+/// Payload bytes of one frame reserved on the sink stage, released once
+/// every queue holding the frame is done with it
+pub(crate) struct Charge {
+    memory: MemoryAccountant,
+    bytes: u64,
+}
+
+impl Charge {
+    /// Reserve `bytes` if they fit, without waiting
+    pub(crate) fn try_reserve(memory: &MemoryAccountant, bytes: u64) -> Option<Arc<Self>> {
+        memory
+            .try_reserve(MemoryStage::Sinks, bytes)
+            .then(|| Self::new(memory, bytes))
+    }
+
+    /// Reserve `bytes`, waiting for space if the sink stage blocks on exceed
+    pub(crate) async fn reserve(memory: &MemoryAccountant, bytes: u64) -> Option<Arc<Self>> {
+        if let Some(charge) = Self::try_reserve(memory, bytes) {
+            return Some(charge);
+        }
+        let timeout = memory.block_timeout(MemoryStage::Sinks)?;
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            tokio::time::sleep(BUDGET_POLL).await;
+            if let Some(charge) = Self::try_reserve(memory, bytes) {
+                return Some(charge);
+            }
+        }
+        None
+    }
+
+    fn new(memory: &MemoryAccountant, bytes: u64) -> Arc<Self> {
+        Arc::new(Self {
+            memory: memory.clone(),
+            bytes,
+        })
+    }
+}
+
+impl Drop for Charge {
+    fn drop(&mut self) {
+        self.memory.release(MemoryStage::Sinks, self.bytes);
+    }
+}
+
+/// Frame waiting in a sink queue, holding its share of the memory charge.
+/// Frames still queued when the worker exits release it when dropped.
+struct Queued {
+    frame: SyncedFrame,
+    _charge: Arc<Charge>,
+}
+
 /// Handle to a running sink worker
 pub struct SinkHandle {
     /// Sink name
     name: String,
     /// Channel to send frames to worker
-    tx: mpsc::Sender<SyncedFrame>,
+    tx: mpsc::Sender<Queued>,
     /// Shared metrics
     metrics: Arc<SinkMetrics>,
+    /// Sink-stage payload byte budget
+    memory: MemoryAccountant,
     /// Worker task handle
     worker_handle: JoinHandle<()>,
 }
@@ -24,6 +87,15 @@ pub struct SinkHandle {
 impl SinkHandle {
     /// Create a new SinkHandle and spawn the worker task
     pub fn spawn<S: DataSink + Send + 'static>(sink: S, queue_capacity: usize) -> Self {
+        Self::spawn_with_memory(sink, queue_capacity, MemoryAccountant::unlimited())
+    }
+
+    /// Create a SinkHandle whose queued frames count against `memory`
+    pub fn spawn_with_memory<S: DataSink + Send + 'static>(
+        sink: S,
+        queue_capacity: usize,
+        memory: MemoryAccountant,
+    ) -> Self {
         let name = sink.name().to_string();
         let (tx, rx) = mpsc::channel(queue_capacity);
         let metrics = Arc::new(SinkMetrics::new());
@@ -39,6 +111,7 @@ impl SinkHandle {
             name,
             tx,
             metrics,
+            memory,
             worker_handle,
         }
     }
@@ -55,19 +128,59 @@ impl SinkHandle {
 
     /// Send a frame to the sink (non-blocking)
     ///
-    /// Returns true if sent, false if queue full (frame dropped)
+    /// Returns true if sent, false if queue full or over the memory budget
+    /// (frame dropped)
     pub fn try_send(&self, frame: SyncedFrame) -> bool {
-        match self.tx.try_send(frame) {
+        match Charge::try_reserve(&self.memory, frame.payload_bytes()) {
+            Some(charge) => self.send_charged(frame, charge),
+            None => {
+                self.drop_over_budget(&frame);
+                false
+            }
+        }
+    }
+
+    /// Send a frame to the sink, waiting for memory budget if the sink
+    /// stage blocks on exceed
+    ///
+    /// Returns true if sent, false if the frame was dropped
+    pub async fn send(&self, frame: SyncedFrame) -> bool {
+        match Charge::reserve(&self.memory, frame.payload_bytes()).await {
+            Some(charge) => self.send_charged(frame, charge),
+            None => {
+                self.drop_over_budget(&frame);
+                false
+            }
+        }
+    }
+
+    /// Count a frame dropped because the memory budget is exhausted
+    pub(crate) fn drop_over_budget(&self, frame: &SyncedFrame) {
+        self.metrics.inc_dropped_count();
+        warn!(
+            sink = %self.name,
+            frame_id = frame.frame_id,
+            "Sink memory budget exhausted, frame dropped"
+        );
+    }
+
+    /// Queue a frame whose bytes are already reserved, without waiting
+    pub(crate) fn send_charged(&self, frame: SyncedFrame, charge: Arc<Charge>) -> bool {
+        let queued = Queued {
+            frame,
+            _charge: charge,
+        };
+        match self.tx.try_send(queued) {
             Ok(()) => {
                 // Update queue length approximation
                 self.metrics.set_queue_len(self.tx.capacity());
                 true
             }
-            Err(mpsc::error::TrySendError::Full(f)) => {
+            Err(mpsc::error::TrySendError::Full(queued)) => {
                 self.metrics.inc_dropped_count();
                 warn!(
                     sink = %self.name,
-                    frame_id = f.frame_id,
+                    frame_id = queued.frame.frame_id,
                     "Queue full, frame dropped"
                 );
                 false
@@ -79,6 +192,25 @@ impl SinkHandle {
         }
     }
 
+    /// Queue a frame whose bytes are already reserved, waiting for a free
+    /// queue slot instead of dropping
+    pub(crate) async fn send_charged_waiting(
+        &self,
+        frame: SyncedFrame,
+        charge: Arc<Charge>,
+    ) -> bool {
+        let queued = Queued {
+            frame,
+            _charge: charge,
+        };
+        if self.tx.send(queued).await.is_err() {
+            error!(sink = %self.name, "Sink worker closed unexpectedly");
+            return false;
+        }
+        self.metrics.set_queue_len(self.tx.capacity());
+        true
+    }
+
     /// Shutdown the sink worker gracefully
     #[instrument(name = "sink_handle_shutdown", skip(self))]
     pub async fn shutdown(self) {
@@ -100,13 +232,14 @@ impl SinkHandle {
 )]
 async fn sink_worker<S: DataSink>(
     mut sink: S,
-    mut rx: mpsc::Receiver<SyncedFrame>,
+    mut rx: mpsc::Receiver<Queued>,
     metrics: Arc<SinkMetrics>,
     name: String,
 ) {
     debug!(sink = %name, "Sink worker started");
 
-    while let Some(frame) = rx.recv().await {
+    // Each frame's charge is held until it is written
+    while let Some(Queued { frame, _charge }) = rx.recv().await {
         // Update queue length
         metrics.set_queue_len(rx.len());
 
@@ -141,7 +274,7 @@ async fn sink_worker<S: DataSink>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::{ContractError, SyncMeta};
+    use contracts::{ContractError, SimTime, SyncMeta};
     use std::collections::HashMap;
     use std::sync::atomic::{AtomicU64, Ordering};
     use tokio::time::{sleep, Duration};
@@ -193,8 +326,9 @@ mod tests {
 
         for i in 0..5 {
             let frame = SyncedFrame {
-                t_sync: i as f64,
+                t_sync: SimTime::from_secs_f64(i as f64),
                 frame_id: i,
+                vehicle_id: None,
                 frames: HashMap::new(),
                 sync_meta: SyncMeta::default(),
             };
@@ -221,8 +355,9 @@ mod tests {
         // Send more than queue can hold
         for i in 0..10 {
             let frame = SyncedFrame {
-                t_sync: i as f64,
+                t_sync: SimTime::from_secs_f64(i as f64),
                 frame_id: i,
+                vehicle_id: None,
                 frames: HashMap::new(),
                 sync_meta: SyncMeta::default(),
             };
@@ -248,8 +383,9 @@ mod tests {
 
         for i in 0..3 {
             let frame = SyncedFrame {
-                t_sync: i as f64,
+                t_sync: SimTime::from_secs_f64(i as f64),
                 frame_id: i,
+                vehicle_id: None,
                 frames: HashMap::new(),
                 sync_meta: SyncMeta::default(),
             };
@@ -264,4 +400,106 @@ mod tests {
 
         handle.shutdown().await;
     }
+
+    #[tokio::test]
+    async fn test_sink_handle_memory_budget() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            sinks: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Drop,
+                block_timeout_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let write_count = Arc::new(AtomicU64::new(0));
+        let sink = MockSink {
+            name: "budget".to_string(),
+            write_count: Arc::clone(&write_count),
+            should_fail: false,
+            delay_ms: 50,
+        };
+        let handle = SinkHandle::spawn_with_memory(sink, 10, memory.clone());
+
+        let frame = |i: u64| {
+            let packet = contracts::SensorPacket {
+                sensor_id: "cam".into(),
+                sensor_type: contracts::SensorType::Camera,
+                timestamp: SimTime::from_secs_f64(i as f64),
+                frame_id: Some(i),
+                payload: contracts::SensorPayload::Raw(vec![0u8; 600 * 1024].into()),
+            };
+            SyncedFrame {
+                t_sync: SimTime::from_secs_f64(i as f64),
+                frame_id: i,
+                vehicle_id: None,
+                frames: HashMap::from([("cam".into(), packet)]),
+                sync_meta: SyncMeta::default(),
+            }
+        };
+
+        // Queue count allows 10 frames, the byte budget only one
+        assert!(handle.try_send(frame(0)));
+        assert!(!handle.try_send(frame(1)));
+        assert_eq!(handle.metrics().dropped_count(), 1);
+
+        // Written frames return their bytes
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(memory.in_use(MemoryStage::Sinks), 0);
+        assert!(handle.send(frame(2)).await);
+
+        handle.shutdown().await;
+        assert_eq!(write_count.load(Ordering::Relaxed), 2);
+        assert_eq!(memory.in_use(MemoryStage::Sinks), 0);
+    }
+
+    /// Sink whose first write panics, ending its worker
+    struct PanickingSink;
+
+    impl DataSink for PanickingSink {
+        fn name(&self) -> &str {
+            "panicking"
+        }
+
+        async fn write(&mut self, _frame: &SyncedFrame) -> Result<(), ContractError> {
+            sleep(Duration::from_millis(20)).await;
+            panic!("sink crashed");
+        }
+
+        async fn flush(&mut self) -> Result<(), ContractError> {
+            Ok(())
+        }
+
+        async fn close(&mut self) -> Result<(), ContractError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queued_frames_released_when_worker_exits() {
+        let memory = MemoryAccountant::unlimited();
+        let handle = SinkHandle::spawn_with_memory(PanickingSink, 10, memory.clone());
+
+        for i in 0..3 {
+            let packet = contracts::SensorPacket {
+                sensor_id: "cam".into(),
+                sensor_type: contracts::SensorType::Camera,
+                timestamp: SimTime::from_secs_f64(i as f64),
+                frame_id: Some(i),
+                payload: contracts::SensorPayload::Raw(vec![0u8; 1024].into()),
+            };
+            let frame = SyncedFrame {
+                t_sync: SimTime::from_secs_f64(i as f64),
+                frame_id: i,
+                vehicle_id: None,
+                frames: HashMap::from([("cam".into(), packet)]),
+                sync_meta: SyncMeta::default(),
+            };
+            assert!(handle.try_send(frame));
+        }
+        assert_eq!(memory.in_use(MemoryStage::Sinks), 3 * 1024);
+
+        // The worker dies on the first frame with two still queued
+        handle.shutdown().await;
+        assert_eq!(memory.in_use(MemoryStage::Sinks), 0);
+    }
 }
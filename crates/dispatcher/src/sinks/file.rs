@@ -1,7 +1,8 @@
 //! FileSink - writes frames to disk with folder structure
 
 use contracts::{
-    ContractError, DataSink, ImageData, ImageFormat, PointCloudData, SensorPayload, SyncedFrame,
+    ContractError, DataSink, ImageData, ImageFormat, PointCloudData, PointFieldType, SensorPayload,
+    SyncedFrame,
 };
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -60,8 +61,11 @@ impl FileSink {
     fn write_frame_to_disk(&mut self, frame: &SyncedFrame) -> std::io::Result<()> {
         let frame_id = frame.frame_id;
 
-        // 1. Write SyncMeta
-        let meta_dir = self.config.base_path.join("meta");
+        // 1. Write SyncMeta (frame ids are per vehicle, so vehicle frames get their own directory)
+        let mut meta_dir = self.config.base_path.join("meta");
+        if let Some(vehicle_id) = &frame.vehicle_id {
+            meta_dir.push(vehicle_id);
+        }
         if !self.created_dirs.contains(&meta_dir) {
             fs::create_dir_all(&meta_dir)?;
             self.created_dirs.insert(meta_dir.clone());
@@ -165,22 +169,44 @@ impl FileSink {
     }
 
     fn save_point_cloud(&self, path: PathBuf, pc: &PointCloudData) -> std::io::Result<()> {
+        let fields = pc.fields().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("point stride {} too small for x, y, z", pc.point_stride),
+            )
+        })?;
+        // Known layouts are written as-is; otherwise keep only x, y, z
+        let field_bytes: u32 = fields.len() as u32 * 4;
+        let raw = field_bytes == pc.point_stride;
+        let fields = if raw { fields } else { &fields[..3] };
+
         let mut file = File::create(path)?;
         // Write PLY header
         writeln!(file, "ply")?;
         writeln!(file, "format binary_little_endian 1.0")?;
-        writeln!(file, "element vertex {}", pc.num_points)?;
-        writeln!(file, "property float x")?;
-        writeln!(file, "property float y")?;
-        writeln!(file, "property float z")?;
-        // Assuming stride 16 (4 floats), 4th is usually intensity or padding.
-        if pc.point_stride >= 16 {
-            writeln!(file, "property float intensity")?;
+        writeln!(file, "element vertex {}", pc.xyz().count())?;
+        for field in fields {
+            let ty = match field.datatype {
+                PointFieldType::F32 => "float",
+                PointFieldType::U32 => "uint",
+            };
+            writeln!(file, "property {} {}", ty, field.name)?;
         }
         writeln!(file, "end_header")?;
 
         // Write binary data
-        file.write_all(&pc.data)?;
+        if raw {
+            let len = pc.num_points as usize * pc.point_stride as usize;
+            file.write_all(&pc.data[..len.min(pc.data.len())])?;
+        } else {
+            let mut data = Vec::with_capacity(pc.num_points as usize * 12);
+            for point in pc.xyz() {
+                for v in point {
+                    data.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+            file.write_all(&data)?;
+        }
         Ok(())
     }
 
@@ -222,7 +248,7 @@ impl DataSink for FileSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::SyncMeta;
+    use contracts::{SimTime, SyncMeta};
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -234,8 +260,9 @@ mod tests {
 
         let mut sink = FileSink::new("test_file", config).unwrap();
         let frame = SyncedFrame {
-            t_sync: 1.0,
+            t_sync: SimTime::from_secs_f64(1.0),
             frame_id: 1,
+            vehicle_id: None,
             frames: HashMap::new(),
             sync_meta: SyncMeta::default(),
         };
@@ -21,7 +21,7 @@ impl LogSink {
         info!(
             sink = %self.name,
             frame_id = frame.frame_id,
-            t_sync = frame.t_sync,
+            t_sync = %frame.t_sync,
             sensors = sensor_count,
             missing = missing_count,
             dropped = frame.sync_meta.dropped_count,
@@ -61,15 +61,16 @@ impl DataSink for LogSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::SyncMeta;
+    use contracts::{SimTime, SyncMeta};
     use std::collections::HashMap;
 
     #[tokio::test]
     async fn test_log_sink_write() {
         let mut sink = LogSink::new("test_log");
         let frame = SyncedFrame {
-            t_sync: 1.0,
+            t_sync: SimTime::from_secs_f64(1.0),
             frame_id: 1,
+            vehicle_id: None,
             frames: HashMap::new(),
             sync_meta: SyncMeta::default(),
         };
@@ -185,7 +185,7 @@ impl DataSink for NetworkSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use contracts::SyncMeta;
+    use contracts::{SimTime, SyncMeta};
     use std::collections::HashMap;
 
     #[tokio::test]
@@ -222,8 +222,9 @@ mod tests {
 
         let mut sink = NetworkSink::new("test_net", config).await.unwrap();
         let frame = SyncedFrame {
-            t_sync: 1.0,
+            t_sync: SimTime::from_secs_f64(1.0),
             frame_id: 1,
+            vehicle_id: None,
             frames: HashMap::new(),
             sync_meta: SyncMeta::default(),
         };
@@ -2,10 +2,10 @@
 
 use std::sync::Arc;
 
-use async_channel::Sender;
-use contracts::{SensorPacket, SensorType};
+use contracts::SensorType;
 
-use crate::config::IngestionMetrics;
+use crate::config::{BackpressureConfig, IngestionMetrics};
+use crate::queue::QueueProducer;
 
 /// Sensor adapter trait
 ///
@@ -13,7 +13,7 @@ use crate::config::IngestionMetrics;
 /// 1. Registering CARLA sensor callbacks
 /// 2. Parsing sensor data
 /// 3. Wrapping into `SensorPacket`
-/// 4. Sending to channel (handling backpressure)
+/// 4. Sending to its sensor queue (handling backpressure)
 pub trait SensorAdapter: Send + Sync {
     /// Get sensor ID
     fn sensor_id(&self) -> &str;
@@ -21,12 +21,15 @@ pub trait SensorAdapter: Send + Sync {
     /// Get sensor type
     fn sensor_type(&self) -> SensorType;
 
+    /// Backpressure configuration of the sensor's queue
+    fn config(&self) -> &BackpressureConfig;
+
     /// Start sensor data collection
     ///
     /// # Arguments
-    /// * `tx` - Data packet sending channel
+    /// * `queue` - Producer side of the sensor's ingestion queue
     /// * `metrics` - Shared ingestion metrics
-    fn start(&self, tx: Sender<SensorPacket>, metrics: Arc<IngestionMetrics>);
+    fn start(&self, queue: QueueProducer, metrics: Arc<IngestionMetrics>);
 
     /// Stop sensor data collection
     fn stop(&self);
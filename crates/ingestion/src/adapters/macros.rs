@@ -29,8 +29,9 @@ macro_rules! define_sensor_adapter {
         use std::sync::atomic::{AtomicBool, Ordering};
         use std::sync::Arc;
 
-        use contracts::{SensorPacket, SensorType};
-        use async_channel::Sender;
+        #[cfg(feature = "real-carla")]
+        use contracts::SensorPacket;
+        use contracts::SensorType;
         #[cfg(feature = "real-carla")]
         use tracing::{debug, trace, warn};
         #[cfg(not(feature = "real-carla"))]
@@ -45,8 +46,8 @@ macro_rules! define_sensor_adapter {
         #[cfg(feature = "real-carla")]
         use crate::adapters::common::send_packet;
         use crate::config::{BackpressureConfig, IngestionMetrics};
+        use crate::queue::QueueProducer;
 
-        #[allow(dead_code)] // config field used only with real-carla
         pub struct $adapter_name {
             sensor_id: String,
             config: BackpressureConfig,
@@ -85,8 +86,12 @@ macro_rules! define_sensor_adapter {
                 $sensor_type
             }
 
+            fn config(&self) -> &BackpressureConfig {
+                &self.config
+            }
+
             #[cfg(feature = "real-carla")]
-            fn start(&self, tx: Sender<SensorPacket>, metrics: Arc<IngestionMetrics>) {
+            fn start(&self, queue: QueueProducer, metrics: Arc<IngestionMetrics>) {
                 if self.listening.swap(true, Ordering::SeqCst) {
                     warn!(sensor_id = %self.sensor_id, "adapter already listening");
                     return;
@@ -94,7 +99,6 @@ macro_rules! define_sensor_adapter {
 
                 let sensor_id = self.sensor_id.clone();
                 let sensor_id_arc: contracts::SensorId = sensor_id.clone().into();
-                let drop_policy = self.config.drop_policy;
                 let listening = self.listening.clone();
 
                 debug!(sensor_id = %sensor_id, sensor_type = ?$sensor_type, "starting adapter");
@@ -113,21 +117,29 @@ macro_rules! define_sensor_adapter {
                         }
                     };
 
+                    let Some(timestamp) =
+                        contracts::SimTime::try_from_secs_f64(sensor_data.timestamp())
+                    else {
+                        metrics.record_parse_error();
+                        trace!(sensor_id = %sensor_id, "non-finite sensor timestamp");
+                        return;
+                    };
+
                     let packet = SensorPacket {
                         sensor_id: sensor_id_arc.clone(),
                         sensor_type: $sensor_type,
-                        timestamp: sensor_data.timestamp(),
+                        timestamp,
                         frame_id: Some(sensor_data.frame() as u64),
                         payload: $to_payload_fn(&data),
                     };
 
                     metrics.record_received();
-                    send_packet(&tx, packet, &metrics, &sensor_id, drop_policy);
+                    send_packet(&queue, packet, &metrics, &sensor_id);
                 });
             }
 
             #[cfg(not(feature = "real-carla"))]
-            fn start(&self, _tx: Sender<SensorPacket>, _metrics: Arc<IngestionMetrics>) {
+            fn start(&self, _queue: QueueProducer, _metrics: Arc<IngestionMetrics>) {
                 self.listening.store(true, Ordering::SeqCst);
                 warn!(sensor_id = %self.sensor_id, "adapter started in mock mode");
             }
@@ -12,6 +12,9 @@ pub struct BackpressureConfig {
 
     /// Drop policy when full
     pub drop_policy: DropPolicy,
+
+    /// Fair-merge weight relative to other sensors
+    pub weight: u32,
 }
 
 impl Default for BackpressureConfig {
@@ -19,6 +22,7 @@ impl Default for BackpressureConfig {
         Self {
             channel_capacity: 100,
             drop_policy: DropPolicy::DropNewest,
+            weight: 1,
         }
     }
 }
@@ -29,8 +33,15 @@ impl BackpressureConfig {
         Self {
             channel_capacity,
             drop_policy,
+            weight: 1,
         }
     }
+
+    /// Set the fair-merge weight
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight.max(1);
+        self
+    }
 }
 
 /// Ingestion metrics
@@ -42,7 +53,7 @@ pub struct IngestionMetrics {
     /// Total packets dropped
     pub packets_dropped: AtomicU64,
 
-    /// Current queue length
+    /// Current queue length (all sensor queues)
     pub queue_len: AtomicUsize,
 
     /// Parse error count
@@ -2,17 +2,21 @@
 //!
 //! Unified adapter implementation based on `SensorSource` trait.
 //! Allows IngestionPipeline to handle Mock and Real sensors uniformly.
+//! Every packet passes a [`PacketValidator`] and the sensor's
+//! [`PreprocessChain`] before it is queued.
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use async_channel::Sender;
-use contracts::{SensorDataCallback, SensorPacket, SensorSource, SensorType};
+use contracts::{PreprocessStage, SensorDataCallback, SensorSource, SensorType};
 use tracing::{debug, trace};
 
 use crate::adapter::SensorAdapter;
 use crate::adapters::common::send_packet;
 use crate::config::{BackpressureConfig, IngestionMetrics};
+use crate::preprocess::PreprocessChain;
+use crate::queue::QueueProducer;
+use crate::validation::PacketValidator;
 
 /// Generic sensor adapter
 ///
@@ -22,6 +26,7 @@ pub struct GenericSensorAdapter {
     sensor_id: String,
     source: Box<dyn SensorSource>,
     config: BackpressureConfig,
+    preprocess: Vec<PreprocessStage>,
     listening: Arc<AtomicBool>,
 }
 
@@ -36,9 +41,16 @@ impl GenericSensorAdapter {
             sensor_id,
             source,
             config,
+            preprocess: Vec::new(),
             listening: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Set pre-processing stages applied to every accepted packet
+    pub fn with_preprocess(mut self, stages: Vec<PreprocessStage>) -> Self {
+        self.preprocess = stages;
+        self
+    }
 }
 
 impl SensorAdapter for GenericSensorAdapter {
@@ -50,14 +62,21 @@ impl SensorAdapter for GenericSensorAdapter {
         self.source.sensor_type()
     }
 
-    fn start(&self, tx: Sender<SensorPacket>, metrics: Arc<IngestionMetrics>) {
+    fn config(&self) -> &BackpressureConfig {
+        &self.config
+    }
+
+    fn start(&self, queue: QueueProducer, metrics: Arc<IngestionMetrics>) {
         if self.listening.swap(true, Ordering::SeqCst) {
             return;
         }
 
         let sensor_id = self.sensor_id.clone();
-        let drop_policy = self.config.drop_policy;
         let listening = self.listening.clone();
+        let stages = Mutex::new((
+            PacketValidator::with_tolerance(self.source.ordering_tolerance()),
+            PreprocessChain::new(&self.preprocess),
+        ));
 
         debug!(sensor_id = %sensor_id, "starting generic adapter");
 
@@ -68,7 +87,21 @@ impl SensorAdapter for GenericSensorAdapter {
 
             metrics.record_received();
             trace!(sensor_id = %sensor_id, "generic adapter received packet");
-            send_packet(&tx, packet, &metrics, &sensor_id, drop_policy);
+            let packet = {
+                let mut stages = stages.lock().unwrap_or_else(|e| e.into_inner());
+                let (validator, chain) = &mut *stages;
+                if !validator.admit(&packet, &metrics, &sensor_id) {
+                    return;
+                }
+                match chain.apply(packet) {
+                    Some(packet) => packet,
+                    None => {
+                        trace!(sensor_id = %sensor_id, "packet decimated");
+                        return;
+                    }
+                }
+            };
+            send_packet(&queue, packet, &metrics, &sensor_id);
         });
 
         self.source.listen(callback);
@@ -90,7 +123,9 @@ impl SensorAdapter for GenericSensorAdapter {
 mod tests {
     use super::*;
     use crate::config::DropPolicy;
+    use crate::queue::SensorQueue;
     use async_channel::bounded;
+    use contracts::SensorPacket;
     use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
@@ -136,7 +171,7 @@ mod tests {
                     let packet = SensorPacket {
                         sensor_id: sensor_id.clone().into(),
                         sensor_type,
-                        timestamp: frame_id as f64 * 0.033,
+                        timestamp: contracts::SimTime::from_nanos(frame_id as i64 * 33_000_000),
                         frame_id: Some(frame_id),
                         payload: contracts::SensorPayload::Imu(contracts::ImuData {
                             accelerometer: contracts::Vector3::default(),
@@ -165,16 +200,19 @@ mod tests {
         let adapter = GenericSensorAdapter::new(
             "test".to_string(),
             Box::new(source),
-            BackpressureConfig {
-                channel_capacity: 10,
-                drop_policy: DropPolicy::DropNewest,
-            },
+            BackpressureConfig::new(10, DropPolicy::DropNewest),
         );
 
-        let (tx, rx) = bounded(10);
+        let (doorbell, _wake) = bounded(1);
+        let (producer, queue) = SensorQueue::new(
+            "test",
+            adapter.config(),
+            doorbell,
+            contracts::MemoryAccountant::unlimited(),
+        );
         let metrics = Arc::new(IngestionMetrics::new());
 
-        adapter.start(tx, metrics.clone());
+        adapter.start(producer, metrics.clone());
         assert!(adapter.is_listening());
 
         // Wait for some packets
@@ -185,7 +223,7 @@ mod tests {
 
         // Should have received some packets
         let count = Arc::new(AtomicU64::new(0));
-        while rx.try_recv().is_ok() {
+        while queue.try_recv().is_some() {
             count.fetch_add(1, Ordering::Relaxed);
         }
         assert!(count.load(Ordering::Relaxed) > 0);
@@ -5,8 +5,10 @@
 //! Responsibilities:
 //! - Register sensor data sources (supports Mock and Real)
 //! - Parse sensor data into `SensorPacket`
-//! - Backpressure management and drop policy
-//! - Send to downstream via async-channel
+//! - Validate payloads and timestamps, quarantining bad packets
+//! - Per-sensor pre-processing (resize, crop, voxel downsample, decimation)
+//! - Per-sensor queues with individual backpressure and drop policy
+//! - Weighted round-robin merge into one downstream stream
 //!
 //! ## Usage Example (Unified Interface)
 //!
@@ -45,6 +47,9 @@ mod error;
 mod generic_adapter;
 mod mock;
 mod pipeline;
+mod preprocess;
+mod queue;
+mod validation;
 
 // Re-exports
 pub use adapter::SensorAdapter;
@@ -55,3 +60,6 @@ pub use error::{IngestionError, Result};
 pub use generic_adapter::GenericSensorAdapter;
 pub use mock::{MockSensorConfig, MockSensorSource};
 pub use pipeline::IngestionPipeline;
+pub use preprocess::PreprocessChain;
+pub use queue::{FairReceiver, PushOutcome, QueueBacklog, QueueProducer};
+pub use validation::{check_payload, PacketValidator, RejectReason};
@@ -10,8 +10,8 @@ use std::time::Duration;
 use async_channel::{bounded, Receiver};
 use bytes::Bytes;
 use contracts::{
-    GnssData, ImageData, ImageFormat, ImuData, PointCloudData, SensorPacket, SensorPayload,
-    SensorType, Vector3,
+    GnssData, ImageData, ImageFormat, ImuData, LidarPoint, PointCloudData, SensorPacket,
+    SensorPayload, SensorType, SimTime, Vector3,
 };
 use tracing::{debug, trace};
 
@@ -142,7 +142,7 @@ impl MockSensorSource {
             );
 
             while running.load(Ordering::Relaxed) {
-                let timestamp = start_time.elapsed().as_secs_f64();
+                let timestamp = SimTime::from_nanos(start_time.elapsed().as_nanos() as i64);
                 frame_id += 1;
 
                 let payload = match config.sensor_type {
@@ -156,12 +156,9 @@ impl MockSensorSource {
                         })
                     }
                     SensorType::Lidar => {
-                        let size = (config.lidar_points * 16) as usize;
-                        SensorPayload::PointCloud(PointCloudData {
-                            num_points: config.lidar_points,
-                            point_stride: 16,
-                            data: Bytes::from(vec![0u8; size]),
-                        })
+                        SensorPayload::PointCloud(PointCloudData::from_points_vec(
+                            vec![LidarPoint::default(); config.lidar_points as usize],
+                        ))
                     }
                     SensorType::Imu => SensorPayload::Imu(ImuData {
                         accelerometer: Vector3 {
@@ -177,10 +174,11 @@ impl MockSensorSource {
                         longitude: -74.0 + (frame_id as f64 * 0.0001),
                         altitude: 100.0,
                     }),
-                    SensorType::Radar => SensorPayload::Radar(contracts::RadarData {
-                        num_detections: 5,
-                        data: Bytes::from(vec![0u8; 5 * 16]),
-                    }),
+                    SensorType::Radar => {
+                        SensorPayload::Radar(contracts::RadarData::from_detections_vec(
+                            vec![contracts::RadarDetection::default(); 5],
+                        ))
+                    }
                 };
 
                 let packet = SensorPacket {
@@ -201,7 +199,7 @@ impl MockSensorSource {
                 trace!(
                     sensor_id = %config.sensor_id,
                     frame_id,
-                    timestamp,
+                    timestamp = %timestamp,
                     "mock packet sent"
                 );
 
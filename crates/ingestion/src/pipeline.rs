@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_channel::{bounded, Receiver, Sender};
-use contracts::{SensorPacket, SensorSource};
+use contracts::{MemoryAccountant, PreprocessStage, SensorSource};
 use tracing::{debug, info, instrument};
 
 #[cfg(feature = "real-carla")]
@@ -17,61 +17,82 @@ use crate::adapter::SensorAdapter;
 use crate::adapters::{CameraAdapter, GnssAdapter, ImuAdapter, LidarAdapter, RadarAdapter};
 use crate::config::{BackpressureConfig, IngestionMetrics};
 use crate::generic_adapter::GenericSensorAdapter;
+use crate::queue::{FairReceiver, QueueBacklog, QueueProducer, QueueRegistry, SensorQueue};
 
 /// Ingestion Pipeline
 ///
 /// Manages multiple sensor adapters, provides unified data stream output.
 /// Supports unified registration of Mock and Real sensors.
+///
+/// Each sensor gets its own bounded queue; the queues are merged by a
+/// weighted round-robin [`FairReceiver`].
 pub struct IngestionPipeline {
     /// Registered adapters
     adapters: HashMap<String, Box<dyn SensorAdapter>>,
 
+    /// Producer side of each sensor's queue
+    producers: HashMap<String, QueueProducer>,
+
+    /// Consumer side of all sensor queues
+    queues: QueueRegistry,
+
     /// Shared metrics
     metrics: Arc<IngestionMetrics>,
 
-    /// Data sender (shared by all adapters)
-    tx: Sender<SensorPacket>,
+    /// Wakes the receiver when any queue gets a packet
+    doorbell: Sender<()>,
 
-    /// Data receiver
-    rx: Option<Receiver<SensorPacket>>,
+    /// Wake-up side of the doorbell, moved into the receiver
+    wake: Option<Receiver<()>>,
 
     /// Default backpressure configuration
     default_config: BackpressureConfig,
+
+    /// Payload byte budget shared by all sensor queues
+    memory: MemoryAccountant,
 }
 
 impl IngestionPipeline {
     /// Create new Ingestion Pipeline
     ///
     /// # Arguments
-    /// * `channel_capacity` - Channel capacity
+    /// * `channel_capacity` - Default per-sensor queue capacity
     pub fn new(channel_capacity: usize) -> Self {
-        let (tx, rx) = bounded(channel_capacity);
-
-        Self {
-            adapters: HashMap::new(),
-            metrics: Arc::new(IngestionMetrics::new()),
-            tx,
-            rx: Some(rx),
-            default_config: BackpressureConfig {
-                channel_capacity,
-                ..Default::default()
-            },
-        }
+        Self::with_config(BackpressureConfig {
+            channel_capacity,
+            ..Default::default()
+        })
     }
 
-    /// Create with custom backpressure configuration
+    /// Create with custom default backpressure configuration
     pub fn with_config(config: BackpressureConfig) -> Self {
-        let (tx, rx) = bounded(config.channel_capacity);
+        let (doorbell, wake) = bounded(1);
 
         Self {
             adapters: HashMap::new(),
+            producers: HashMap::new(),
+            queues: QueueRegistry::default(),
             metrics: Arc::new(IngestionMetrics::new()),
-            tx,
-            rx: Some(rx),
+            doorbell,
+            wake: Some(wake),
             default_config: config,
+            memory: MemoryAccountant::unlimited(),
         }
     }
 
+    /// Charge queued payload bytes to a shared memory accountant
+    ///
+    /// Must be set before sensors are registered.
+    pub fn with_memory(mut self, memory: MemoryAccountant) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Default backpressure configuration for sensors registered without one
+    pub fn default_config(&self) -> &BackpressureConfig {
+        &self.default_config
+    }
+
     /// Register sensor data source (unified interface)
     ///
     /// This is the recommended registration method, supports Mock and Real sensors.
@@ -90,14 +111,28 @@ impl IngestionPipeline {
         sensor_id: String,
         source: Box<dyn SensorSource>,
         config: Option<BackpressureConfig>,
+    ) {
+        self.register_sensor_source_with_preprocess(sensor_id, source, config, Vec::new());
+    }
+
+    /// Register sensor data source with a pre-processing chain
+    ///
+    /// Stages run in order on every validated packet before it is queued.
+    pub fn register_sensor_source_with_preprocess(
+        &mut self,
+        sensor_id: String,
+        source: Box<dyn SensorSource>,
+        config: Option<BackpressureConfig>,
+        preprocess: Vec<PreprocessStage>,
     ) {
         let adapter = GenericSensorAdapter::new(
             sensor_id.clone(),
             source,
             config.unwrap_or_else(|| self.default_config.clone()),
-        );
+        )
+        .with_preprocess(preprocess);
         debug!(sensor_id = %sensor_id, "registered sensor source");
-        self.adapters.insert(sensor_id, Box::new(adapter));
+        self.insert_adapter(sensor_id, Box::new(adapter));
     }
 
     /// Register sensor (using carla-rust Sensor)
@@ -123,6 +158,19 @@ impl IngestionPipeline {
             config.unwrap_or_else(|| self.default_config.clone()),
         );
         debug!(sensor_id = %sensor_id, "registered sensor adapter");
+        self.insert_adapter(sensor_id, adapter);
+    }
+
+    /// Create the adapter's queue and register both
+    fn insert_adapter(&mut self, sensor_id: String, adapter: Box<dyn SensorAdapter>) {
+        let (producer, queue) = SensorQueue::new(
+            &sensor_id,
+            adapter.config(),
+            self.doorbell.clone(),
+            self.memory.clone(),
+        );
+        self.queues.insert(queue);
+        self.producers.insert(sensor_id.clone(), producer);
         self.adapters.insert(sensor_id, adapter);
     }
 
@@ -164,8 +212,11 @@ impl IngestionPipeline {
 
     fn start_adapter(&self, sensor_id: &str, adapter: &dyn SensorAdapter) {
         if !adapter.is_listening() {
+            let Some(producer) = self.producers.get(sensor_id) else {
+                return;
+            };
             debug!(sensor_id = %sensor_id, "starting adapter");
-            adapter.start(self.tx.clone(), self.metrics.clone());
+            adapter.start(producer.clone(), self.metrics.clone());
         }
     }
 
@@ -176,11 +227,26 @@ impl IngestionPipeline {
         }
     }
 
-    /// Get data stream receiver
+    /// Get the merged data stream receiver
     ///
     /// Note: Can only be called once, subsequent calls return None
-    pub fn take_receiver(&mut self) -> Option<Receiver<SensorPacket>> {
-        self.rx.take()
+    pub fn take_receiver(&mut self) -> Option<FairReceiver> {
+        let wake = self.wake.take()?;
+        Some(FairReceiver::new(
+            self.queues.clone(),
+            wake,
+            self.metrics.clone(),
+        ))
+    }
+
+    /// Packets currently queued per sensor
+    pub fn queue_depths(&self) -> HashMap<String, usize> {
+        self.queues.depths().into_iter().collect()
+    }
+
+    /// Fill levels of the sensor queues, shared with producers
+    pub fn backlog(&self) -> QueueBacklog {
+        QueueBacklog(self.queues.clone())
     }
 
     /// Get metrics reference
@@ -224,4 +290,149 @@ mod tests {
         assert!(pipeline.take_receiver().is_some());
         assert!(pipeline.take_receiver().is_none());
     }
+
+    struct BurstSource {
+        sensor_id: String,
+        count: u64,
+    }
+
+    impl SensorSource for BurstSource {
+        fn sensor_id(&self) -> &str {
+            &self.sensor_id
+        }
+
+        fn sensor_type(&self) -> contracts::SensorType {
+            contracts::SensorType::Imu
+        }
+
+        fn listen(&self, callback: contracts::SensorDataCallback) {
+            for seq in 0..self.count {
+                callback(contracts::SensorPacket {
+                    sensor_id: self.sensor_id.as_str().into(),
+                    sensor_type: contracts::SensorType::Imu,
+                    timestamp: contracts::SimTime::from_nanos(seq as i64),
+                    frame_id: Some(seq),
+                    payload: contracts::SensorPayload::Raw(bytes::Bytes::new()),
+                });
+            }
+        }
+
+        fn stop(&self) {}
+
+        fn is_listening(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_burst_only_overflows_own_queue() {
+        let mut pipeline = IngestionPipeline::new(8);
+        let source = |sensor_id: &str, count| {
+            Box::new(BurstSource {
+                sensor_id: sensor_id.to_string(),
+                count,
+            })
+        };
+        pipeline.register_sensor_source("imu".into(), source("imu", 50), None);
+        pipeline.register_sensor_source("cam".into(), source("cam", 2), None);
+        pipeline.start_all();
+
+        let depths = pipeline.queue_depths();
+        assert_eq!(depths["imu"], 8);
+        assert_eq!(depths["cam"], 2);
+        assert_eq!(pipeline.metrics().snapshot().packets_dropped, 42);
+
+        let mut rx = pipeline.take_receiver().unwrap();
+        let first: Vec<String> = (0..4)
+            .map(|_| rx.try_recv().unwrap().sensor_id.to_string())
+            .collect();
+        assert_eq!(first.iter().filter(|id| *id == "cam").count(), 2);
+    }
+
+    /// Streams large packets from its own thread until stopped
+    #[derive(Default)]
+    struct StreamingSource {
+        running: Arc<std::sync::atomic::AtomicBool>,
+        thread: std::sync::Mutex<Option<std::thread::JoinHandle<()>>>,
+    }
+
+    impl SensorSource for StreamingSource {
+        fn sensor_id(&self) -> &str {
+            "cam"
+        }
+
+        fn sensor_type(&self) -> contracts::SensorType {
+            contracts::SensorType::Imu
+        }
+
+        fn listen(&self, callback: contracts::SensorDataCallback) {
+            use std::sync::atomic::Ordering;
+            self.running.store(true, Ordering::SeqCst);
+            let running = self.running.clone();
+            let thread = std::thread::spawn(move || {
+                let mut seq = 0u64;
+                while running.load(Ordering::SeqCst) {
+                    callback(contracts::SensorPacket {
+                        sensor_id: "cam".into(),
+                        sensor_type: contracts::SensorType::Imu,
+                        timestamp: contracts::SimTime::from_nanos(seq as i64),
+                        frame_id: Some(seq),
+                        payload: contracts::SensorPayload::Raw(bytes::Bytes::from(vec![
+                            0u8;
+                            600 * 1024
+                        ])),
+                    });
+                    seq += 1;
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            });
+            *self.thread.lock().unwrap() = Some(thread);
+        }
+
+        fn stop(&self) {
+            self.running
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+            if let Some(thread) = self.thread.lock().unwrap().take() {
+                thread.join().unwrap();
+            }
+        }
+
+        fn is_listening(&self) -> bool {
+            self.running.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn test_blocking_budget_does_not_stall_stop() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            ingestion: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Block,
+                block_timeout_ms: 60_000,
+            }),
+            ..Default::default()
+        });
+        let mut pipeline = IngestionPipeline::new(100).with_memory(memory);
+        pipeline.register_sensor_source("cam".into(), Box::<StreamingSource>::default(), None);
+        pipeline.start_all();
+
+        // Nobody consumes: the budget stays exhausted
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while pipeline.metrics().snapshot().packets_dropped == 0 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "budget never exhausted"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let (done_tx, done_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            pipeline.stop_all();
+            let _ = done_tx.send(pipeline);
+        });
+        done_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("stop_all should not wait on the memory budget");
+    }
 }
@@ -0,0 +1,276 @@
+//! Per-sensor pre-processing chain
+//!
+//! Shrinks payloads during ingestion so bandwidth-limited rigs never carry
+//! full-resolution data to the sync engine and sinks. Stages run in the
+//! configured order; a stage that does not apply to a payload leaves it
+//! unchanged.
+
+use std::collections::HashSet;
+
+use bytes::Bytes;
+use contracts::{ImageData, ImageFormat, PreprocessStage, SensorPacket, SensorPayload, SimTime};
+
+/// Fraction of the decimation period a packet must trail the last kept one,
+/// so sensor jitter does not skip a whole output period
+const DECIMATE_TOLERANCE: f64 = 0.9;
+
+/// Ordered pre-processing stages for one sensor
+#[derive(Debug, Clone, Default)]
+pub struct PreprocessChain {
+    stages: Vec<PreprocessStage>,
+    /// Last kept timestamp per stage (used by `decimate`)
+    last_kept: Vec<Option<SimTime>>,
+}
+
+impl PreprocessChain {
+    /// Create a chain from configured stages
+    pub fn new(stages: &[PreprocessStage]) -> Self {
+        Self {
+            stages: stages.to_vec(),
+            last_kept: vec![None; stages.len()],
+        }
+    }
+
+    /// Whether the chain has no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Run the chain; `None` means the packet was decimated away
+    pub fn apply(&mut self, mut packet: SensorPacket) -> Option<SensorPacket> {
+        for (stage, last_kept) in self.stages.iter().zip(&mut self.last_kept) {
+            match (*stage, &mut packet.payload) {
+                (PreprocessStage::Decimate { rate_hz }, _) => {
+                    let min_gap = DECIMATE_TOLERANCE / rate_hz;
+                    if let Some(last) = *last_kept {
+                        if (packet.timestamp - last).as_secs_f64() < min_gap {
+                            return None;
+                        }
+                    }
+                    *last_kept = Some(packet.timestamp);
+                }
+                (PreprocessStage::Resize { width, height }, SensorPayload::Image(image)) => {
+                    resize(image, width, height);
+                }
+                (
+                    PreprocessStage::Crop {
+                        x,
+                        y,
+                        width,
+                        height,
+                    },
+                    SensorPayload::Image(image),
+                ) => crop(image, x, y, width, height),
+                (PreprocessStage::ConvertFormat { format }, SensorPayload::Image(image)) => {
+                    convert_format(image, format);
+                }
+                (PreprocessStage::RangeCrop { min_m, max_m }, SensorPayload::PointCloud(cloud)) => {
+                    cloud.retain_points(|[x, y, z]| {
+                        let d2 = x * x + y * y + z * z;
+                        d2 >= min_m * min_m && d2 <= max_m * max_m
+                    });
+                }
+                (
+                    PreprocessStage::VoxelDownsample { voxel_m },
+                    SensorPayload::PointCloud(cloud),
+                ) => {
+                    let mut occupied = HashSet::new();
+                    cloud.retain_points(|[x, y, z]| {
+                        let cell = |v: f32| (v / voxel_m).floor() as i32;
+                        occupied.insert((cell(x), cell(y), cell(z)))
+                    });
+                }
+                _ => {}
+            }
+        }
+        Some(packet)
+    }
+}
+
+/// Nearest-neighbour resize
+fn resize(image: &mut ImageData, width: u32, height: u32) {
+    let (src_w, src_h) = (image.width as usize, image.height as usize);
+    if (width, height) == (image.width, image.height) || src_w == 0 || src_h == 0 {
+        return;
+    }
+    let (dst_w, dst_h) = (width as usize, height as usize);
+    let bpp = image.format.bytes_per_pixel();
+
+    let mut data = Vec::with_capacity(dst_w * dst_h * bpp);
+    for y in 0..dst_h {
+        let row = (y * src_h / dst_h) * src_w;
+        for x in 0..dst_w {
+            let offset = (row + x * src_w / dst_w) * bpp;
+            data.extend_from_slice(&image.data[offset..offset + bpp]);
+        }
+    }
+    image.width = width;
+    image.height = height;
+    image.data = Bytes::from(data);
+}
+
+/// Crop to a region clamped to the image; an empty region leaves it unchanged
+fn crop(image: &mut ImageData, x: u32, y: u32, width: u32, height: u32) {
+    let x1 = x.saturating_add(width).min(image.width);
+    let y1 = y.saturating_add(height).min(image.height);
+    if x >= x1 || y >= y1 {
+        return;
+    }
+    let bpp = image.format.bytes_per_pixel();
+    let stride = image.width as usize * bpp;
+    let (start, end) = (x as usize * bpp, x1 as usize * bpp);
+
+    let mut data = Vec::with_capacity((end - start) * (y1 - y) as usize);
+    for row in y as usize..y1 as usize {
+        data.extend_from_slice(&image.data[row * stride + start..row * stride + end]);
+    }
+    image.width = x1 - x;
+    image.height = y1 - y;
+    image.data = Bytes::from(data);
+}
+
+/// Byte offsets of R, G, B and optional A within a pixel
+fn channel_layout(format: ImageFormat) -> Option<([usize; 3], Option<usize>)> {
+    match format {
+        ImageFormat::Rgb8 => Some(([0, 1, 2], None)),
+        ImageFormat::Rgba8 => Some(([0, 1, 2], Some(3))),
+        ImageFormat::Bgra8 => Some(([2, 1, 0], Some(3))),
+        ImageFormat::Depth | ImageFormat::SemanticSeg => None,
+    }
+}
+
+/// Convert between colour formats; encoded formats are left unchanged
+fn convert_format(image: &mut ImageData, format: ImageFormat) {
+    if image.format == format {
+        return;
+    }
+    let (Some((src_rgb, src_a)), Some((dst_rgb, dst_a))) =
+        (channel_layout(image.format), channel_layout(format))
+    else {
+        return;
+    };
+    let (src_bpp, dst_bpp) = (image.format.bytes_per_pixel(), format.bytes_per_pixel());
+
+    let mut data = vec![0u8; image.data.len() / src_bpp * dst_bpp];
+    for (src, dst) in image
+        .data
+        .chunks_exact(src_bpp)
+        .zip(data.chunks_exact_mut(dst_bpp))
+    {
+        for (s, d) in src_rgb.iter().zip(dst_rgb) {
+            dst[d] = src[*s];
+        }
+        if let Some(d) = dst_a {
+            dst[d] = src_a.map_or(u8::MAX, |s| src[s]);
+        }
+    }
+    image.format = format;
+    image.data = Bytes::from(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{LidarPoint, PointCloudData, SensorType};
+
+    fn packet(seconds: f64, payload: SensorPayload) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "sensor".into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(seconds),
+            frame_id: None,
+            payload,
+        }
+    }
+
+    /// 4x2 BGRA image where pixel (x, y) has blue = x, green = y
+    fn bgra_image() -> SensorPayload {
+        let data = (0..2u8)
+            .flat_map(|y| (0..4u8).flat_map(move |x| [x, y, 9, 255]))
+            .collect::<Vec<_>>();
+        SensorPayload::Image(ImageData {
+            width: 4,
+            height: 2,
+            format: ImageFormat::Bgra8,
+            data: Bytes::from(data),
+        })
+    }
+
+    fn cloud(points: &[[f32; 3]]) -> SensorPayload {
+        let points = points
+            .iter()
+            .map(|&[x, y, z]| LidarPoint {
+                x,
+                y,
+                z,
+                intensity: 1.0,
+            })
+            .collect::<Vec<_>>();
+        SensorPayload::PointCloud(PointCloudData::from_points(&points))
+    }
+
+    #[test]
+    fn test_image_stages() {
+        let mut chain = PreprocessChain::new(&[
+            PreprocessStage::Crop {
+                x: 2,
+                y: 0,
+                width: 10,
+                height: 2,
+            },
+            PreprocessStage::Resize {
+                width: 1,
+                height: 2,
+            },
+            PreprocessStage::ConvertFormat {
+                format: ImageFormat::Rgb8,
+            },
+        ]);
+        let out = chain.apply(packet(0.0, bgra_image())).unwrap();
+        let SensorPayload::Image(image) = out.payload else {
+            panic!("expected image");
+        };
+        assert_eq!((image.width, image.height), (1, 2));
+        assert_eq!(image.format, ImageFormat::Rgb8);
+        // Pixel (2, y) after crop; RGB = (9, y, 2)
+        assert_eq!(&image.data[..], &[9, 0, 2, 9, 1, 2]);
+    }
+
+    #[test]
+    fn test_point_cloud_stages() {
+        let mut chain = PreprocessChain::new(&[
+            PreprocessStage::RangeCrop {
+                min_m: 1.0,
+                max_m: 10.0,
+            },
+            PreprocessStage::VoxelDownsample { voxel_m: 1.0 },
+        ]);
+        let points = [
+            [0.1, 0.0, 0.0],  // too close
+            [50.0, 0.0, 0.0], // too far
+            [2.1, 2.1, 0.0],
+            [2.4, 2.9, 0.5], // same voxel as previous
+            [-3.0, 0.0, 0.0],
+        ];
+        let out = chain.apply(packet(0.0, cloud(&points))).unwrap();
+        let SensorPayload::PointCloud(cloud) = out.payload else {
+            panic!("expected point cloud");
+        };
+        assert_eq!(cloud.num_points, 2);
+        assert_eq!(cloud.data.len(), 32);
+        assert_eq!(cloud.xyz().nth(1), Some([-3.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_decimate_tolerates_jitter() {
+        let mut chain = PreprocessChain::new(&[PreprocessStage::Decimate { rate_hz: 10.0 }]);
+        // 20 Hz source with up to 1 ms jitter
+        let kept = [0.0, 0.0505, 0.0995, 0.150, 0.2005, 0.2495, 0.300]
+            .into_iter()
+            .filter_map(|t| chain.apply(packet(t, SensorPayload::Raw(Bytes::new()))))
+            .map(|p| p.timestamp)
+            .collect::<Vec<_>>();
+        assert_eq!(kept.len(), 4);
+        assert_eq!(kept[1], SimTime::from_secs_f64(0.0995));
+    }
+}
@@ -0,0 +1,474 @@
+//! Per-sensor ingestion queues and the fair merge into the sync loop.
+//!
+//! Every sensor owns a bounded queue with its own capacity and drop policy,
+//! so a burst from one sensor only overflows that sensor's queue. The
+//! [`FairReceiver`] drains the queues in weighted round-robin order.
+//! Queued payload bytes are charged to the shared ingestion memory budget;
+//! pushing never waits for it, since producers run in simulator callbacks.
+
+use std::sync::{Arc, Mutex};
+
+use async_channel::{bounded, Receiver, RecvError, Sender, TrySendError};
+use contracts::{DropPolicy, MemoryAccountant, MemoryStage, SensorPacket};
+use metrics::{Counter, Gauge};
+
+use crate::config::{BackpressureConfig, IngestionMetrics};
+
+/// Result of pushing a packet into a sensor queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Packet queued
+    Queued,
+    /// Queue full, the incoming packet was discarded
+    DroppedNewest,
+    /// Queue full, the oldest queued packet was evicted for the incoming one
+    DroppedOldest,
+    /// Ingestion memory budget exhausted, the incoming packet was discarded
+    DroppedOverBudget,
+    /// Consumer is gone
+    Closed,
+}
+
+/// Producer side of one sensor's ingestion queue
+#[derive(Clone)]
+pub struct QueueProducer {
+    tx: Sender<SensorPacket>,
+    /// Receiver clone used to evict the oldest packet under `DropOldest`
+    evict: Receiver<SensorPacket>,
+    drop_policy: DropPolicy,
+    doorbell: Sender<()>,
+    memory: MemoryAccountant,
+    depth: Gauge,
+    dropped: Counter,
+}
+
+impl QueueProducer {
+    /// Push a packet, applying the queue's drop policy when full.
+    ///
+    /// Never waits, even if the ingestion stage blocks on exceed: the caller
+    /// is a simulator callback. When the budget is exhausted, `DropOldest`
+    /// evicts this sensor's oldest packets to make room and `DropNewest`
+    /// discards the incoming packet.
+    pub fn push(&self, packet: SensorPacket) -> PushOutcome {
+        let bytes = packet.payload_bytes();
+        let mut budget_evictions = 0;
+        while !self.memory.try_reserve(MemoryStage::Ingestion, bytes) {
+            let evicted = match self.drop_policy {
+                DropPolicy::DropOldest => self.evict.try_recv().ok(),
+                DropPolicy::DropNewest => None,
+            };
+            let Some(evicted) = evicted else {
+                self.dropped.increment(budget_evictions + 1);
+                self.depth.set(self.tx.len() as f64);
+                return PushOutcome::DroppedOverBudget;
+            };
+            self.release(&evicted);
+            budget_evictions += 1;
+        }
+        self.dropped.increment(budget_evictions);
+
+        let outcome = match self.tx.try_send(packet) {
+            Ok(()) => PushOutcome::Queued,
+            Err(TrySendError::Closed(_)) => PushOutcome::Closed,
+            Err(TrySendError::Full(packet)) => match self.drop_policy {
+                DropPolicy::DropNewest => PushOutcome::DroppedNewest,
+                DropPolicy::DropOldest => {
+                    if let Ok(evicted) = self.evict.try_recv() {
+                        self.release(&evicted);
+                    }
+                    match self.tx.try_send(packet) {
+                        Ok(()) => PushOutcome::DroppedOldest,
+                        Err(TrySendError::Full(_)) => PushOutcome::DroppedNewest,
+                        Err(TrySendError::Closed(_)) => PushOutcome::Closed,
+                    }
+                }
+            },
+        };
+
+        match outcome {
+            PushOutcome::Queued => {}
+            PushOutcome::DroppedOldest => self.dropped.increment(1),
+            PushOutcome::DroppedNewest | PushOutcome::DroppedOverBudget => {
+                self.memory.release(MemoryStage::Ingestion, bytes);
+                self.dropped.increment(1);
+            }
+            PushOutcome::Closed => {
+                self.memory.release(MemoryStage::Ingestion, bytes);
+                return outcome;
+            }
+        }
+        self.depth.set(self.tx.len() as f64);
+        // Wake the merger; a pending wake-up already covers this packet
+        let _ = self.doorbell.try_send(());
+        match outcome {
+            PushOutcome::Queued if budget_evictions > 0 => PushOutcome::DroppedOldest,
+            outcome => outcome,
+        }
+    }
+
+    fn release(&self, packet: &SensorPacket) {
+        self.memory
+            .release(MemoryStage::Ingestion, packet.payload_bytes());
+    }
+
+    /// Packets currently queued
+    pub fn len(&self) -> usize {
+        self.tx.len()
+    }
+
+    /// Whether the queue is empty
+    pub fn is_empty(&self) -> bool {
+        self.tx.is_empty()
+    }
+}
+
+/// Consumer side of one sensor's ingestion queue
+pub(crate) struct SensorQueue {
+    sensor_id: String,
+    weight: u32,
+    rx: Receiver<SensorPacket>,
+    memory: MemoryAccountant,
+    depth: Gauge,
+}
+
+impl SensorQueue {
+    /// Create a queue for `sensor_id` sized by `config`, charging queued
+    /// payload bytes to `memory`
+    pub(crate) fn new(
+        sensor_id: &str,
+        config: &BackpressureConfig,
+        doorbell: Sender<()>,
+        memory: MemoryAccountant,
+    ) -> (QueueProducer, Self) {
+        let (tx, rx) = bounded(config.channel_capacity.max(1));
+        let depth = metrics::gauge!("ingestion_queue_depth", "sensor_id" => sensor_id.to_string());
+        let producer = QueueProducer {
+            tx,
+            evict: rx.clone(),
+            drop_policy: config.drop_policy,
+            doorbell,
+            memory: memory.clone(),
+            depth: depth.clone(),
+            dropped: metrics::counter!(
+                "ingestion_queue_dropped_total",
+                "sensor_id" => sensor_id.to_string()
+            ),
+        };
+        let queue = Self {
+            sensor_id: sensor_id.to_string(),
+            weight: config.weight.max(1),
+            rx,
+            memory,
+            depth,
+        };
+        (producer, queue)
+    }
+
+    pub(crate) fn try_recv(&self) -> Option<SensorPacket> {
+        let packet = self.rx.try_recv().ok()?;
+        self.memory
+            .release(MemoryStage::Ingestion, packet.payload_bytes());
+        self.depth.set(self.rx.len() as f64);
+        Some(packet)
+    }
+}
+
+impl Drop for SensorQueue {
+    /// Return the budget of packets that were never consumed
+    fn drop(&mut self) {
+        while let Ok(packet) = self.rx.try_recv() {
+            self.memory
+                .release(MemoryStage::Ingestion, packet.payload_bytes());
+        }
+    }
+}
+
+/// Queues registered with a pipeline, shared with its [`FairReceiver`]
+#[derive(Clone, Default)]
+pub(crate) struct QueueRegistry(Arc<Mutex<Vec<SensorQueue>>>);
+
+impl QueueRegistry {
+    /// Add a queue, replacing any previous queue of the same sensor
+    pub(crate) fn insert(&self, queue: SensorQueue) {
+        let mut queues = self.lock();
+        queues.retain(|q| q.sensor_id != queue.sensor_id);
+        queues.push(queue);
+    }
+
+    /// Queued packets per sensor
+    pub(crate) fn depths(&self) -> Vec<(String, usize)> {
+        self.lock()
+            .iter()
+            .map(|q| (q.sensor_id.clone(), q.rx.len()))
+            .collect()
+    }
+
+    /// Fill ratio of the fullest queue
+    fn max_fill(&self) -> f64 {
+        self.lock()
+            .iter()
+            .map(|q| q.rx.len() as f64 / q.rx.capacity().unwrap_or(usize::MAX).max(1) as f64)
+            .fold(0.0, f64::max)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<SensorQueue>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Read-only view of queue fill levels, for sources that pace themselves
+/// on downstream backpressure
+#[derive(Clone)]
+pub struct QueueBacklog(pub(crate) QueueRegistry);
+
+impl QueueBacklog {
+    /// Fill ratio (0.0..=1.0) of the fullest sensor queue
+    pub fn max_fill(&self) -> f64 {
+        self.0.max_fill()
+    }
+}
+
+/// Merged packet stream over all sensor queues.
+///
+/// Each scheduling round takes up to `weight` packets from a sensor before
+/// moving on, so no sensor can starve the others.
+pub struct FairReceiver {
+    queues: QueueRegistry,
+    doorbell: Receiver<()>,
+    metrics: Arc<IngestionMetrics>,
+    cursor: usize,
+    credit: u32,
+}
+
+impl FairReceiver {
+    pub(crate) fn new(
+        queues: QueueRegistry,
+        doorbell: Receiver<()>,
+        metrics: Arc<IngestionMetrics>,
+    ) -> Self {
+        Self {
+            queues,
+            doorbell,
+            metrics,
+            cursor: 0,
+            credit: 0,
+        }
+    }
+
+    /// Receive the next packet, waiting if all queues are empty.
+    ///
+    /// Fails once every producer and the pipeline are gone and all queues
+    /// have been drained.
+    pub async fn recv(&mut self) -> Result<SensorPacket, RecvError> {
+        loop {
+            if let Some(packet) = self.try_recv() {
+                return Ok(packet);
+            }
+            if self.doorbell.recv().await.is_err() {
+                return self.try_recv().ok_or(RecvError);
+            }
+        }
+    }
+
+    /// Take the next packet in scheduling order without waiting
+    pub fn try_recv(&mut self) -> Option<SensorPacket> {
+        let queues = self.queues.lock();
+        let n = queues.len();
+        if n == 0 {
+            return None;
+        }
+
+        for _ in 0..n {
+            self.cursor %= n;
+            let queue = &queues[self.cursor];
+            if self.credit == 0 {
+                self.credit = queue.weight;
+            }
+            if let Some(packet) = queue.try_recv() {
+                self.credit -= 1;
+                if self.credit == 0 {
+                    self.cursor += 1;
+                }
+                let total = queues.iter().map(|q| q.rx.len()).sum();
+                self.metrics.update_queue_len(total);
+                return Some(packet);
+            }
+            // Empty queue forfeits the rest of its round
+            self.cursor += 1;
+            self.credit = 0;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{SensorPayload, SensorType, SimTime};
+
+    fn packet(sensor_id: &str, seq: u64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_nanos(seq as i64),
+            frame_id: Some(seq),
+            payload: SensorPayload::Raw(Bytes::new()),
+        }
+    }
+
+    fn config(capacity: usize, drop_policy: DropPolicy, weight: u32) -> BackpressureConfig {
+        BackpressureConfig {
+            channel_capacity: capacity,
+            drop_policy,
+            weight,
+        }
+    }
+
+    #[test]
+    fn test_drop_policies_apply_per_queue() {
+        let (doorbell, _wake) = bounded(1);
+        let (oldest, oldest_queue) = SensorQueue::new(
+            "imu",
+            &config(2, DropPolicy::DropOldest, 1),
+            doorbell.clone(),
+            MemoryAccountant::unlimited(),
+        );
+        let (newest, newest_queue) = SensorQueue::new(
+            "cam",
+            &config(2, DropPolicy::DropNewest, 1),
+            doorbell,
+            MemoryAccountant::unlimited(),
+        );
+
+        for seq in 1..=3 {
+            oldest.push(packet("imu", seq));
+            newest.push(packet("cam", seq));
+        }
+        assert_eq!(oldest.push(packet("imu", 4)), PushOutcome::DroppedOldest);
+        assert_eq!(newest.push(packet("cam", 4)), PushOutcome::DroppedNewest);
+
+        let frame_ids = |queue: &SensorQueue| {
+            std::iter::from_fn(|| queue.try_recv())
+                .map(|p| p.frame_id.unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(frame_ids(&oldest_queue), vec![3, 4]);
+        assert_eq!(frame_ids(&newest_queue), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_weighted_round_robin_does_not_starve() {
+        let registry = QueueRegistry::default();
+        let (doorbell, wake) = bounded(1);
+        let (imu, imu_queue) = SensorQueue::new(
+            "imu",
+            &config(100, DropPolicy::DropNewest, 3),
+            doorbell.clone(),
+            MemoryAccountant::unlimited(),
+        );
+        let (cam, cam_queue) = SensorQueue::new(
+            "cam",
+            &config(100, DropPolicy::DropNewest, 1),
+            doorbell,
+            MemoryAccountant::unlimited(),
+        );
+        registry.insert(imu_queue);
+        registry.insert(cam_queue);
+
+        // IMU burst queued ahead of the camera
+        for seq in 0..40 {
+            imu.push(packet("imu", seq));
+        }
+        for seq in 0..4 {
+            cam.push(packet("cam", seq));
+        }
+
+        let mut rx = FairReceiver::new(registry.clone(), wake, Arc::new(IngestionMetrics::new()));
+        let order: Vec<String> = (0..16)
+            .map(|_| rx.try_recv().unwrap().sensor_id.to_string())
+            .collect();
+        let cams = order.iter().filter(|id| *id == "cam").count();
+        assert_eq!(cams, 4);
+        assert_eq!(&order[..4], ["imu", "imu", "imu", "cam"]);
+        assert_eq!(registry.depths().len(), 2);
+    }
+
+    #[test]
+    fn test_byte_budget_shared_across_queues() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            ingestion: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Drop,
+                block_timeout_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let (doorbell, _wake) = bounded(1);
+        let queue = |sensor_id| {
+            SensorQueue::new(
+                sensor_id,
+                &config(100, DropPolicy::DropOldest, 1),
+                doorbell.clone(),
+                memory.clone(),
+            )
+        };
+        let (cam, cam_queue) = queue("cam");
+        let (lidar, lidar_queue) = queue("lidar");
+        let big = |sensor_id: &str, seq| SensorPacket {
+            payload: SensorPayload::Raw(Bytes::from(vec![0u8; 400 * 1024])),
+            ..packet(sensor_id, seq)
+        };
+
+        assert_eq!(cam.push(big("cam", 0)), PushOutcome::Queued);
+        assert_eq!(cam.push(big("cam", 1)), PushOutcome::Queued);
+        assert_eq!(lidar.push(big("lidar", 0)), PushOutcome::DroppedOverBudget);
+        assert_eq!(memory.in_use(MemoryStage::Ingestion), 800 * 1024);
+
+        // Consuming a packet frees budget for the other sensor
+        assert!(cam_queue.try_recv().is_some());
+        assert_eq!(lidar.push(big("lidar", 1)), PushOutcome::Queued);
+
+        drop((cam_queue, lidar_queue));
+        assert_eq!(memory.in_use(MemoryStage::Ingestion), 0);
+    }
+
+    #[test]
+    fn test_exhausted_budget_applies_drop_policy_without_waiting() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            ingestion: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Block,
+                block_timeout_ms: 60_000,
+            }),
+            ..Default::default()
+        });
+        let (doorbell, _wake) = bounded(1);
+        let queue = |sensor_id, drop_policy| {
+            SensorQueue::new(
+                sensor_id,
+                &config(100, drop_policy, 1),
+                doorbell.clone(),
+                memory.clone(),
+            )
+        };
+        let (cam, cam_queue) = queue("cam", DropPolicy::DropOldest);
+        let (lidar, _lidar_queue) = queue("lidar", DropPolicy::DropNewest);
+        let big = |sensor_id: &str, seq| SensorPacket {
+            payload: SensorPayload::Raw(Bytes::from(vec![0u8; 400 * 1024])),
+            ..packet(sensor_id, seq)
+        };
+
+        let started = std::time::Instant::now();
+        assert_eq!(cam.push(big("cam", 0)), PushOutcome::Queued);
+        assert_eq!(cam.push(big("cam", 1)), PushOutcome::Queued);
+        assert_eq!(lidar.push(big("lidar", 0)), PushOutcome::DroppedOverBudget);
+        assert_eq!(cam.push(big("cam", 2)), PushOutcome::DroppedOldest);
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+
+        let frame_ids: Vec<_> = std::iter::from_fn(|| cam_queue.try_recv())
+            .map(|p| p.frame_id.unwrap())
+            .collect();
+        assert_eq!(frame_ids, vec![1, 2]);
+    }
+}
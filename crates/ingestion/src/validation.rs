@@ -0,0 +1,342 @@
+//! Packet validation and quarantine
+//!
+//! Checks payload consistency and timestamp sanity before packets enter
+//! the sensor queues. Rejected packets are quarantined: counted per reason,
+//! logged, and never reach the sync engine or sinks.
+
+use std::collections::VecDeque;
+
+use contracts::{OrderingTolerance, RadarDetection, SensorPacket, SensorPayload, SimTime};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::config::IngestionMetrics;
+
+/// Minimum LiDAR point size (x, y, z: f32)
+const MIN_POINT_STRIDE: u32 = 12;
+
+/// Recent frame_ids remembered for duplicate detection
+const DUPLICATE_WINDOW: usize = 16;
+
+/// Why a packet was quarantined
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RejectReason {
+    /// Payload byte length disagrees with its declared dimensions
+    #[error("payload size mismatch: expected {expected} bytes, got {actual}")]
+    PayloadSize {
+        /// Bytes implied by the declared dimensions
+        expected: usize,
+        /// Bytes actually present
+        actual: usize,
+    },
+
+    /// Point stride cannot hold x, y, z
+    #[error("point stride {0} is smaller than 12 bytes")]
+    PointStride(u32),
+
+    /// NaN or infinite value in a measurement
+    #[error("non-finite {0} value")]
+    NonFinite(&'static str),
+
+    /// Timestamp before the simulation start
+    #[error("negative timestamp {0}")]
+    NegativeTimestamp(SimTime),
+
+    /// Timestamp earlier than the previous accepted packet
+    #[error("timestamp went backwards: {timestamp} < {previous}")]
+    TimestampRegression {
+        /// Rejected packet timestamp
+        timestamp: SimTime,
+        /// Last accepted timestamp
+        previous: SimTime,
+    },
+
+    /// Same frame_id as a recently accepted packet
+    #[error("duplicate frame_id {0}")]
+    DuplicateFrame(u64),
+}
+
+impl RejectReason {
+    /// Metric label for this reason
+    pub fn label(&self) -> &'static str {
+        match self {
+            RejectReason::PayloadSize { .. } => "payload_size",
+            RejectReason::PointStride(_) => "point_stride",
+            RejectReason::NonFinite(_) => "non_finite",
+            RejectReason::NegativeTimestamp(_) => "negative_timestamp",
+            RejectReason::TimestampRegression { .. } => "timestamp_regression",
+            RejectReason::DuplicateFrame(_) => "duplicate_frame",
+        }
+    }
+}
+
+/// Per-sensor packet validator.
+///
+/// Tracks the newest accepted timestamp and the last 16 frame_ids;
+/// rejected packets do not advance that state. A duplicate older than that
+/// window is not detected. Non-finite timestamps never reach the validator:
+/// sources reject them when converting with
+/// [`SimTime::try_from_secs_f64`], and decoding a [`SimTime`] fails on them.
+///
+/// Sources that reorder or repeat packets on purpose (fault injection,
+/// simulated clock jitter) declare an [`OrderingTolerance`] that relaxes the
+/// matching checks.
+#[derive(Debug, Default)]
+pub struct PacketValidator {
+    last_timestamp: Option<SimTime>,
+    recent_frame_ids: VecDeque<u64>,
+    tolerance: OrderingTolerance,
+}
+
+impl PacketValidator {
+    /// Create a validator with no history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a validator accepting the given ordering irregularities
+    pub fn with_tolerance(tolerance: OrderingTolerance) -> Self {
+        Self {
+            tolerance,
+            ..Self::default()
+        }
+    }
+
+    /// Check a packet, recording it as the latest on success
+    pub fn check(&mut self, packet: &SensorPacket) -> Result<(), RejectReason> {
+        if packet.timestamp < SimTime::ZERO {
+            return Err(RejectReason::NegativeTimestamp(packet.timestamp));
+        }
+        if let Some(previous) = self.last_timestamp.filter(|_| !self.tolerance.out_of_order) {
+            if packet.timestamp < previous {
+                return Err(RejectReason::TimestampRegression {
+                    timestamp: packet.timestamp,
+                    previous,
+                });
+            }
+        }
+        if let Some(frame_id) = packet.frame_id.filter(|_| !self.tolerance.duplicates) {
+            if self.recent_frame_ids.contains(&frame_id) {
+                return Err(RejectReason::DuplicateFrame(frame_id));
+            }
+        }
+        check_payload(&packet.payload)?;
+
+        self.last_timestamp = Some(
+            self.last_timestamp
+                .map_or(packet.timestamp, |t| t.max(packet.timestamp)),
+        );
+        if let Some(frame_id) = packet.frame_id {
+            if self.recent_frame_ids.len() == DUPLICATE_WINDOW {
+                self.recent_frame_ids.pop_front();
+            }
+            self.recent_frame_ids.push_back(frame_id);
+        }
+        Ok(())
+    }
+
+    /// Check a packet and quarantine it on failure.
+    ///
+    /// Returns `true` if the packet may be forwarded.
+    pub fn admit(
+        &mut self,
+        packet: &SensorPacket,
+        metrics: &IngestionMetrics,
+        sensor_id: &str,
+    ) -> bool {
+        let Err(reason) = self.check(packet) else {
+            return true;
+        };
+
+        metrics.record_parse_error();
+        metrics::counter!(
+            "ingestion_quarantined_total",
+            "sensor_id" => sensor_id.to_string(),
+            "reason" => reason.label()
+        )
+        .increment(1);
+        warn!(
+            sensor_id = %sensor_id,
+            timestamp = %packet.timestamp,
+            frame_id = ?packet.frame_id,
+            reason = reason.label(),
+            "packet quarantined: {}",
+            reason
+        );
+        false
+    }
+}
+
+/// Check that a payload is internally consistent
+pub fn check_payload(payload: &SensorPayload) -> Result<(), RejectReason> {
+    let expect_len = |expected: usize, actual: usize| {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(RejectReason::PayloadSize { expected, actual })
+        }
+    };
+    let finite = |name: &'static str, values: &[f64]| {
+        if values.iter().all(|v| v.is_finite()) {
+            Ok(())
+        } else {
+            Err(RejectReason::NonFinite(name))
+        }
+    };
+
+    match payload {
+        SensorPayload::Image(image) => {
+            let expected =
+                image.width as usize * image.height as usize * image.format.bytes_per_pixel();
+            expect_len(expected, image.data.len())
+        }
+        SensorPayload::PointCloud(cloud) => {
+            if cloud.num_points > 0 && cloud.point_stride < MIN_POINT_STRIDE {
+                return Err(RejectReason::PointStride(cloud.point_stride));
+            }
+            let expected = cloud.num_points as usize * cloud.point_stride as usize;
+            expect_len(expected, cloud.data.len())
+        }
+        SensorPayload::Radar(radar) => {
+            let expected = radar.num_detections as usize * RadarDetection::STRIDE;
+            expect_len(expected, radar.data.len())
+        }
+        SensorPayload::Imu(imu) => {
+            let (a, g) = (imu.accelerometer, imu.gyroscope);
+            finite("imu", &[a.x, a.y, a.z, g.x, g.y, g.z, imu.compass])
+        }
+        SensorPayload::Gnss(gnss) => {
+            finite("gnss", &[gnss.latitude, gnss.longitude, gnss.altitude])
+        }
+        SensorPayload::Raw(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{ImageData, ImageFormat, PointCloudData, SensorType};
+
+    fn packet(seconds: f64, frame_id: u64, payload: SensorPayload) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "sensor".into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(seconds),
+            frame_id: Some(frame_id),
+            payload,
+        }
+    }
+
+    fn image(width: u32, height: u32, len: usize) -> SensorPayload {
+        SensorPayload::Image(ImageData {
+            width,
+            height,
+            format: ImageFormat::Bgra8,
+            data: Bytes::from(vec![0u8; len]),
+        })
+    }
+
+    #[test]
+    fn test_payload_size_checks() {
+        assert!(check_payload(&image(4, 2, 32)).is_ok());
+        assert_eq!(
+            check_payload(&image(4, 2, 31)),
+            Err(RejectReason::PayloadSize {
+                expected: 32,
+                actual: 31
+            })
+        );
+
+        let cloud = |num_points, point_stride, len| {
+            SensorPayload::PointCloud(PointCloudData {
+                num_points,
+                point_stride,
+                data: Bytes::from(vec![0u8; len]),
+            })
+        };
+        assert!(check_payload(&cloud(10, 16, 160)).is_ok());
+        assert!(check_payload(&cloud(0, 0, 0)).is_ok());
+        assert_eq!(
+            check_payload(&cloud(10, 16, 150)).unwrap_err().label(),
+            "payload_size"
+        );
+        assert_eq!(
+            check_payload(&cloud(10, 4, 40)),
+            Err(RejectReason::PointStride(4))
+        );
+    }
+
+    #[test]
+    fn test_timestamp_sanity() {
+        let mut validator = PacketValidator::new();
+        assert!(validator.check(&packet(1.0, 1, image(1, 1, 4))).is_ok());
+
+        let backwards = validator.check(&packet(0.5, 2, image(1, 1, 4)));
+        assert_eq!(backwards.unwrap_err().label(), "timestamp_regression");
+        let duplicate = validator.check(&packet(1.1, 1, image(1, 1, 4)));
+        assert_eq!(duplicate, Err(RejectReason::DuplicateFrame(1)));
+        let negative = validator.check(&packet(-0.1, 3, image(1, 1, 4)));
+        assert_eq!(negative.unwrap_err().label(), "negative_timestamp");
+
+        // Rejects do not advance the validator
+        assert!(validator.check(&packet(1.0, 2, image(1, 1, 4))).is_ok());
+    }
+
+    #[test]
+    fn test_duplicates_within_window() {
+        let mut validator = PacketValidator::new();
+        for frame_id in 1..=3 {
+            let t = frame_id as f64;
+            assert!(validator
+                .check(&packet(t, frame_id, image(1, 1, 4)))
+                .is_ok());
+        }
+        // Not just the previous frame_id
+        let repeated = validator.check(&packet(3.5, 1, image(1, 1, 4)));
+        assert_eq!(repeated, Err(RejectReason::DuplicateFrame(1)));
+
+        for frame_id in 4..=(DUPLICATE_WINDOW as u64 + 1) {
+            let t = frame_id as f64;
+            assert!(validator
+                .check(&packet(t, frame_id, image(1, 1, 4)))
+                .is_ok());
+        }
+        // Frame 1 has left the window
+        let late = DUPLICATE_WINDOW as f64 + 2.0;
+        assert!(validator.check(&packet(late, 1, image(1, 1, 4))).is_ok());
+    }
+
+    #[test]
+    fn test_tolerance_admits_reordered_and_duplicates() {
+        let mut validator = PacketValidator::with_tolerance(OrderingTolerance {
+            out_of_order: true,
+            duplicates: true,
+        });
+        assert!(validator.check(&packet(1.0, 2, image(1, 1, 4))).is_ok());
+        assert!(validator.check(&packet(0.9, 1, image(1, 1, 4))).is_ok());
+        assert!(validator.check(&packet(0.9, 1, image(1, 1, 4))).is_ok());
+        assert_eq!(validator.last_timestamp, Some(SimTime::from_secs_f64(1.0)));
+        // Sanity and payload checks still apply
+        assert!(validator.check(&packet(-0.1, 3, image(1, 1, 4))).is_err());
+        assert!(validator.check(&packet(1.1, 3, image(1, 1, 3))).is_err());
+    }
+
+    #[test]
+    fn test_admit_counts_parse_errors() {
+        let metrics = IngestionMetrics::new();
+        let mut validator = PacketValidator::new();
+        let imu = SensorPayload::Imu(contracts::ImuData {
+            accelerometer: contracts::Vector3 {
+                x: f64::NAN,
+                ..Default::default()
+            },
+            gyroscope: Default::default(),
+            compass: 0.0,
+        });
+
+        assert!(!validator.admit(&packet(1.0, 1, imu), &metrics, "imu"));
+        assert!(validator.admit(&packet(1.0, 1, image(2, 2, 16)), &metrics, "imu"));
+        assert_eq!(metrics.snapshot().parse_errors, 1);
+    }
+}
@@ -30,8 +30,9 @@ use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, Env
 
 // Re-exports
 pub use crate::metrics::{
-    record_buffer_depth, record_frame_dispatched, record_packet_received, record_sync_latency_ms,
-    record_sync_metrics, MetricsSummary, RunningStats, StatsSummary, SyncMetricsAggregator,
+    record_buffer_depth, record_frame_dispatched, record_memory_usage, record_packet_received,
+    record_sync_latency_ms, record_sync_metrics, MetricsSummary, RunningStats, StatsSummary,
+    SyncMetricsAggregator,
 };
 
 /// Initialize observability (Tracing + Prometheus)
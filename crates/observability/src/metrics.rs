@@ -2,7 +2,7 @@
 //!
 //! Collects and aggregates sync engine runtime metrics based on SyncMeta.
 
-use contracts::SyncMeta;
+use contracts::{MemoryAccountant, MemoryStage, SyncMeta};
 use metrics::{counter, gauge, histogram};
 
 /// Record metrics from SyncMeta
@@ -125,6 +125,17 @@ pub fn record_buffer_depth(sensor_id: &str, depth: usize) {
     .set(depth as f64);
 }
 
+/// Record payload bytes in flight per pipeline stage
+pub fn record_memory_usage(memory: &MemoryAccountant) {
+    for stage in MemoryStage::ALL {
+        gauge!(
+            "carla_syncer_memory_bytes_in_flight",
+            "stage" => stage.as_str()
+        )
+        .set(memory.in_use(stage) as f64);
+    }
+}
+
 /// Sync metrics aggregator
 ///
 /// Aggregates metrics in memory for statistics and summary output.
@@ -407,6 +418,8 @@ mod tests {
             missing_sensors: vec!["radar".into()],
             dropped_count: 2,
             out_of_order_count: 1,
+            deskew: HashMap::new(),
+            degraded_sensors: Vec::new(),
         };
 
         aggregator.update(&meta);
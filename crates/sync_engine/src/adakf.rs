@@ -5,6 +5,7 @@
 
 use std::collections::VecDeque;
 
+use crate::checkpoint::EstimatorSnapshot;
 use crate::AdaKFConfig;
 
 const MIN_DT: f64 = 1e-3;
@@ -137,6 +138,30 @@ impl AdaKF {
         self.covariance[0][0]
     }
 
+    /// Capture the learned filter state
+    pub fn snapshot(&self) -> EstimatorSnapshot {
+        EstimatorSnapshot {
+            offset: self.state[0],
+            drift: self.state[1],
+            covariance: self.covariance,
+            measurement_noise: self.r,
+            ewma_variance: self.ewma_variance,
+            residuals: self.residual_window.iter().copied().collect(),
+        }
+    }
+
+    /// Restore learned state; noise terms are clamped to this filter's bounds
+    pub fn restore(&mut self, snapshot: &EstimatorSnapshot) {
+        self.state = [snapshot.offset, snapshot.drift];
+        self.covariance = snapshot.covariance;
+        self.ewma_variance = snapshot.ewma_variance.max(0.0);
+        self.r = snapshot
+            .measurement_noise
+            .clamp(self.base_r * 0.1, self.base_r * 10.0);
+        let skip = snapshot.residuals.len().saturating_sub(self.window_size);
+        self.residual_window = snapshot.residuals[skip..].iter().copied().collect();
+    }
+
     fn record_residual(&mut self, residual: f64) {
         self.residual_window.push_back(residual);
         if self.residual_window.len() > self.window_size {
@@ -159,6 +184,103 @@ impl AdaKF {
     }
 }
 
+/// Fixed-interval Rauch-Tung-Striebel smoother over the AdaKF state model.
+///
+/// * `times` - ascending reference timestamps (seconds)
+/// * `observations` - offset observation per step (`None` = no measurement)
+///
+/// Returns the smoothed `[offset, drift]` state for every step.
+pub fn rts_smooth(
+    config: &AdaKFConfig,
+    times: &[f64],
+    observations: &[Option<f64>],
+) -> Vec<[f64; 2]> {
+    let n = times.len().min(observations.len());
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let q_offset = config.process_noise.max(1e-9);
+    let q_drift = (config.process_noise * 0.1).max(1e-9);
+    let r = config.measurement_noise.max(1e-9);
+
+    let mut predicted: Vec<([f64; 2], [[f64; 2]; 2])> = Vec::with_capacity(n);
+    let mut filtered: Vec<([f64; 2], [[f64; 2]; 2])> = Vec::with_capacity(n);
+
+    let mut x = [config.initial_offset, 0.0];
+    let mut p = [[1.0, 0.0], [0.0, 1.0]];
+
+    // ===== Forward Kalman pass =====
+    for k in 0..n {
+        if k > 0 {
+            let dt = (times[k] - times[k - 1]).max(MIN_DT);
+            x = [x[0] + dt * x[1], x[1]];
+            p = [
+                [
+                    p[0][0] + 2.0 * dt * p[0][1] + dt * dt * p[1][1] + q_offset,
+                    p[0][1] + dt * p[1][1],
+                ],
+                [p[0][1] + dt * p[1][1], p[1][1] + q_drift],
+            ];
+        }
+        predicted.push((x, p));
+
+        if let Some(observation) = observations[k] {
+            let residual = observation - x[0];
+            let s = p[0][0] + r;
+            let k0 = p[0][0] / s;
+            let k1 = p[1][0] / s;
+            x = [x[0] + k0 * residual, x[1] + k1 * residual];
+            p = [
+                [(1.0 - k0) * p[0][0], (1.0 - k0) * p[0][1]],
+                [p[1][0] - k1 * p[0][0], p[1][1] - k1 * p[0][1]],
+            ];
+        }
+        filtered.push((x, p));
+    }
+
+    // ===== Backward RTS pass =====
+    let mut smoothed = vec![[0.0; 2]; n];
+    smoothed[n - 1] = filtered[n - 1].0;
+    for k in (0..n - 1).rev() {
+        let dt = (times[k + 1] - times[k]).max(MIN_DT);
+        let (xf, pf) = filtered[k];
+        let (xp, pp) = predicted[k + 1];
+
+        // P_f * F^T
+        let pft = [
+            [pf[0][0] + dt * pf[0][1], pf[0][1]],
+            [pf[1][0] + dt * pf[1][1], pf[1][1]],
+        ];
+        let det = pp[0][0] * pp[1][1] - pp[0][1] * pp[1][0];
+        if det.abs() < f64::EPSILON {
+            smoothed[k] = xf;
+            continue;
+        }
+        let inv = [
+            [pp[1][1] / det, -pp[0][1] / det],
+            [-pp[1][0] / det, pp[0][0] / det],
+        ];
+        let gain = [
+            [
+                pft[0][0] * inv[0][0] + pft[0][1] * inv[1][0],
+                pft[0][0] * inv[0][1] + pft[0][1] * inv[1][1],
+            ],
+            [
+                pft[1][0] * inv[0][0] + pft[1][1] * inv[1][0],
+                pft[1][0] * inv[0][1] + pft[1][1] * inv[1][1],
+            ],
+        ];
+        let d = [smoothed[k + 1][0] - xp[0], smoothed[k + 1][1] - xp[1]];
+        smoothed[k] = [
+            xf[0] + gain[0][0] * d[0] + gain[0][1] * d[1],
+            xf[1] + gain[1][0] * d[0] + gain[1][1] * d[1],
+        ];
+    }
+
+    smoothed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +375,28 @@ mod tests {
             estimated
         );
     }
+
+    #[test]
+    fn test_rts_smooth_fills_gaps_with_drift() {
+        let config = AdaKFConfig::default();
+        let times: Vec<f64> = (0..100).map(|i| i as f64 * 0.05).collect();
+        let observations: Vec<Option<f64>> = times
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (i % 3 != 0).then_some(0.02 + 0.001 * t))
+            .collect();
+
+        let smoothed = rts_smooth(&config, &times, &observations);
+        assert_eq!(smoothed.len(), times.len());
+        for (t, state) in times.iter().zip(&smoothed) {
+            let expected = 0.02 + 0.001 * t;
+            assert!(
+                (state[0] - expected).abs() < 0.002,
+                "t={} expected ~{}, got {}",
+                t,
+                expected,
+                state[0]
+            );
+        }
+    }
 }
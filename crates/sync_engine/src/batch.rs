@@ -0,0 +1,559 @@
+//! Offline batch synchronization.
+//!
+//! Unlike the online [`SyncEngine`](crate::SyncEngine), the batch synchronizer
+//! sees a whole recording up front. Per-sensor clock offsets are estimated
+//! with an RTS smoother over the AdaKF model, then packets are assigned to
+//! reference frames with an order-preserving one-to-one matching that
+//! minimizes the total alignment error. With
+//! [`MissingDataStrategy::Interpolate`], a sensor left without a packet is
+//! filled from its bracketing packets where the payload allows it.
+
+use std::collections::HashMap;
+
+use contracts::{SensorId, SensorPacket, SensorPayload, SimTime, SyncMeta, SyncedFrame};
+use tracing::{debug, info, instrument};
+
+use crate::adakf::rts_smooth;
+use crate::resample::interpolate_packet;
+use crate::window::{compute_motion_intensity, compute_window_size};
+use crate::{MissingDataStrategy, SyncEngineConfig};
+
+/// Result of a batch synchronization run
+#[derive(Debug, Clone, Default)]
+pub struct BatchOutput {
+    /// Synchronized frames ordered by reference time
+    pub frames: Vec<SyncedFrame>,
+    /// Reference frames dropped by the missing-data strategy
+    pub dropped_frames: u64,
+    /// Packets per required sensor that were not assigned to any frame
+    pub unassigned: HashMap<SensorId, usize>,
+}
+
+/// Packets of one required sensor together with their frame assignment
+struct SensorTrack {
+    id: SensorId,
+    packets: Vec<SensorPacket>,
+    offsets: Vec<f64>,
+    assignment: Vec<Option<usize>>,
+}
+
+impl SensorTrack {
+    /// Packet interpolated at `target` from the neighbours within half a
+    /// window on either side, when the payload supports it
+    fn interpolate_at(&self, target: f64, window: f64) -> Option<SensorPacket> {
+        let half = window / 2.0;
+        let split = self
+            .packets
+            .partition_point(|p| p.timestamp.as_secs_f64() <= target);
+        let before = self.packets[..split]
+            .last()
+            .filter(|p| target - p.timestamp.as_secs_f64() <= half)?;
+        let after = self.packets[split..]
+            .first()
+            .filter(|p| p.timestamp.as_secs_f64() - target <= half)?;
+        interpolate_packet(before, after, SimTime::try_from_secs_f64(target)?)
+    }
+}
+
+/// Per-reference-frame window parameters
+#[derive(Debug, Clone, Copy)]
+struct FrameWindow {
+    window: f64,
+    motion_intensity: Option<f64>,
+}
+
+/// Offline synchronizer producing globally optimal frame assignments
+#[derive(Debug, Clone)]
+pub struct BatchSynchronizer {
+    config: SyncEngineConfig,
+}
+
+impl BatchSynchronizer {
+    /// Create a batch synchronizer using the same configuration as the online engine
+    pub fn new(config: SyncEngineConfig) -> Self {
+        Self { config }
+    }
+
+    /// Synchronize a complete packet sequence.
+    ///
+    /// Packets may be supplied in any order; sensors that are neither the
+    /// reference nor required are ignored, as in the online engine.
+    #[instrument(name = "batch_sync", skip_all)]
+    pub fn synchronize(&self, packets: impl IntoIterator<Item = SensorPacket>) -> BatchOutput {
+        let mut streams: HashMap<SensorId, Vec<SensorPacket>> = HashMap::new();
+        for packet in packets {
+            streams
+                .entry(packet.sensor_id.clone())
+                .or_default()
+                .push(packet);
+        }
+        for stream in streams.values_mut() {
+            stream.sort_by_key(|p| p.timestamp);
+        }
+
+        let reference_id = self.config.reference_sensor_id.clone();
+        let Some(reference) = streams.get(&reference_id).cloned() else {
+            return BatchOutput::default();
+        };
+        let ref_times: Vec<f64> = reference
+            .iter()
+            .map(|p| p.timestamp.as_secs_f64())
+            .collect();
+
+        let imu_stream = self
+            .config
+            .imu_sensor_id
+            .as_ref()
+            .and_then(|id| streams.get(id));
+        let windows = self.frame_windows(&ref_times, imu_stream.map(Vec::as_slice));
+
+        let tracks: Vec<SensorTrack> = self
+            .config
+            .required_sensors
+            .iter()
+            .filter(|id| **id != reference_id)
+            .map(|id| {
+                let packets = streams.remove(id).unwrap_or_default();
+                self.build_track(id.clone(), packets, &ref_times, &windows)
+            })
+            .collect();
+
+        let reference_required = self.config.required_sensors.contains(&reference_id);
+        let mut output = BatchOutput::default();
+        let mut frame_counter = 0u64;
+
+        for (i, reference_packet) in reference.into_iter().enumerate() {
+            let t_sync = reference_packet.timestamp;
+            let t_ref = ref_times[i];
+            let capacity = tracks.len() + 1;
+            let mut frames = HashMap::with_capacity(capacity);
+            let mut time_offsets = HashMap::with_capacity(capacity);
+            let mut kf_residuals = HashMap::with_capacity(capacity);
+            let mut missing_sensors = Vec::new();
+
+            if reference_required {
+                frames.insert(reference_id.clone(), reference_packet);
+                time_offsets.insert(reference_id.clone(), 0.0);
+                kf_residuals.insert(reference_id.clone(), 0.0);
+            }
+
+            for track in &tracks {
+                let offset = track.offsets[i];
+                let packet = match track.assignment[i] {
+                    Some(j) => Some(track.packets[j].clone()),
+                    None if self.config.missing_strategy == MissingDataStrategy::Interpolate => {
+                        track.interpolate_at(t_ref + offset, windows[i].window)
+                    }
+                    None => None,
+                };
+                match packet {
+                    Some(packet) => {
+                        time_offsets.insert(track.id.clone(), offset);
+                        kf_residuals.insert(
+                            track.id.clone(),
+                            packet.timestamp.as_secs_f64() - t_ref - offset,
+                        );
+                        frames.insert(track.id.clone(), packet);
+                    }
+                    None => missing_sensors.push(track.id.clone()),
+                }
+            }
+
+            if !missing_sensors.is_empty()
+                && self.config.missing_strategy == MissingDataStrategy::Drop
+            {
+                output.dropped_frames += 1;
+                continue;
+            }
+
+            frame_counter += 1;
+            output.frames.push(SyncedFrame {
+                t_sync,
+                frame_id: frame_counter,
+                vehicle_id: self.config.vehicle_id.clone(),
+                frames,
+                sync_meta: SyncMeta {
+                    reference_sensor_id: reference_id.clone(),
+                    window_size: windows[i].window,
+                    motion_intensity: windows[i].motion_intensity,
+                    time_offsets,
+                    kf_residuals,
+                    missing_sensors,
+                    dropped_count: 0,
+                    out_of_order_count: 0,
+                    deskew: HashMap::new(),
+                    degraded_sensors: Vec::new(),
+                },
+            });
+        }
+
+        for track in &tracks {
+            let assigned = track.assignment.iter().flatten().count();
+            output
+                .unassigned
+                .insert(track.id.clone(), track.packets.len() - assigned);
+        }
+
+        info!(
+            reference_frames = ref_times.len(),
+            frames = output.frames.len(),
+            dropped = output.dropped_frames,
+            "Batch synchronization complete"
+        );
+
+        output
+    }
+
+    fn build_track(
+        &self,
+        id: SensorId,
+        packets: Vec<SensorPacket>,
+        ref_times: &[f64],
+        windows: &[FrameWindow],
+    ) -> SensorTrack {
+        let times: Vec<f64> = packets.iter().map(|p| p.timestamp.as_secs_f64()).collect();
+
+        let mut adakf = self.config.adakf.clone();
+        adakf.expected_interval = self.config.sensor_intervals.get(&id).copied();
+
+        let observations = mutual_nearest_observations(ref_times, &times, adakf.initial_offset);
+        let offsets: Vec<f64> = rts_smooth(&adakf, ref_times, &observations)
+            .into_iter()
+            .map(|state| state[0])
+            .collect();
+
+        let targets: Vec<(f64, f64)> = ref_times
+            .iter()
+            .zip(&offsets)
+            .zip(windows)
+            .map(|((t_ref, offset), window)| (t_ref + offset, window.window / 2.0))
+            .collect();
+        let assignment = assign_one_to_one(&targets, &times);
+
+        debug!(
+            sensor_id = %id,
+            packets = packets.len(),
+            observations = observations.iter().flatten().count(),
+            assigned = assignment.iter().flatten().count(),
+            "Batch track solved"
+        );
+
+        SensorTrack {
+            id,
+            packets,
+            offsets,
+            assignment,
+        }
+    }
+
+    fn frame_windows(&self, ref_times: &[f64], imu: Option<&[SensorPacket]>) -> Vec<FrameWindow> {
+        let imu_times: Vec<f64> = imu
+            .map(|packets| packets.iter().map(|p| p.timestamp.as_secs_f64()).collect())
+            .unwrap_or_default();
+
+        ref_times
+            .iter()
+            .map(|&t| {
+                let motion_intensity =
+                    nearest_index(&imu_times, t).and_then(|idx| match &imu?[idx].payload {
+                        SensorPayload::Imu(data) => Some(compute_motion_intensity(data)),
+                        _ => None,
+                    });
+                FrameWindow {
+                    window: compute_window_size(
+                        motion_intensity.unwrap_or(0.0),
+                        &self.config.window,
+                    ),
+                    motion_intensity,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Index of the value closest to `t` in an ascending slice
+fn nearest_index(sorted: &[f64], t: f64) -> Option<usize> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let idx = sorted.partition_point(|&v| v < t);
+    if idx == 0 {
+        Some(0)
+    } else if idx == sorted.len() {
+        Some(idx - 1)
+    } else if (sorted[idx] - t).abs() < (t - sorted[idx - 1]).abs() {
+        Some(idx)
+    } else {
+        Some(idx - 1)
+    }
+}
+
+/// Offset observations for reference frames whose nearest packet (after the
+/// prior offset) also has that frame as its nearest reference.
+///
+/// Mutual nearest pairs avoid feeding the smoother ambiguous matches when a
+/// sensor runs at a lower rate than the reference.
+fn mutual_nearest_observations(ref_times: &[f64], times: &[f64], prior: f64) -> Vec<Option<f64>> {
+    ref_times
+        .iter()
+        .enumerate()
+        .map(|(i, &t_ref)| {
+            let j = nearest_index(times, t_ref + prior)?;
+            let back = nearest_index(ref_times, times[j] - prior)?;
+            (back == i).then(|| times[j] - t_ref)
+        })
+        .collect()
+}
+
+/// Search node for the assignment dynamic program
+struct AssignNode {
+    /// Index of the last packet used along this path
+    last: Option<usize>,
+    /// Accumulated cost
+    cost: f64,
+    /// Index of the parent node in the previous layer
+    parent: usize,
+    /// Packet chosen for this frame
+    choice: Option<usize>,
+}
+
+/// Order-preserving one-to-one assignment of packets to reference frames.
+///
+/// `targets[i]` is `(target_time, tolerance)`; frame `i` may take packet `j`
+/// when `|times[j] - target_time| <= tolerance`. The total absolute
+/// alignment error is minimized, charging `tolerance` for every frame left
+/// unmatched so that matching is always preferred over skipping.
+fn assign_one_to_one(targets: &[(f64, f64)], times: &[f64]) -> Vec<Option<usize>> {
+    let n = targets.len();
+    let ranges: Vec<(usize, usize)> = targets
+        .iter()
+        .map(|&(target, tol)| {
+            (
+                times.partition_point(|&v| v < target - tol),
+                times.partition_point(|&v| v <= target + tol),
+            )
+        })
+        .collect();
+
+    // Paths whose last packet precedes every remaining candidate are interchangeable
+    let mut floor = vec![usize::MAX; n + 1];
+    for i in (0..n).rev() {
+        floor[i] = floor[i + 1].min(ranges[i].0);
+    }
+
+    let mut layers: Vec<Vec<AssignNode>> = Vec::with_capacity(n + 1);
+    layers.push(vec![AssignNode {
+        last: None,
+        cost: 0.0,
+        parent: 0,
+        choice: None,
+    }]);
+
+    for i in 0..n {
+        let (target, tol) = targets[i];
+        let (lo, hi) = ranges[i];
+        let prev = &layers[i];
+
+        let stale = prev
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.last.is_none_or(|l| l < floor[i]))
+            .min_by(|a, b| a.1.cost.total_cmp(&b.1.cost))
+            .map(|(idx, _)| idx);
+        let live = prev
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.last.is_some_and(|l| l >= floor[i]))
+            .map(|(idx, _)| idx);
+
+        let mut next = Vec::new();
+        for parent in stale.into_iter().chain(live) {
+            let node = &prev[parent];
+            next.push(AssignNode {
+                last: node.last,
+                cost: node.cost + tol,
+                parent,
+                choice: None,
+            });
+            let start = node.last.map_or(lo, |l| lo.max(l + 1));
+            for (j, &t) in times.iter().enumerate().take(hi).skip(start) {
+                next.push(AssignNode {
+                    last: Some(j),
+                    cost: node.cost + (t - target).abs(),
+                    parent,
+                    choice: Some(j),
+                });
+            }
+        }
+
+        // Keep the cheapest path per last-used packet
+        next.sort_by(|a, b| a.last.cmp(&b.last).then(a.cost.total_cmp(&b.cost)));
+        next.dedup_by(|later, earlier| later.last == earlier.last);
+        layers.push(next);
+    }
+
+    let mut assignment = vec![None; n];
+    let Some(mut idx) = layers[n]
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.cost.total_cmp(&b.1.cost))
+        .map(|(idx, _)| idx)
+    else {
+        return assignment;
+    };
+
+    for i in (0..n).rev() {
+        let node = &layers[i + 1][idx];
+        assignment[i] = node.choice;
+        idx = node.parent;
+    }
+
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{RadarData, SensorType, SimTime};
+
+    fn packet(sensor_id: &str, sensor_type: SensorType, timestamp: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Radar(RadarData {
+                num_detections: 0,
+                data: Bytes::new(),
+            }),
+        }
+    }
+
+    fn config(missing_strategy: MissingDataStrategy) -> SyncEngineConfig {
+        SyncEngineConfig {
+            reference_sensor_id: "cam".into(),
+            required_sensors: vec!["cam".into(), "lidar".into()],
+            imu_sensor_id: None,
+            window: Default::default(),
+            buffer: Default::default(),
+            adakf: Default::default(),
+            missing_strategy,
+            sensor_intervals: HashMap::new(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_recovers_constant_offset() {
+        let mut packets = Vec::new();
+        for i in 0..200 {
+            let t = i as f64 * 0.05;
+            packets.push(packet("cam", SensorType::Camera, t));
+            // Lidar clock runs 12ms ahead with small alternating jitter
+            let jitter = if i % 2 == 0 { 0.002 } else { -0.002 };
+            packets.push(packet("lidar", SensorType::Lidar, t + 0.012 + jitter));
+        }
+
+        let output = BatchSynchronizer::new(config(MissingDataStrategy::Drop)).synchronize(packets);
+
+        assert_eq!(output.frames.len(), 200);
+        assert_eq!(output.unassigned[&SensorId::from("lidar")], 0);
+        let frame = &output.frames[100];
+        let offset = frame.sync_meta.time_offsets[&SensorId::from("lidar")];
+        assert!((offset - 0.012).abs() < 0.002, "offset {}", offset);
+    }
+
+    #[test]
+    fn test_batch_assigns_each_packet_once() {
+        let mut packets = Vec::new();
+        for i in 0..40 {
+            let t = i as f64 * 0.05;
+            packets.push(packet("cam", SensorType::Camera, t));
+            if i % 2 == 0 {
+                packets.push(packet("lidar", SensorType::Lidar, t));
+            }
+        }
+
+        let output =
+            BatchSynchronizer::new(config(MissingDataStrategy::Empty)).synchronize(packets);
+
+        assert_eq!(output.frames.len(), 40);
+        let lidar = SensorId::from("lidar");
+        let with_lidar = output
+            .frames
+            .iter()
+            .filter(|f| f.frames.contains_key(&lidar))
+            .count();
+        assert_eq!(with_lidar, 20);
+        assert!(output.frames[1].sync_meta.missing_sensors.contains(&lidar));
+    }
+
+    #[test]
+    fn test_assignment_resolves_conflicts_globally() {
+        // A greedy matcher gives the packet to the first frame; the optimal
+        // assignment leaves frame 0 for the earlier packet.
+        let targets = [(0.00, 0.05), (0.05, 0.05)];
+        let times = [0.02, 0.04];
+        assert_eq!(assign_one_to_one(&targets, &times), vec![Some(0), Some(1)]);
+
+        let times = [0.04];
+        assert_eq!(assign_one_to_one(&targets, &times), vec![None, Some(0)]);
+    }
+
+    #[test]
+    fn test_batch_interpolates_missing_imu() {
+        use contracts::{ImuData, Vector3};
+
+        let imu = |timestamp: f64, accel_x: f64| SensorPacket {
+            sensor_id: "imu".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Imu(ImuData {
+                accelerometer: Vector3 {
+                    x: accel_x,
+                    y: 0.0,
+                    z: 9.8,
+                },
+                gyroscope: Vector3::default(),
+                compass: 0.0,
+            }),
+        };
+        let mut packets = Vec::new();
+        for i in 0..3 {
+            packets.push(packet("cam", SensorType::Camera, i as f64 * 0.05));
+        }
+        // The middle frame has no IMU packet of its own
+        packets.push(imu(0.0, 1.0));
+        packets.push(imu(0.1, 3.0));
+
+        let mut config = config(MissingDataStrategy::Interpolate);
+        config.required_sensors = vec!["cam".into(), "imu".into()];
+        config.window.min_ms = 120.0;
+        config.window.max_ms = 120.0;
+        let output = BatchSynchronizer::new(config.clone()).synchronize(packets.clone());
+
+        assert_eq!(output.frames.len(), 3);
+        let imu_id = SensorId::from("imu");
+        let middle = &output.frames[1];
+        assert!(middle.sync_meta.missing_sensors.is_empty());
+        let filled = &middle.frames[&imu_id];
+        assert_eq!(filled.timestamp, SimTime::from_secs_f64(0.05));
+        let SensorPayload::Imu(data) = &filled.payload else {
+            panic!("expected an IMU payload");
+        };
+        assert!((data.accelerometer.x - 2.0).abs() < 1e-9);
+
+        // Without interpolation the gap stays open
+        config.missing_strategy = MissingDataStrategy::Empty;
+        let output = BatchSynchronizer::new(config).synchronize(packets);
+        assert!(output.frames[1].sync_meta.missing_sensors.contains(&imu_id));
+    }
+}
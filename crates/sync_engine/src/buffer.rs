@@ -5,11 +5,13 @@
 //! - Slab stores actual SensorPacket data
 //!
 //! This avoids moving large payloads during buffer operations.
+//!
+//! Buffered payload bytes are charged to the shared sync-buffer memory
+//! budget; a sensor over budget evicts its own oldest packets.
 
-use std::cmp::Ordering;
 use std::fmt;
 
-use contracts::SensorPacket;
+use contracts::{MemoryAccountant, MemoryStage, SensorPacket, SimTime};
 use ringbuf::{traits::*, HeapRb};
 use slab::Slab;
 
@@ -17,7 +19,7 @@ use slab::Slab;
 #[derive(Debug, Clone, Copy)]
 struct PacketMeta {
     /// Timestamp for ordering
-    timestamp: f64,
+    timestamp: SimTime,
     /// Key into the slab storage
     slab_key: usize,
 }
@@ -35,7 +37,9 @@ pub struct SensorBuffer {
     max_size: usize,
     dropped_count: u64,
     out_of_order_count: u64,
-    last_timestamp: Option<f64>,
+    last_timestamp: Option<SimTime>,
+    /// Payload byte budget shared with other buffers
+    memory: MemoryAccountant,
 }
 
 impl fmt::Debug for SensorBuffer {
@@ -59,15 +63,27 @@ impl SensorBuffer {
             dropped_count: 0,
             out_of_order_count: 0,
             last_timestamp: None,
+            memory: MemoryAccountant::unlimited(),
         }
     }
 
+    /// Charge buffered payload bytes to `memory` instead of the current accountant
+    pub fn set_memory(&mut self, memory: MemoryAccountant) {
+        let held = self.held_bytes();
+        self.memory.release(MemoryStage::SyncBuffer, held);
+        memory.force_reserve(MemoryStage::SyncBuffer, held);
+        self.memory = memory;
+    }
+
     /// Push a packet into the buffer
     ///
-    /// If buffer is full, overwrites the oldest packet.
+    /// If the buffer is full or the memory budget is exhausted, evicts the
+    /// oldest packets. The newest packet is always kept, so a sensor is
+    /// never starved by others holding the shared budget.
     #[inline]
     pub fn push(&mut self, packet: SensorPacket) {
         let timestamp = packet.timestamp;
+        let bytes = packet.payload_bytes();
 
         // Track out-of-order arrivals
         if let Some(last) = self.last_timestamp {
@@ -77,12 +93,15 @@ impl SensorBuffer {
         }
         self.last_timestamp = Some(timestamp);
 
-        // If full, remove oldest entry from both index and storage
-        if self.index.is_full() {
-            if let Some(old_meta) = self.index.try_pop() {
-                self.storage.remove(old_meta.slab_key);
+        // Make room in the byte budget, then in the ring buffer
+        while !self.memory.try_reserve(MemoryStage::SyncBuffer, bytes) {
+            if !self.evict_oldest() {
+                self.memory.force_reserve(MemoryStage::SyncBuffer, bytes);
+                break;
             }
-            self.dropped_count += 1;
+        }
+        if self.index.is_full() {
+            self.evict_oldest();
         }
 
         // Insert packet into slab and metadata into ring buffer
@@ -99,11 +118,7 @@ impl SensorBuffer {
     pub fn peek(&self) -> Option<&SensorPacket> {
         self.index
             .iter()
-            .min_by(|a, b| {
-                a.timestamp
-                    .partial_cmp(&b.timestamp)
-                    .unwrap_or(Ordering::Equal)
-            })
+            .min_by_key(|meta| meta.timestamp)
             .and_then(|meta| self.storage.get(meta.slab_key))
     }
 
@@ -120,11 +135,7 @@ impl SensorBuffer {
             .index
             .iter()
             .enumerate()
-            .min_by(|(_, a), (_, b)| {
-                a.timestamp
-                    .partial_cmp(&b.timestamp)
-                    .unwrap_or(Ordering::Equal)
-            })
+            .min_by_key(|(_, meta)| meta.timestamp)
             .map(|(i, _)| i)?;
 
         // Collect all metadata, remove target, rebuild index
@@ -137,7 +148,22 @@ impl SensorBuffer {
         }
 
         // Remove and return actual packet from storage
-        Some(self.storage.remove(removed_meta.slab_key))
+        Some(take(&mut self.storage, &self.memory, removed_meta.slab_key))
+    }
+
+    /// Drop the oldest inserted packet, returning false if empty
+    fn evict_oldest(&mut self) -> bool {
+        let Some(old_meta) = self.index.try_pop() else {
+            return false;
+        };
+        take(&mut self.storage, &self.memory, old_meta.slab_key);
+        self.dropped_count += 1;
+        true
+    }
+
+    /// Payload bytes currently buffered
+    pub fn held_bytes(&self) -> u64 {
+        self.storage.iter().map(|(_, p)| p.payload_bytes()).sum()
     }
 
     /// Get the number of packets in the buffer
@@ -155,8 +181,8 @@ impl SensorBuffer {
     /// Evict packets older than (now - timeout_s)
     #[inline]
     #[allow(dead_code)]
-    pub fn evict_expired(&mut self, now: f64, timeout_s: f64) -> usize {
-        let cutoff = now - timeout_s;
+    pub fn evict_expired(&mut self, now: SimTime, timeout_s: f64) -> usize {
+        let cutoff = now - SimTime::from_secs_f64(timeout_s);
         let mut evicted = 0;
 
         // Collect metadata, filtering expired entries
@@ -168,7 +194,7 @@ impl SensorBuffer {
                     true
                 } else {
                     // Remove expired packet from storage
-                    self.storage.remove(m.slab_key);
+                    take(&mut self.storage, &self.memory, m.slab_key);
                     evicted += 1;
                     false
                 }
@@ -186,33 +212,70 @@ impl SensorBuffer {
 
     /// Find the closest packet to target timestamp within window
     #[inline]
-    pub fn find_closest_in_window(&self, target: f64, window: f64) -> Option<&SensorPacket> {
-        let half = window / 2.0;
+    pub fn find_closest_in_window(&self, target: SimTime, window: f64) -> Option<&SensorPacket> {
+        let half = SimTime::from_secs_f64(window / 2.0);
         let (min_t, max_t) = (target - half, target + half);
 
         self.index
             .iter()
             .filter(|m| m.timestamp >= min_t && m.timestamp <= max_t)
-            .min_by(|a, b| {
-                let da = (a.timestamp - target).abs();
-                let db = (b.timestamp - target).abs();
-                da.partial_cmp(&db).unwrap_or(Ordering::Equal)
-            })
+            .min_by_key(|m| (m.timestamp - target).abs())
             .and_then(|meta| self.storage.get(meta.slab_key))
     }
 
+    /// Closest packets at or before and at or after `target` within window
+    #[inline]
+    pub fn find_bracketing_in_window(
+        &self,
+        target: SimTime,
+        window: f64,
+    ) -> (Option<&SensorPacket>, Option<&SensorPacket>) {
+        let half = SimTime::from_secs_f64(window / 2.0);
+        let before = self
+            .index
+            .iter()
+            .filter(|m| m.timestamp <= target && m.timestamp >= target - half)
+            .max_by_key(|m| m.timestamp);
+        let after = self
+            .index
+            .iter()
+            .filter(|m| m.timestamp >= target && m.timestamp <= target + half)
+            .min_by_key(|m| m.timestamp);
+
+        (
+            before.and_then(|meta| self.storage.get(meta.slab_key)),
+            after.and_then(|meta| self.storage.get(meta.slab_key)),
+        )
+    }
+
+    /// Newest timestamp currently buffered
+    #[inline]
+    pub fn newest_timestamp(&self) -> Option<SimTime> {
+        self.index.iter().map(|m| m.timestamp).max()
+    }
+
     /// Remove consumed packets up to and including the given timestamp
     #[inline]
-    pub fn remove_consumed(&mut self, up_to_timestamp: f64) {
-        // Collect metadata, removing consumed entries from storage
+    pub fn remove_consumed(&mut self, up_to_timestamp: SimTime) {
+        self.retain(|timestamp| timestamp > up_to_timestamp);
+    }
+
+    /// Remove packets strictly older than the given timestamp
+    #[inline]
+    pub fn remove_before(&mut self, timestamp: SimTime) {
+        self.retain(|t| t >= timestamp);
+    }
+
+    fn retain(&mut self, keep: impl Fn(SimTime) -> bool) {
+        // Collect metadata, removing discarded entries from storage
         let remaining: Vec<PacketMeta> = self
             .index
             .pop_iter()
             .filter(|m| {
-                if m.timestamp > up_to_timestamp {
+                if keep(m.timestamp) {
                     true
                 } else {
-                    self.storage.remove(m.slab_key);
+                    take(&mut self.storage, &self.memory, m.slab_key);
                     false
                 }
             })
@@ -237,6 +300,20 @@ impl SensorBuffer {
     }
 }
 
+impl Drop for SensorBuffer {
+    fn drop(&mut self) {
+        self.memory
+            .release(MemoryStage::SyncBuffer, self.held_bytes());
+    }
+}
+
+/// Remove a packet from storage and return its bytes to the budget
+fn take(storage: &mut Slab<SensorPacket>, memory: &MemoryAccountant, key: usize) -> SensorPacket {
+    let packet = storage.remove(key);
+    memory.release(MemoryStage::SyncBuffer, packet.payload_bytes());
+    packet
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,7 +324,7 @@ mod tests {
         SensorPacket {
             sensor_id: sensor_id.into(),
             sensor_type: SensorType::Camera,
-            timestamp,
+            timestamp: SimTime::from_secs_f64(timestamp),
             frame_id: None,
             payload: SensorPayload::Raw(Bytes::new()),
         }
@@ -262,9 +339,9 @@ mod tests {
         buffer.push(make_packet("cam", 2.0));
 
         // Pop returns earliest by timestamp
-        assert_eq!(buffer.pop().unwrap().timestamp, 1.0);
-        assert_eq!(buffer.pop().unwrap().timestamp, 2.0);
-        assert_eq!(buffer.pop().unwrap().timestamp, 3.0);
+        assert_eq!(buffer.pop().unwrap().timestamp, SimTime::from_secs_f64(1.0));
+        assert_eq!(buffer.pop().unwrap().timestamp, SimTime::from_secs_f64(2.0));
+        assert_eq!(buffer.pop().unwrap().timestamp, SimTime::from_secs_f64(3.0));
     }
 
     #[test]
@@ -288,7 +365,7 @@ mod tests {
         buffer.push(make_packet("cam", 0.5));
         buffer.push(make_packet("cam", 1.5));
 
-        let evicted = buffer.evict_expired(2.0, 1.0);
+        let evicted = buffer.evict_expired(SimTime::from_secs_f64(2.0), 1.0);
         assert_eq!(evicted, 2); // 0.0 and 0.5 expired
         assert_eq!(buffer.len(), 1);
     }
@@ -301,9 +378,9 @@ mod tests {
         buffer.push(make_packet("cam", 1.05));
         buffer.push(make_packet("cam", 1.1));
 
-        let closest = buffer.find_closest_in_window(1.04, 0.1);
+        let closest = buffer.find_closest_in_window(SimTime::from_secs_f64(1.04), 0.1);
         assert!(closest.is_some());
-        assert_eq!(closest.unwrap().timestamp, 1.05);
+        assert_eq!(closest.unwrap().timestamp, SimTime::from_secs_f64(1.05));
     }
 
     #[test]
@@ -316,4 +393,76 @@ mod tests {
 
         assert_eq!(buffer.out_of_order_count(), 1);
     }
+
+    #[test]
+    fn test_bracketing_and_remove_before() {
+        let mut buffer = SensorBuffer::new(10, 10.0);
+        for t in [1.0, 1.1, 1.2, 1.3] {
+            buffer.push(make_packet("imu", t));
+        }
+
+        let (before, after) = buffer.find_bracketing_in_window(SimTime::from_secs_f64(1.15), 0.2);
+        assert_eq!(before.unwrap().timestamp, SimTime::from_secs_f64(1.1));
+        assert_eq!(after.unwrap().timestamp, SimTime::from_secs_f64(1.2));
+        assert_eq!(buffer.newest_timestamp(), Some(SimTime::from_secs_f64(1.3)));
+
+        buffer.remove_before(SimTime::from_secs_f64(1.2));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(
+            buffer.peek().unwrap().timestamp,
+            SimTime::from_secs_f64(1.2)
+        );
+    }
+
+    #[test]
+    fn test_window_edge_exact_after_long_run() {
+        let mut buffer = SensorBuffer::new(10, 10.0);
+        let period = SimTime::from_secs_f64(0.05);
+        let mut target = SimTime::ZERO;
+        for _ in 0..72_000 {
+            target += period;
+        }
+
+        // Packet exactly on the window edge one hour in
+        buffer.push(make_packet("lidar", 3600.01));
+        let found = buffer.find_closest_in_window(target, 0.02);
+        assert_eq!(found.unwrap().timestamp, SimTime::from_secs_f64(3600.01));
+    }
+
+    #[test]
+    fn test_memory_budget_evicts_oldest() {
+        let memory = MemoryAccountant::new(&contracts::MemoryConfig {
+            sync_buffer: Some(contracts::StageBudget {
+                limit_mb: 1,
+                on_exceed: contracts::ExceedPolicy::Drop,
+                block_timeout_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let image = |timestamp| SensorPacket {
+            payload: SensorPayload::Raw(Bytes::from(vec![0u8; 300 * 1024])),
+            ..make_packet("cam", timestamp)
+        };
+
+        let mut cam = SensorBuffer::new(10, 10.0);
+        cam.set_memory(memory.clone());
+        for t in 0..5 {
+            cam.push(image(t as f64));
+        }
+        // Only three 300 KiB payloads fit in 1 MiB
+        assert_eq!(cam.len(), 3);
+        assert_eq!(cam.dropped_count(), 2);
+        assert_eq!(cam.peek().unwrap().timestamp, SimTime::from_secs_f64(2.0));
+
+        // Another sensor keeps its newest packet even when the budget is full
+        let mut lidar = SensorBuffer::new(10, 10.0);
+        lidar.set_memory(memory.clone());
+        lidar.push(image(5.0));
+        assert_eq!(lidar.len(), 1);
+        assert_eq!(memory.in_use(MemoryStage::SyncBuffer), 4 * 300 * 1024);
+
+        cam.remove_consumed(SimTime::from_secs_f64(3.0));
+        drop(lidar);
+        assert_eq!(memory.in_use(MemoryStage::SyncBuffer), 300 * 1024);
+    }
 }
@@ -0,0 +1,168 @@
+//! Learned sync state snapshots for warm starts.
+//!
+//! A checkpoint holds each engine's per-sensor AdaKF state and the adaptive
+//! quality threshold so a later session on the same rig starts converged.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use contracts::SensorId;
+use serde::{Deserialize, Serialize};
+
+use crate::error::SyncEngineError;
+
+/// Current checkpoint file format version
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Snapshot of one AdaKF estimator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EstimatorSnapshot {
+    /// Offset estimate (seconds)
+    pub offset: f64,
+    /// Drift estimate (seconds per second)
+    pub drift: f64,
+    /// State covariance
+    pub covariance: [[f64; 2]; 2],
+    /// Adapted measurement noise
+    pub measurement_noise: f64,
+    /// EWMA of residual variance
+    pub ewma_variance: f64,
+    /// Recent residuals, oldest first
+    #[serde(default)]
+    pub residuals: Vec<f64>,
+}
+
+/// Learned state of one sync engine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineCheckpoint {
+    /// Vehicle the engine synchronizes (None = ungrouped pipeline)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vehicle_id: Option<String>,
+    /// Reference sensor the offsets were learned against
+    pub reference_sensor_id: SensorId,
+    /// Adaptive quality threshold multiplier
+    pub quality_multiplier: f64,
+    /// Running frame accept rate
+    pub accept_rate: f64,
+    /// Estimator state per sensor
+    pub sensors: HashMap<SensorId, EstimatorSnapshot>,
+}
+
+/// Outcome of restoring a checkpoint into an engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreSummary {
+    /// Sensors whose estimator state was restored
+    pub restored: usize,
+    /// Checkpoint sensors skipped because the current config does not
+    /// know them
+    pub ignored: usize,
+}
+
+/// On-disk checkpoint covering every sync group of a pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointFile {
+    /// Format version
+    pub version: u32,
+    /// One entry per sync engine
+    pub engines: Vec<EngineCheckpoint>,
+}
+
+impl CheckpointFile {
+    /// Create a checkpoint at the current format version
+    pub fn new(engines: Vec<EngineCheckpoint>) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            engines,
+        }
+    }
+
+    /// Load a checkpoint from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SyncEngineError> {
+        let content = fs::read_to_string(path)?;
+        let file: Self = serde_json::from_str(&content)?;
+        if file.version != CHECKPOINT_VERSION {
+            return Err(SyncEngineError::CheckpointVersion {
+                found: file.version,
+                expected: CHECKPOINT_VERSION,
+            });
+        }
+        Ok(file)
+    }
+
+    /// Write the checkpoint as JSON, replacing any previous file atomically
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SyncEngineError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Engine state for a vehicle (None = ungrouped pipeline)
+    pub fn find(&self, vehicle_id: Option<&str>) -> Option<&EngineCheckpoint> {
+        self.engines
+            .iter()
+            .find(|engine| engine.vehicle_id.as_deref() == vehicle_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(vehicle_id: Option<&str>) -> EngineCheckpoint {
+        EngineCheckpoint {
+            vehicle_id: vehicle_id.map(str::to_string),
+            reference_sensor_id: "cam".into(),
+            quality_multiplier: 0.8,
+            accept_rate: 0.93,
+            sensors: HashMap::from([(
+                SensorId::from("lidar"),
+                EstimatorSnapshot {
+                    offset: 0.012,
+                    drift: 1e-5,
+                    covariance: [[1e-6, 0.0], [0.0, 1e-8]],
+                    measurement_noise: 8e-4,
+                    ewma_variance: 7e-4,
+                    residuals: vec![0.001, -0.002],
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("sync_checkpoint_{}", std::process::id()));
+        let path = dir.join("state.json");
+        let file = CheckpointFile::new(vec![engine(Some("ego")), engine(None)]);
+
+        file.save(&path).unwrap();
+        let loaded = CheckpointFile::load(&path).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(loaded, file);
+        assert_eq!(loaded.find(Some("ego")), Some(&file.engines[0]));
+        assert_eq!(loaded.find(None), Some(&file.engines[1]));
+        assert!(loaded.find(Some("peer")).is_none());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let path = std::env::temp_dir().join(format!(
+            "sync_checkpoint_version_{}.json",
+            std::process::id()
+        ));
+        fs::write(&path, r#"{"version": 99, "engines": []}"#).unwrap();
+        let result = CheckpointFile::load(&path);
+        fs::remove_file(&path).ok();
+
+        assert!(matches!(
+            result,
+            Err(SyncEngineError::CheckpointVersion { found: 99, .. })
+        ));
+    }
+}
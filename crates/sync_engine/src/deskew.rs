@@ -0,0 +1,407 @@
+//! LiDAR motion compensation (deskew) post-stage.
+//!
+//! Each point gets a capture time from its azimuth within the sweep, and is
+//! then moved into the sensor frame at `t_sync` using the IMU angular
+//! velocity and, when available, a GNSS-derived ego speed. Both are rotated
+//! into the LiDAR frame through the configured sensor mounts.
+
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+
+use contracts::{
+    DeskewConfig, DeskewCorrection, GnssData, Rotation, SensorId, SensorPacket, SensorPayload,
+    SensorType, SimTime, Vector3,
+};
+use nalgebra as na;
+
+/// IMU/GNSS history kept for deskewing (seconds)
+const HISTORY_S: f64 = 2.0;
+/// Maximum distance of the nearest IMU sample when none falls in the sweep
+const MAX_IMU_GAP_S: f64 = 0.5;
+/// Maximum GNSS fix age relative to the sweep end
+const MAX_GNSS_AGE_S: f64 = 1.0;
+/// Points used to detect the rotation direction of the sweep
+const DIRECTION_PROBE_POINTS: usize = 64;
+const EARTH_RADIUS_M: f64 = 6_378_137.0;
+
+/// Deskews LiDAR sweeps using buffered IMU and GNSS history
+#[derive(Debug)]
+pub struct Deskewer {
+    config: DeskewConfig,
+    imu_sensor_id: Option<SensorId>,
+    gyro_history: VecDeque<(SimTime, Vector3)>,
+    gnss_history: VecDeque<(SimTime, GnssData)>,
+}
+
+impl Deskewer {
+    /// Create a deskewer reading angular velocity from `imu_sensor_id`
+    pub fn new(config: DeskewConfig, imu_sensor_id: Option<SensorId>) -> Self {
+        Self {
+            config,
+            imu_sensor_id,
+            gyro_history: VecDeque::new(),
+            gnss_history: VecDeque::new(),
+        }
+    }
+
+    /// Record IMU and GNSS packets needed for later corrections
+    pub fn observe(&mut self, packet: &SensorPacket) {
+        match &packet.payload {
+            SensorPayload::Imu(imu) if self.imu_sensor_id.as_ref() == Some(&packet.sensor_id) => {
+                push_history(&mut self.gyro_history, packet.timestamp, imu.gyroscope);
+            }
+            SensorPayload::Gnss(gnss)
+                if self.config.gnss_sensor_id.as_ref() == Some(&packet.sensor_id) =>
+            {
+                push_history(&mut self.gnss_history, packet.timestamp, *gnss);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the packet is a LiDAR sweep selected for deskewing
+    pub fn applies_to(&self, packet: &SensorPacket) -> bool {
+        packet.sensor_type == SensorType::Lidar
+            && matches!(packet.payload, SensorPayload::PointCloud(_))
+            && (self.config.sensors.is_empty() || self.config.sensors.contains(&packet.sensor_id))
+    }
+
+    /// Configured sweep duration, if any
+    pub fn sweep_duration(&self) -> Option<f64> {
+        self.config.sweep_duration_s
+    }
+
+    /// Deskew a sweep ending at `packet.timestamp` to `t_sync` in place.
+    ///
+    /// Returns `None` when the payload has no usable xyz layout.
+    pub fn deskew(
+        &self,
+        packet: &mut SensorPacket,
+        t_sync: SimTime,
+        sweep_duration_s: f64,
+    ) -> Option<DeskewCorrection> {
+        let t_end = packet.timestamp;
+        let t_start = t_end - SimTime::from_secs_f64(sweep_duration_s);
+        let SensorPayload::PointCloud(cloud) = &mut packet.payload else {
+            return None;
+        };
+
+        let stride = cloud.point_stride as usize;
+        let num_points = cloud.num_points as usize;
+        if stride < 12 || cloud.data.len() < stride * num_points {
+            return None;
+        }
+
+        // Vehicle-frame quantities expressed in the LiDAR frame
+        let to_lidar = self.mount(&packet.sensor_id).transpose();
+        let imu_to_lidar = match &self.imu_sensor_id {
+            Some(imu) => to_lidar * self.mount(imu),
+            None => to_lidar,
+        };
+        let omega = imu_to_lidar * from_contract(self.mean_angular_velocity(t_start, t_end));
+        let velocity = to_lidar * na::Vector3::new(self.ego_speed(t_end), 0.0, 0.0);
+
+        let probe: Vec<_> = cloud
+            .xyz()
+            .take(DIRECTION_PROBE_POINTS)
+            .map(to_vector)
+            .collect();
+        let direction = sweep_direction(&probe);
+        let start_azimuth = probe.first().map(|point| azimuth(*point));
+        let start_to_sync = (t_start - t_sync).as_secs_f64();
+        let mut max_displacement_m = 0.0_f64;
+
+        cloud.map_xyz(|_, xyz| {
+            let point = to_vector(xyz);
+            let fraction = start_azimuth
+                .map(|start| (direction * (azimuth(point) - start)).rem_euclid(TAU) / TAU)
+                .unwrap_or(0.0);
+            let dt = start_to_sync + fraction * sweep_duration_s;
+
+            let corrected = na::Rotation3::from_scaled_axis(omega * dt) * point + velocity * dt;
+            max_displacement_m = max_displacement_m.max((corrected - point).norm());
+            corrected.map(|v| v as f32).into()
+        });
+
+        Some(DeskewCorrection {
+            sweep_duration_s,
+            angular_velocity: to_contract(omega),
+            linear_velocity: to_contract(velocity),
+            max_displacement_m,
+        })
+    }
+
+    /// Mean gyro reading over the sweep, falling back to the nearest sample
+    fn mean_angular_velocity(&self, t_start: SimTime, t_end: SimTime) -> Vector3 {
+        let (sum, count) = self
+            .gyro_history
+            .iter()
+            .filter(|(t, _)| *t >= t_start && *t <= t_end)
+            .fold(((0.0, 0.0, 0.0), 0usize), |((x, y, z), n), (_, g)| {
+                ((x + g.x, y + g.y, z + g.z), n + 1)
+            });
+        if count > 0 {
+            let n = count as f64;
+            return Vector3 {
+                x: sum.0 / n,
+                y: sum.1 / n,
+                z: sum.2 / n,
+            };
+        }
+
+        let mid = SimTime::from_nanos(t_start.as_nanos() + (t_end - t_start).as_nanos() / 2);
+        let max_gap = SimTime::from_secs_f64(MAX_IMU_GAP_S);
+        self.gyro_history
+            .iter()
+            .filter(|(t, _)| (*t - mid).abs() <= max_gap)
+            .min_by_key(|(t, _)| (*t - mid).abs())
+            .map(|(_, g)| *g)
+            .unwrap_or_default()
+    }
+
+    /// Mounting rotation of a sensor, identity when not configured
+    fn mount(&self, sensor_id: &SensorId) -> na::Matrix3<f64> {
+        self.config
+            .mounts
+            .get(sensor_id)
+            .map(mount_matrix)
+            .unwrap_or_else(na::Matrix3::identity)
+    }
+
+    /// Ego speed from the two latest GNSS fixes (m/s)
+    fn ego_speed(&self, t_end: SimTime) -> f64 {
+        let max_age = SimTime::from_secs_f64(MAX_GNSS_AGE_S);
+        let mut fixes = self
+            .gnss_history
+            .iter()
+            .rev()
+            .filter(|(t, _)| *t <= t_end + max_age);
+        let (Some((t1, b)), Some((t0, a))) = (fixes.next(), fixes.next()) else {
+            return 0.0;
+        };
+        let dt = (*t1 - *t0).as_secs_f64();
+        if dt <= 0.0 || t_end - *t1 > max_age {
+            return 0.0;
+        }
+
+        let north = (b.latitude - a.latitude).to_radians() * EARTH_RADIUS_M;
+        let east = (b.longitude - a.longitude).to_radians()
+            * EARTH_RADIUS_M
+            * a.latitude.to_radians().cos();
+        north.hypot(east) / dt
+    }
+}
+
+fn push_history<T>(history: &mut VecDeque<(SimTime, T)>, timestamp: SimTime, value: T) {
+    history.push_back((timestamp, value));
+    let Some(newest) = history.iter().map(|(t, _)| *t).max() else {
+        return;
+    };
+    let cutoff = newest - SimTime::from_secs_f64(HISTORY_S);
+    while history.front().is_some_and(|(t, _)| *t < cutoff) {
+        history.pop_front();
+    }
+}
+
+/// Sensor-to-vehicle rotation whose columns are the sensor's forward, right
+/// and up axes (CARLA pitch/yaw/roll in degrees)
+fn mount_matrix(rotation: &Rotation) -> na::Matrix3<f64> {
+    let (sp, cp) = rotation.pitch.to_radians().sin_cos();
+    let (sy, cy) = rotation.yaw.to_radians().sin_cos();
+    let (sr, cr) = rotation.roll.to_radians().sin_cos();
+    na::Matrix3::from_columns(&[
+        na::Vector3::new(cp * cy, cp * sy, sp),
+        na::Vector3::new(cy * sp * sr - sy * cr, sy * sp * sr + cy * cr, -cp * sr),
+        na::Vector3::new(-cy * sp * cr - sy * sr, -sy * sp * cr + cy * sr, cp * cr),
+    ])
+}
+
+fn from_contract(v: Vector3) -> na::Vector3<f64> {
+    na::Vector3::new(v.x, v.y, v.z)
+}
+
+fn to_contract(v: na::Vector3<f64>) -> Vector3 {
+    Vector3 {
+        x: v.x,
+        y: v.y,
+        z: v.z,
+    }
+}
+
+fn to_vector([x, y, z]: [f32; 3]) -> na::Vector3<f64> {
+    na::Vector3::new(x as f64, y as f64, z as f64)
+}
+
+fn azimuth(point: na::Vector3<f64>) -> f64 {
+    point.y.atan2(point.x)
+}
+
+/// Sign of the azimuth progression over the first points of the sweep
+fn sweep_direction(probe: &[na::Vector3<f64>]) -> f64 {
+    let total: f64 = probe
+        .windows(2)
+        .map(|pair| {
+            let (prev, next) = (azimuth(pair[0]), azimuth(pair[1]));
+            (next - prev + TAU / 2.0).rem_euclid(TAU) - TAU / 2.0
+        })
+        .sum();
+    if total < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{ImuData, LidarPoint, PointCloudData};
+
+    fn sweep(points: &[[f32; 3]], timestamp: f64) -> SensorPacket {
+        let points: Vec<_> = points
+            .iter()
+            .map(|&[x, y, z]| LidarPoint {
+                x,
+                y,
+                z,
+                intensity: 1.0,
+            })
+            .collect();
+        SensorPacket {
+            sensor_id: "lidar".into(),
+            sensor_type: SensorType::Lidar,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::PointCloud(PointCloudData::from_points(&points)),
+        }
+    }
+
+    fn points(packet: &SensorPacket) -> Vec<na::Vector3<f64>> {
+        let SensorPayload::PointCloud(cloud) = &packet.payload else {
+            panic!("expected point cloud");
+        };
+        cloud.xyz().map(to_vector).collect()
+    }
+
+    fn gnss(timestamp: f64, latitude: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "gnss".into(),
+            sensor_type: SensorType::Gnss,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Gnss(GnssData {
+                latitude,
+                longitude: 0.0,
+                altitude: 0.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_translation_from_gnss_speed() {
+        let config = DeskewConfig {
+            gnss_sensor_id: Some("gnss".into()),
+            ..Default::default()
+        };
+        let mut deskewer = Deskewer::new(config, None);
+        // 10 m north in one second
+        let metres_per_degree = EARTH_RADIUS_M * 1f64.to_radians();
+        deskewer.observe(&gnss(0.0, 0.0));
+        deskewer.observe(&gnss(1.0, 10.0 / metres_per_degree));
+
+        let mut packet = sweep(
+            &[[10.0, 0.0, 0.0], [0.0, 10.0, 0.0], [-10.0, 0.0, 0.0]],
+            1.0,
+        );
+        let correction = deskewer
+            .deskew(&mut packet, SimTime::from_secs_f64(1.0), 0.1)
+            .unwrap();
+
+        let corrected = points(&packet);
+        // First point was captured at sweep start, 0.1 s before t_sync
+        assert!((corrected[0].x - 9.0).abs() < 1e-3);
+        assert!((corrected[1].x + 0.75).abs() < 1e-3);
+        assert!((correction.linear_velocity.x - 10.0).abs() < 1e-6);
+        assert!((correction.max_displacement_m - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rotation_from_imu() {
+        let mut deskewer = Deskewer::new(DeskewConfig::default(), Some("imu".into()));
+        deskewer.observe(&SensorPacket {
+            sensor_id: "imu".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(0.95),
+            frame_id: None,
+            payload: SensorPayload::Imu(ImuData {
+                accelerometer: Vector3::default(),
+                gyroscope: Vector3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 1.0,
+                },
+                compass: 0.0,
+            }),
+        });
+
+        let mut packet = sweep(&[[10.0, 0.0, 0.0]], 1.0);
+        let correction = deskewer
+            .deskew(&mut packet, SimTime::from_secs_f64(1.0), 0.1)
+            .unwrap();
+
+        let corrected = points(&packet)[0];
+        assert!((corrected.x - 10.0 * 0.1f64.cos()).abs() < 1e-4);
+        assert!((corrected.y + 10.0 * 0.1f64.sin()).abs() < 1e-4);
+        assert_eq!(correction.angular_velocity.z, 1.0);
+    }
+
+    #[test]
+    fn test_velocities_rotated_into_lidar_mount() {
+        let mount = |yaw, roll| Rotation {
+            pitch: 0.0,
+            yaw,
+            roll,
+        };
+        let config = DeskewConfig {
+            gnss_sensor_id: Some("gnss".into()),
+            mounts: [
+                ("lidar".into(), mount(90.0, 0.0)),
+                ("imu".into(), mount(0.0, 90.0)),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let mut deskewer = Deskewer::new(config, Some("imu".into()));
+        let metres_per_degree = EARTH_RADIUS_M * 1f64.to_radians();
+        deskewer.observe(&gnss(0.0, 0.0));
+        deskewer.observe(&gnss(1.0, 10.0 / metres_per_degree));
+        // Rolled IMU: its y axis points along the vehicle's down axis
+        deskewer.observe(&SensorPacket {
+            sensor_id: "imu".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(0.95),
+            frame_id: None,
+            payload: SensorPayload::Imu(ImuData {
+                accelerometer: Vector3::default(),
+                gyroscope: Vector3 {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 0.0,
+                },
+                compass: 0.0,
+            }),
+        });
+
+        let mut packet = sweep(&[[10.0, 0.0, 0.0]], 1.0);
+        let correction = deskewer
+            .deskew(&mut packet, SimTime::from_secs_f64(1.0), 0.1)
+            .unwrap();
+
+        // Vehicle forward is the -y axis of a LiDAR yawed by 90 degrees
+        let velocity = correction.linear_velocity;
+        assert!(velocity.x.abs() < 1e-6 && (velocity.y + 10.0).abs() < 1e-6);
+        let omega = correction.angular_velocity;
+        assert!(omega.x.abs() < 1e-9 && omega.y.abs() < 1e-9);
+        assert!((omega.z + 1.0).abs() < 1e-9);
+    }
+}
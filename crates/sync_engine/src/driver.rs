@@ -0,0 +1,295 @@
+//! Tokio driver that combines packet ingestion with latency deadlines.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use contracts::{DeadlineClock, SensorPacket, SimTime, SyncedFrame};
+use tokio::sync::mpsc;
+use tokio::time::{Interval, MissedTickBehavior};
+
+use crate::SyncEngine;
+
+const DEFAULT_TICK: Duration = Duration::from_millis(50);
+const MIN_TICK: Duration = Duration::from_millis(1);
+
+/// How the driver estimates the current sim time between packets
+#[derive(Clone, Default)]
+pub enum SimClock {
+    /// Newest packet timestamp only; deadlines advance with incoming data.
+    /// Use when sim time does not follow the wall clock (synchronous
+    /// simulation, as-fast-as-possible replay).
+    #[default]
+    Packets,
+    /// Newest packet timestamp plus the wall time since it arrived, scaled
+    /// by `rate` sim seconds per wall second
+    WallClock { rate: f64 },
+    /// Current sim time read from an external clock, e.g. a replay transport
+    External(Arc<dyn Fn() -> Option<SimTime> + Send + Sync>),
+}
+
+impl From<DeadlineClock> for SimClock {
+    fn from(clock: DeadlineClock) -> Self {
+        match clock {
+            DeadlineClock::Packets => Self::Packets,
+            DeadlineClock::WallClock { rate } => Self::WallClock { rate },
+        }
+    }
+}
+
+impl fmt::Debug for SimClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Packets => f.write_str("Packets"),
+            Self::WallClock { rate } => f.debug_struct("WallClock").field("rate", rate).finish(),
+            Self::External(_) => f.write_str("External"),
+        }
+    }
+}
+
+/// Wraps a [`SyncEngine`] and polls it periodically so that frames keep
+/// flowing when a required sensor stops delivering data.
+///
+/// Between packets the current sim time comes from the [`SimClock`]; it never
+/// falls behind the newest packet timestamp.
+#[derive(Debug)]
+pub struct DeadlineDriver {
+    engine: SyncEngine,
+    tick: Duration,
+    clock: SimClock,
+    last_arrival: Option<Instant>,
+}
+
+impl DeadlineDriver {
+    /// Create a driver, deriving the poll interval from the latency budget
+    /// and the sim clock from the engine's deadline clock
+    pub fn new(engine: SyncEngine) -> Self {
+        let tick = engine
+            .latency_budget()
+            .map(|budget| Duration::from_secs_f64((budget / 4.0).max(0.0)))
+            .map(|tick| tick.clamp(MIN_TICK, DEFAULT_TICK))
+            .unwrap_or(DEFAULT_TICK);
+        let clock = engine.deadline_clock().into();
+        Self {
+            engine,
+            tick,
+            clock,
+            last_arrival: None,
+        }
+    }
+
+    /// Override the poll interval
+    pub fn with_tick(mut self, tick: Duration) -> Self {
+        self.tick = tick.max(MIN_TICK);
+        self
+    }
+
+    /// Override how sim time is estimated between packets
+    pub fn with_clock(mut self, clock: SimClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Poll interval used by [`DeadlineDriver::ticker`]
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+
+    /// Create an interval timer suitable for `tokio::select!` loops
+    pub fn ticker(&self) -> Interval {
+        let mut ticker = tokio::time::interval(self.tick);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ticker
+    }
+
+    /// Access the wrapped engine
+    pub fn engine(&self) -> &SyncEngine {
+        &self.engine
+    }
+
+    /// Consume the driver and return the wrapped engine
+    pub fn into_engine(self) -> SyncEngine {
+        self.engine
+    }
+
+    /// Push a packet into the engine
+    pub fn push(&mut self, packet: SensorPacket) -> Option<SyncedFrame> {
+        self.last_arrival = Some(Instant::now());
+        self.engine.push(packet)
+    }
+
+    /// Estimated current sim time
+    pub fn sim_time_estimate(&self) -> Option<SimTime> {
+        let newest = self.engine.newest_timestamp()?;
+        let now = match &self.clock {
+            SimClock::Packets => newest,
+            SimClock::WallClock { rate } => {
+                let elapsed = self
+                    .last_arrival
+                    .map_or(0.0, |arrival| arrival.elapsed().as_secs_f64());
+                newest + SimTime::from_secs_f64(elapsed * rate.max(0.0))
+            }
+            SimClock::External(now) => now().map_or(newest, |now| now.max(newest)),
+        };
+        Some(now)
+    }
+
+    /// Poll the engine at the estimated sim time
+    pub fn poll(&mut self) -> Option<SyncedFrame> {
+        let now = self.sim_time_estimate()?;
+        self.engine.poll(now)
+    }
+
+    /// Run until the packet channel closes or the frame receiver is dropped.
+    ///
+    /// Returns the engine so callers can inspect or persist its state.
+    pub async fn run(
+        mut self,
+        mut rx: mpsc::Receiver<SensorPacket>,
+        tx: mpsc::Sender<SyncedFrame>,
+    ) -> SyncEngine {
+        let mut ticker = self.ticker();
+        'run: loop {
+            let frame = tokio::select! {
+                packet = rx.recv() => match packet {
+                    Some(packet) => self.push(packet),
+                    None => break,
+                },
+                _ = ticker.tick() => self.poll(),
+            };
+
+            // A fixed-rate grid may have completed several ticks
+            let frames: Vec<SyncedFrame> =
+                frame.into_iter().chain(self.engine.drain_ready()).collect();
+            for frame in frames {
+                if tx.send(frame).await.is_err() {
+                    break 'run;
+                }
+            }
+        }
+        self.engine
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{ImageData, ImageFormat, MissingDataStrategy, SensorPayload, SensorType};
+
+    fn camera_packet(timestamp: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "cam".into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Image(ImageData {
+                width: 1,
+                height: 1,
+                format: ImageFormat::Rgb8,
+                data: Bytes::from_static(&[0, 0, 0]),
+            }),
+        }
+    }
+
+    fn stalled_config(budget: f64) -> crate::SyncEngineConfig {
+        crate::SyncEngineConfig {
+            reference_sensor_id: "cam".into(),
+            required_sensors: vec!["cam".into(), "lidar".into()],
+            imu_sensor_id: None,
+            window: Default::default(),
+            buffer: Default::default(),
+            adakf: Default::default(),
+            missing_strategy: MissingDataStrategy::Empty,
+            sensor_intervals: Default::default(),
+            latency_budget_s: Some(budget),
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
+        }
+    }
+
+    #[test]
+    fn test_tick_derived_from_budget() {
+        let driver = DeadlineDriver::new(SyncEngine::new(stalled_config(0.02)));
+        assert_eq!(driver.tick(), Duration::from_millis(5));
+
+        let driver = DeadlineDriver::new(SyncEngine::new(stalled_config(10.0)));
+        assert_eq!(driver.tick(), DEFAULT_TICK);
+    }
+
+    #[test]
+    fn test_sim_clock_sources() {
+        let mut driver = DeadlineDriver::new(SyncEngine::new(stalled_config(0.02)))
+            .with_clock(SimClock::Packets);
+        driver.push(camera_packet(1.0));
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(
+            driver.sim_time_estimate(),
+            Some(SimTime::from_secs_f64(1.0))
+        );
+        assert!(driver.poll().is_none(), "sim time did not advance");
+
+        let mut driver = driver.with_clock(SimClock::External(Arc::new(|| {
+            Some(SimTime::from_secs_f64(1.5))
+        })));
+        assert_eq!(
+            driver.sim_time_estimate(),
+            Some(SimTime::from_secs_f64(1.5))
+        );
+        assert!(driver.poll().is_some(), "external clock expires the budget");
+
+        let driver = driver.with_clock(SimClock::WallClock { rate: 100.0 });
+        assert!(driver.sim_time_estimate().unwrap() >= SimTime::from_secs_f64(2.0));
+    }
+
+    #[tokio::test]
+    async fn test_packet_clock_ignores_wall_time() {
+        // Synchronous simulation: a slow tick must not expire the budget
+        let driver = DeadlineDriver::new(SyncEngine::new(stalled_config(0.02)));
+        let (packet_tx, packet_rx) = mpsc::channel(8);
+        let (frame_tx, mut frame_rx) = mpsc::channel(8);
+        let handle = tokio::spawn(driver.run(packet_rx, frame_tx));
+
+        packet_tx.send(camera_packet(1.0)).await.unwrap();
+        let waited = tokio::time::timeout(Duration::from_millis(200), frame_rx.recv()).await;
+        assert!(waited.is_err(), "no partial frame while sim time is still");
+
+        // Sim time moves past the budget with the next packet
+        packet_tx.send(camera_packet(1.1)).await.unwrap();
+        let frame = tokio::time::timeout(Duration::from_secs(1), frame_rx.recv())
+            .await
+            .expect("deadline should fire")
+            .expect("frame expected");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(1.0));
+
+        drop(packet_tx);
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_emits_when_sensor_stalls() {
+        let mut config = stalled_config(0.02);
+        config.deadline_clock = DeadlineClock::WallClock { rate: 1.0 };
+        let driver = DeadlineDriver::new(SyncEngine::new(config));
+        let (packet_tx, packet_rx) = mpsc::channel(8);
+        let (frame_tx, mut frame_rx) = mpsc::channel(8);
+        let handle = tokio::spawn(driver.run(packet_rx, frame_tx));
+
+        packet_tx.send(camera_packet(1.0)).await.unwrap();
+
+        let frame = tokio::time::timeout(Duration::from_secs(1), frame_rx.recv())
+            .await
+            .expect("deadline should fire")
+            .expect("frame expected");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(1.0));
+        assert_eq!(frame.sync_meta.missing_sensors.len(), 1);
+
+        drop(packet_tx);
+        let engine = handle.await.unwrap();
+        assert_eq!(engine.frame_count(), 1);
+    }
+}
@@ -4,14 +4,22 @@
 use std::collections::HashMap;
 
 use contracts::{
-    ImuData, SensorId, SensorPacket, SensorPayload, SensorType, SyncMeta, SyncedFrame,
+    DeadlineClock, DeskewCorrection, ImuData, MemoryAccountant, OutputClock, SensorId,
+    SensorPacket, SensorPayload, SensorType, SimTime, SyncMeta, SyncedFrame,
 };
 use tracing::instrument;
 
 use crate::adakf::AdaKF;
 use crate::buffer::SensorBuffer;
-use crate::window::{compute_motion_intensity, compute_window_size, fuse_motion_pressure};
-use crate::{MissingDataStrategy, SyncEngineConfig};
+use crate::checkpoint::{EngineCheckpoint, RestoreSummary};
+use crate::deskew::Deskewer;
+use crate::error::SyncEngineError;
+use crate::resample::interpolate_packet;
+use crate::window::{
+    compute_motion_intensity, compute_window_size, fuse_motion_pressure, jitter_half_width,
+    JitterStats,
+};
+use crate::{MissingDataStrategy, SyncEngineConfig, WindowPolicy};
 
 const DEFAULT_SENSOR_INTERVAL: f64 = 0.05;
 const MIN_WINDOW_FLOOR_S: f64 = 0.005;
@@ -87,7 +95,7 @@ impl FrameSelection {
 
 #[derive(Debug, Clone, Copy)]
 struct SyncContext {
-    reference_time: f64,
+    reference_time: SimTime,
     window: f64,
     fused_intensity: f64,
     min_window_s: f64,
@@ -103,11 +111,19 @@ struct SensorState {
     /// Kalman filter estimator
     estimator: AdaKF,
     /// Last estimator update time
-    last_update_time: f64,
+    last_update_time: Option<SimTime>,
     /// Last emitted timestamp (for jitter tracking)
-    last_emit_time: f64,
+    last_emit_time: Option<SimTime>,
     /// Expected interval between packets
     expected_interval: f64,
+    /// Newest packet timestamp received from this sensor
+    last_seen: Option<SimTime>,
+    /// Silent for longer than the liveness limit
+    degraded: bool,
+    /// Inter-arrival deviation from the expected period
+    arrival_jitter: JitterStats,
+    /// Absolute AdaKF residuals
+    residual_jitter: JitterStats,
 }
 
 impl SensorState {
@@ -124,9 +140,13 @@ impl SensorState {
             id,
             buffer: SensorBuffer::new(buffer_size, timeout_s),
             estimator: AdaKF::new(&kf_config),
-            last_update_time: 0.0,
-            last_emit_time: 0.0,
+            last_update_time: None,
+            last_emit_time: None,
             expected_interval,
+            last_seen: None,
+            degraded: false,
+            arrival_jitter: JitterStats::default(),
+            residual_jitter: JitterStats::default(),
         }
     }
 }
@@ -149,11 +169,23 @@ pub struct SyncEngine {
     /// Current motion intensity
     motion_intensity: f64,
     /// Last synced timestamp for jitter calculation
-    last_sync_time: Option<f64>,
+    last_sync_time: Option<SimTime>,
     /// Adaptive quality threshold multiplier (1.0 = use base threshold)
     quality_multiplier: f64,
     /// Running accept rate for adaptive threshold
     accept_rate: f64,
+    /// Newest packet timestamp observed (sim time)
+    newest_timestamp: Option<SimTime>,
+    /// First packet timestamp, the liveness baseline for silent sensors
+    first_timestamp: Option<SimTime>,
+    /// Grid period in nanoseconds when running on a fixed-rate output clock
+    grid_period_ns: Option<i64>,
+    /// Next grid tick index when running on a fixed-rate output clock
+    grid_tick: Option<i64>,
+    /// LiDAR deskew post-stage (None = disabled)
+    deskewer: Option<Deskewer>,
+    /// Payload byte budget shared by all sensor buffers
+    memory: MemoryAccountant,
 }
 
 impl SyncEngine {
@@ -198,6 +230,13 @@ impl SyncEngine {
             ));
         }
 
+        let deskewer = config
+            .deskew
+            .clone()
+            .map(|deskew| Deskewer::new(deskew, config.imu_sensor_id.clone()));
+
+        let grid_period_ns = config.output_clock.period_ns();
+
         Self {
             config,
             sensors,
@@ -209,7 +248,22 @@ impl SyncEngine {
             last_sync_time: None,
             quality_multiplier: 1.0,
             accept_rate: 1.0,
+            newest_timestamp: None,
+            first_timestamp: None,
+            grid_period_ns,
+            grid_tick: None,
+            deskewer,
+            memory: MemoryAccountant::unlimited(),
+        }
+    }
+
+    /// Charge buffered payload bytes to a shared memory accountant
+    pub fn with_memory(mut self, memory: MemoryAccountant) -> Self {
+        for sensor in &mut self.sensors {
+            sensor.buffer.set_memory(memory.clone());
         }
+        self.memory = memory;
+        self
     }
 
     /// Find sensor state by id (linear search, fast for small N)
@@ -238,36 +292,201 @@ impl SyncEngine {
             &self.config.adakf,
             expected_interval,
         ));
-        self.sensors.len() - 1
+        let idx = self.sensors.len() - 1;
+        self.sensors[idx].buffer.set_memory(self.memory.clone());
+        idx
     }
 
     /// Push a packet into the sync engine
     ///
     /// Returns `Some(SyncedFrame)` if a synchronized frame can be produced.
+    /// On a fixed-rate clock, follow with [`SyncEngine::drain_ready`] to
+    /// collect further completed ticks.
     #[instrument(
         level = "trace",
         name = "sync_engine_push",
         skip(self, packet),
-        fields(sensor_id = %packet.sensor_id, timestamp = packet.timestamp)
+        fields(sensor_id = %packet.sensor_id, timestamp = %packet.timestamp)
     )]
     pub fn push(&mut self, packet: SensorPacket) -> Option<SyncedFrame> {
         let sensor_id = packet.sensor_id.clone();
+        let timestamp = packet.timestamp;
 
         self.update_motion_from_packet(&sensor_id, &packet);
+        if let Some(deskewer) = &mut self.deskewer {
+            deskewer.observe(&packet);
+        }
 
         let idx = self.find_or_create_sensor(&sensor_id);
         self.sensors[idx].buffer.push(packet);
+        let sensor = &mut self.sensors[idx];
+        if let Some(last_seen) = sensor.last_seen {
+            sensor.arrival_jitter.record_interval(
+                (timestamp - last_seen).as_secs_f64(),
+                sensor.expected_interval,
+            );
+        }
+        sensor.last_seen = Some(sensor.last_seen.map_or(timestamp, |t| t.max(timestamp)));
+        if sensor.degraded {
+            self.readmit_sensor(idx);
+        }
+        let newest = self
+            .newest_timestamp
+            .map_or(timestamp, |t| t.max(timestamp));
+        self.newest_timestamp = Some(newest);
+        self.first_timestamp.get_or_insert(timestamp);
+        self.update_liveness(newest);
+
+        if let (None, Some(period_ns)) = (self.grid_tick, self.grid_period_ns) {
+            // Start the grid at the first tick not earlier than the first packet
+            self.grid_tick = Some(-(-timestamp.as_nanos()).div_euclid(period_ns));
+        }
 
         self.update_state();
 
-        self.try_sync()
+        // Event-driven unless a latency budget lets newer data expire the
+        // pending reference
+        match self.try_sync() {
+            Some(frame) => Some(frame),
+            None if self.config.latency_budget_s.is_some() => self.expire_deadline(newest),
+            None => None,
+        }
+    }
+
+    /// Emit every further grid tick completed by the data already buffered.
+    ///
+    /// On a fixed-rate clock faster than a sensor, one packet can complete
+    /// several ticks; `push` returns the first and the rest are drained
+    /// here. Always empty on the reference clock.
+    pub fn drain_ready(&mut self) -> Vec<SyncedFrame> {
+        let mut frames = Vec::new();
+        if self.grid_period_ns.is_none() {
+            return frames;
+        }
+        // Each attempt advances the grid, whether the tick is emitted or
+        // dropped; never run ahead of the data
+        while self.state == SyncState::Ready && self.reference_timestamp() <= self.newest_timestamp
+        {
+            frames.extend(self.try_sync());
+        }
+        frames
+    }
+
+    /// Drive time-based emission at sim time `now`.
+    ///
+    /// When a latency budget is configured and the pending reference packet
+    /// is older than the budget, a frame is produced even though some
+    /// required sensors have no data. Missing slots are then handled by the
+    /// configured `MissingDataStrategy`.
+    #[instrument(name = "sync_engine_poll", level = "trace", skip(self))]
+    pub fn poll(&mut self, now: SimTime) -> Option<SyncedFrame> {
+        if self.update_liveness(now) {
+            self.update_state();
+        }
+
+        match self.state {
+            SyncState::Ready => self.try_sync(),
+            SyncState::Idle => None,
+            SyncState::Buffering => self.expire_deadline(now),
+        }
+    }
+
+    /// Emit the pending frame while still buffering if its reference is
+    /// older than the latency budget at `now`
+    fn expire_deadline(&mut self, now: SimTime) -> Option<SyncedFrame> {
+        if self.state != SyncState::Buffering {
+            return None;
+        }
+        let budget = self.config.latency_budget_s?;
+        let t_ref = self.reference_timestamp()?;
+        if (now - t_ref).as_secs_f64() < budget {
+            return None;
+        }
+
+        tracing::debug!(
+            t_ref = %t_ref,
+            now = %now,
+            budget,
+            "latency budget expired, emitting partial frame"
+        );
+        metrics::counter!("sync_deadline_expired_total").increment(1);
+
+        let context = self.prepare_sync_context()?;
+        self.log_sync_attempt(context);
+        self.perform_sync(context)
+    }
+
+    /// Degrade required sensors silent for longer than the liveness limit.
+    ///
+    /// Returns true if any sensor changed state.
+    fn update_liveness(&mut self, now: SimTime) -> bool {
+        let (Some(liveness), Some(baseline)) = (self.config.liveness, self.first_timestamp) else {
+            return false;
+        };
+
+        let mut changed = false;
+        for idx in 0..self.sensors.len() {
+            let sensor = &self.sensors[idx];
+            if idx == self.reference_idx
+                || sensor.degraded
+                || !self.config.required_sensors.contains(&sensor.id)
+            {
+                continue;
+            }
+            let silent_for = (now - sensor.last_seen.unwrap_or(baseline)).as_secs_f64();
+            if silent_for > liveness.missed_periods as f64 * sensor.expected_interval {
+                self.degrade_sensor(idx, silent_for);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn degrade_sensor(&mut self, idx: usize, silent_for: f64) {
+        let sensor = &mut self.sensors[idx];
+        sensor.degraded = true;
+        tracing::warn!(
+            sensor_id = %sensor.id,
+            silent_for,
+            "required sensor degraded"
+        );
+        metrics::counter!("sync_sensor_degraded_total", "sensor_id" => sensor.id.to_string())
+            .increment(1);
+        metrics::gauge!("sync_sensor_degraded", "sensor_id" => sensor.id.to_string()).set(1.0);
+    }
+
+    fn readmit_sensor(&mut self, idx: usize) {
+        let sensor = &mut self.sensors[idx];
+        sensor.degraded = false;
+        tracing::info!(sensor_id = %sensor.id, "degraded sensor re-admitted");
+        metrics::counter!("sync_sensor_readmitted_total", "sensor_id" => sensor.id.to_string())
+            .increment(1);
+        metrics::gauge!("sync_sensor_degraded", "sensor_id" => sensor.id.to_string()).set(0.0);
+    }
+
+    /// Whether a sensor is degraded and no longer waited for
+    fn is_dropped(&self, idx: usize) -> bool {
+        self.sensors[idx].degraded
+            && self
+                .config
+                .liveness
+                .is_some_and(|liveness| liveness.drop_degraded)
+    }
+
+    /// Sensors currently degraded
+    pub fn degraded_sensors(&self) -> Vec<SensorId> {
+        self.sensors
+            .iter()
+            .filter(|sensor| sensor.degraded)
+            .map(|sensor| sensor.id.clone())
+            .collect()
     }
 
     /// Update internal state based on buffer contents
     fn update_state(&mut self) {
         if self.all_buffers_empty() {
             self.state = SyncState::Idle;
-        } else if self.all_required_sensors_have_data() {
+        } else if self.required_sensors_ready() {
             self.state = SyncState::Ready;
         } else {
             self.state = SyncState::Buffering;
@@ -279,11 +498,34 @@ impl SyncEngine {
         self.sensors.iter().all(|s| s.buffer.is_empty())
     }
 
-    /// Check if all required sensors have at least one packet
-    fn all_required_sensors_have_data(&self) -> bool {
+    /// Check if all required sensors can fill their slot.
+    ///
+    /// On the reference clock every required sensor needs at least one
+    /// packet. On a fixed-rate clock each sensor must also have delivered a
+    /// packet at or past the pending tick, so the nearest packet is known.
+    fn required_sensors_ready(&self) -> bool {
+        let tick = match self.config.output_clock {
+            OutputClock::Reference => None,
+            OutputClock::FixedRate { .. } => match self.reference_timestamp() {
+                Some(tick) => Some(tick),
+                None => return false,
+            },
+        };
+
         self.config.required_sensors.iter().all(|id| {
             self.find_sensor(id)
-                .map(|idx| !self.sensors[idx].buffer.is_empty())
+                .map(|idx| {
+                    let sensor = &self.sensors[idx];
+                    if self.is_dropped(idx) {
+                        return true;
+                    }
+                    match tick {
+                        Some(tick) => sensor.buffer.newest_timestamp().is_some_and(|newest| {
+                            newest >= tick + SimTime::from_secs_f64(sensor.estimator.offset())
+                        }),
+                        None => !sensor.buffer.is_empty(),
+                    }
+                })
                 .unwrap_or(false)
         })
     }
@@ -336,10 +578,42 @@ impl SyncEngine {
         capped.max(MIN_WINDOW_FLOOR_S)
     }
 
-    fn estimator_dt(&mut self, idx: usize, t_ref: f64) -> f64 {
+    /// Effective window policy (`Auto` resolved by IMU availability)
+    fn window_policy(&self) -> WindowPolicy {
+        match self.config.window.policy {
+            WindowPolicy::Auto if self.config.imu_sensor_id.is_some() => WindowPolicy::Motion,
+            WindowPolicy::Auto => WindowPolicy::Jitter,
+            policy => policy,
+        }
+    }
+
+    /// Window covering the target percentile of every required sensor's
+    /// timing errors, None until enough samples have been learned
+    fn jitter_window(&self) -> Option<f64> {
+        let p = self.config.window.target_percentile;
+        let half_width = self
+            .config
+            .required_sensors
+            .iter()
+            .filter_map(|id| self.find_sensor(id))
+            .filter(|&idx| !self.is_dropped(idx))
+            .filter_map(|idx| {
+                let sensor = &self.sensors[idx];
+                jitter_half_width(&sensor.arrival_jitter, &sensor.residual_jitter, p)
+            })
+            .reduce(f64::max)?;
+
+        let window = &self.config.window;
+        Some((2.0 * half_width).clamp(window.min_ms / 1000.0, window.max_ms / 1000.0))
+    }
+
+    fn estimator_dt(&mut self, idx: usize, t_ref: SimTime) -> f64 {
         let sensor = &mut self.sensors[idx];
-        let dt = (t_ref - sensor.last_update_time).abs();
-        sensor.last_update_time = t_ref;
+        let last = sensor
+            .last_update_time
+            .replace(t_ref)
+            .unwrap_or(SimTime::ZERO);
+        let dt = (t_ref - last).abs().as_secs_f64();
         if dt > 0.0 {
             dt
         } else {
@@ -361,47 +635,40 @@ impl SyncEngine {
         let time_term = (-((time_delta.abs() / sigma_t).powi(2))).exp();
         let residual_term = (-((residual.abs() / sigma_r).powi(2))).exp();
         let load_term = 1.0 - 0.5 * load_index.clamp(0.0, 1.0);
-        let sensor_bias = match packet.sensor_type {
-            SensorType::Camera => 1.0,
-            SensorType::Lidar => 0.9,
-            SensorType::Imu => 0.8,
-            _ => 0.95,
-        };
+        let sensor_bias = self
+            .config
+            .quality
+            .bias(&packet.sensor_id, packet.sensor_type);
         (time_term * residual_term * load_term * sensor_bias).clamp(0.0, 1.0)
     }
 
-    /// Get quality threshold for a sensor type
-    /// Uses base threshold with adaptive multiplier (targets 95% accept rate)
-    fn quality_threshold(&self, sensor_type: SensorType) -> f64 {
-        let base = match sensor_type {
-            SensorType::Camera => 0.05,
-            SensorType::Lidar => 0.04,
-            SensorType::Imu => 0.02,
-            _ => 0.03,
-        };
+    /// Get quality threshold for a sensor
+    /// Uses configured base threshold with adaptive multiplier
+    fn quality_threshold(&self, sensor_id: &str, sensor_type: SensorType) -> f64 {
+        let base = self.config.quality.threshold(sensor_id, sensor_type);
         // Apply adaptive multiplier (lower multiplier = lower threshold = more accepting)
         (base * self.quality_multiplier).clamp(0.001, 1.0)
     }
 
     /// Update adaptive quality threshold based on accept/reject outcome
-    /// Targets fixed 95% accept rate with EMA smoothing
+    /// Targets the configured accept rate with EMA smoothing
     fn update_adaptive_threshold(&mut self, accepted: usize, total: usize) {
         if total == 0 {
             return;
         }
 
-        const TARGET_ACCEPT_RATE: f64 = 0.95;
         const SMOOTHING: f64 = 0.98;
+        let target_accept_rate = self.config.quality.target_accept_rate;
 
         let current_rate = accepted as f64 / total as f64;
 
         // Exponential moving average of accept rate
         self.accept_rate = SMOOTHING * self.accept_rate + (1.0 - SMOOTHING) * current_rate;
 
-        // Adjust multiplier based on accept rate vs 95% target
-        let adjustment = if self.accept_rate < TARGET_ACCEPT_RATE - 0.05 {
+        // Adjust multiplier based on accept rate vs target
+        let adjustment = if self.accept_rate < target_accept_rate - 0.05 {
             0.995 // Lower threshold gradually
-        } else if self.accept_rate > TARGET_ACCEPT_RATE + 0.02 {
+        } else if self.accept_rate > target_accept_rate + 0.02 {
             1.002 // Raise threshold gradually
         } else {
             1.0 // In acceptable range
@@ -414,9 +681,16 @@ impl SyncEngine {
         for (sensor_id, packet) in frames {
             if let Some(idx) = self.find_sensor(sensor_id) {
                 let sensor = &mut self.sensors[idx];
-                let interval = (packet.timestamp - sensor.last_emit_time).abs();
-                let budget = Self::sensor_jitter_budget(packet.sensor_type);
-                if interval > budget && sensor.last_emit_time > 0.0 {
+                let last_emit = sensor.last_emit_time.replace(packet.timestamp);
+                let Some(last_emit) = last_emit.filter(|&t| t > SimTime::ZERO) else {
+                    continue;
+                };
+                let interval = (packet.timestamp - last_emit).abs().as_secs_f64();
+                let budget = self
+                    .config
+                    .quality
+                    .jitter_budget(sensor_id, packet.sensor_type);
+                if interval > budget {
                     tracing::warn!(
                         sensor_id = %sensor_id,
                         jitter = interval,
@@ -429,21 +703,10 @@ impl SyncEngine {
                     )
                     .increment(1);
                 }
-                sensor.last_emit_time = packet.timestamp;
             }
         }
     }
 
-    fn sensor_jitter_budget(sensor_type: SensorType) -> f64 {
-        match sensor_type {
-            SensorType::Camera => 0.265,
-            SensorType::Lidar => 0.4,
-            SensorType::Imu => 0.12,
-            SensorType::Gnss => 0.5,
-            SensorType::Radar => 0.3,
-        }
-    }
-
     /// Try to produce a synchronized frame
     #[instrument(name = "sync_engine_try_sync", skip(self))]
     fn try_sync(&mut self) -> Option<SyncedFrame> {
@@ -462,7 +725,11 @@ impl SyncEngine {
         let fused_intensity =
             fuse_motion_pressure(self.motion_intensity, self.average_buffer_pressure());
         let min_window_s = self.derived_min_window_seconds();
-        let window = compute_window_size(fused_intensity, &self.config.window);
+        let window = match self.window_policy() {
+            WindowPolicy::Jitter => self.jitter_window(),
+            _ => None,
+        }
+        .unwrap_or_else(|| compute_window_size(fused_intensity, &self.config.window));
         Some(SyncContext {
             reference_time,
             window,
@@ -476,7 +743,7 @@ impl SyncEngine {
         level = "debug",
         skip(self),
         fields(
-            t_ref = context.reference_time,
+            t_ref = %context.reference_time,
             window = context.window,
             motion_intensity = context.fused_intensity
         )
@@ -491,7 +758,7 @@ impl SyncEngine {
             self.collect_frames(context.reference_time, context.window, context.min_window_s);
 
         if self.should_drop_for_missing(&selection.missing_sensors) {
-            self.evict_consumed(context.reference_time);
+            self.release_consumed(context);
             return None;
         }
 
@@ -499,7 +766,7 @@ impl SyncEngine {
         self.frame_counter += 1;
 
         // Convert to HashMaps for metrics and output
-        let (frames, time_offsets, kf_residuals, quality_scores, missing_sensors) =
+        let (mut frames, time_offsets, kf_residuals, quality_scores, missing_sensors) =
             selection.into_hashmaps();
 
         self.record_frame_metrics(
@@ -510,6 +777,7 @@ impl SyncEngine {
         );
 
         self.check_sensor_jitter(&frames);
+        let deskew = self.deskew_frames(&mut frames, context.reference_time);
 
         let sync_meta = SyncMeta {
             reference_sensor_id: self.config.reference_sensor_id.clone(),
@@ -520,21 +788,80 @@ impl SyncEngine {
             missing_sensors,
             dropped_count,
             out_of_order_count,
+            deskew,
+            degraded_sensors: self.degraded_sensors(),
         };
 
-        self.evict_consumed(context.reference_time);
+        self.release_consumed(context);
 
         Some(SyncedFrame {
             t_sync: context.reference_time,
             frame_id: self.frame_counter,
+            vehicle_id: self.config.vehicle_id.clone(),
             frames,
             sync_meta,
         })
     }
 
+    /// Apply the LiDAR deskew post-stage to the selected sweeps
+    fn deskew_frames(
+        &self,
+        frames: &mut HashMap<SensorId, SensorPacket>,
+        t_sync: SimTime,
+    ) -> HashMap<SensorId, DeskewCorrection> {
+        let Some(deskewer) = &self.deskewer else {
+            return HashMap::new();
+        };
+
+        let mut corrections = HashMap::new();
+        for (sensor_id, packet) in frames.iter_mut() {
+            if !deskewer.applies_to(packet) {
+                continue;
+            }
+            let sweep_duration = deskewer
+                .sweep_duration()
+                .unwrap_or_else(|| self.sensor_expected_interval(sensor_id));
+            if let Some(correction) = deskewer.deskew(packet, t_sync, sweep_duration) {
+                metrics::histogram!(
+                    "sync_deskew_displacement",
+                    "sensor_id" => sensor_id.to_string()
+                )
+                .record(correction.max_displacement_m);
+                corrections.insert(sensor_id.clone(), correction);
+            }
+        }
+        corrections
+    }
+
+    /// Release buffered packets once a frame has been emitted or dropped
+    fn release_consumed(&mut self, context: SyncContext) {
+        match self.grid_period_ns {
+            Some(period_ns) => self.advance_grid(period_ns, context.window),
+            None => self.evict_consumed(context.reference_time),
+        }
+    }
+
+    /// Move to the next grid tick, keeping packets still needed to
+    /// interpolate around it
+    #[instrument(name = "sync_engine_advance_grid", skip(self))]
+    fn advance_grid(&mut self, period_ns: i64, window: f64) {
+        let Some(tick) = self.grid_tick.as_mut() else {
+            return;
+        };
+        *tick += 1;
+        let t_next = SimTime::from_nanos(*tick * period_ns);
+
+        for sensor in &mut self.sensors {
+            let keep_from =
+                t_next + SimTime::from_secs_f64(sensor.estimator.offset() - window / 2.0);
+            sensor.buffer.remove_before(keep_from);
+        }
+        self.update_state();
+    }
+
     /// Evict frames that have been consumed
     #[instrument(name = "sync_engine_evict_consumed", skip(self))]
-    fn evict_consumed(&mut self, up_to: f64) {
+    fn evict_consumed(&mut self, up_to: SimTime) {
         for sensor in &mut self.sensors {
             sensor.buffer.remove_consumed(up_to);
         }
@@ -546,8 +873,8 @@ impl SyncEngine {
     pub fn buffer_stats(&self) -> crate::BufferStats {
         let mut depths = HashMap::new();
         let mut total = 0;
-        let mut oldest: Option<f64> = None;
-        let mut newest: Option<f64> = None;
+        let mut oldest: Option<SimTime> = None;
+        let mut newest: Option<SimTime> = None;
 
         for sensor in &self.sensors {
             let len = sensor.buffer.len();
@@ -573,18 +900,97 @@ impl SyncEngine {
 
     /// Get current sync latency estimate (time from oldest buffered to now)
     #[instrument(name = "sync_engine_estimated_latency", skip(self))]
-    pub fn estimated_latency(&self, current_time: f64) -> f64 {
+    pub fn estimated_latency(&self, current_time: SimTime) -> f64 {
         self.buffer_stats()
             .oldest_timestamp
-            .map(|oldest| current_time - oldest)
+            .map(|oldest| (current_time - oldest).as_secs_f64())
             .unwrap_or(0.0)
     }
 
+    /// Configured latency budget in seconds (None = event-driven only)
+    pub fn latency_budget(&self) -> Option<f64> {
+        self.config.latency_budget_s
+    }
+
+    /// Configured sim time estimate between packets for the budget
+    pub fn deadline_clock(&self) -> DeadlineClock {
+        self.config.deadline_clock
+    }
+
+    /// Newest packet timestamp pushed so far
+    pub fn newest_timestamp(&self) -> Option<SimTime> {
+        self.newest_timestamp
+    }
+
     /// Get frame counter
     pub fn frame_count(&self) -> u64 {
         self.frame_counter
     }
 
+    /// Snapshot the learned estimator and quality state
+    pub fn checkpoint(&self) -> EngineCheckpoint {
+        EngineCheckpoint {
+            vehicle_id: self.config.vehicle_id.clone(),
+            reference_sensor_id: self.config.reference_sensor_id.clone(),
+            quality_multiplier: self.quality_multiplier,
+            accept_rate: self.accept_rate,
+            sensors: self
+                .sensors
+                .iter()
+                .map(|sensor| (sensor.id.clone(), sensor.estimator.snapshot()))
+                .collect(),
+        }
+    }
+
+    /// Warm-start from a checkpoint.
+    ///
+    /// Offsets are relative to the reference sensor, so a checkpoint learned
+    /// against a different reference is rejected. Sensors the current
+    /// config does not name are skipped, so a stale checkpoint cannot add
+    /// phantom sensors to liveness and required-set tracking.
+    pub fn restore(
+        &mut self,
+        checkpoint: &EngineCheckpoint,
+    ) -> Result<RestoreSummary, SyncEngineError> {
+        if checkpoint.reference_sensor_id != self.config.reference_sensor_id {
+            return Err(SyncEngineError::reference_mismatch(
+                self.config.reference_sensor_id.as_ref(),
+                checkpoint.reference_sensor_id.as_ref(),
+            ));
+        }
+
+        let mut summary = RestoreSummary {
+            restored: 0,
+            ignored: 0,
+        };
+        for (sensor_id, snapshot) in &checkpoint.sensors {
+            if !self.is_configured_sensor(sensor_id) {
+                tracing::warn!(%sensor_id, "checkpoint sensor not in config, ignored");
+                summary.ignored += 1;
+                continue;
+            }
+            let idx = self.find_or_create_sensor(sensor_id);
+            self.sensors[idx].estimator.restore(snapshot);
+            summary.restored += 1;
+        }
+        self.quality_multiplier = checkpoint.quality_multiplier.clamp(0.1, 2.0);
+        self.accept_rate = checkpoint.accept_rate.clamp(0.0, 1.0);
+
+        Ok(summary)
+    }
+
+    /// Whether the config names the sensor or it already has state
+    fn is_configured_sensor(&self, sensor_id: &SensorId) -> bool {
+        self.find_sensor(sensor_id).is_some()
+            || self.config.imu_sensor_id.as_ref() == Some(sensor_id)
+            || self.config.sensor_intervals.contains_key(sensor_id)
+            || self
+                .config
+                .deskew
+                .as_ref()
+                .is_some_and(|deskew| deskew.gnss_sensor_id.as_ref() == Some(sensor_id))
+    }
+
     /// Get current motion intensity
     pub fn motion_intensity(&self) -> f64 {
         fuse_motion_pressure(self.motion_intensity, self.average_buffer_pressure())
@@ -599,20 +1005,40 @@ impl SyncEngine {
         }
     }
 
-    fn reference_timestamp(&self) -> Option<f64> {
+    /// Timestamp of the pending frame: the oldest reference packet, or the
+    /// pending grid tick on a fixed-rate clock
+    fn reference_timestamp(&self) -> Option<SimTime> {
+        if let Some(period_ns) = self.grid_period_ns {
+            return self
+                .grid_tick
+                .map(|tick| SimTime::from_nanos(tick * period_ns));
+        }
         self.sensors
             .get(self.reference_idx)
             .and_then(|s| s.buffer.peek().map(|packet| packet.timestamp))
     }
 
+    /// Interpolate a packet for `sensor` at `t_target` from its bracketing
+    /// neighbours, when the payload supports it
+    fn resample_at(&self, idx: usize, t_target: SimTime, window: f64) -> Option<SensorPacket> {
+        match self.sensors[idx]
+            .buffer
+            .find_bracketing_in_window(t_target, window)
+        {
+            (Some(before), Some(after)) => interpolate_packet(before, after, t_target),
+            _ => None,
+        }
+    }
+
     #[instrument(
         name = "sync_engine_collect_frames",
         level = "trace",
         skip(self),
-        fields(t_ref = t_ref, window = window)
+        fields(t_ref = %t_ref, window = window)
     )]
-    fn collect_frames(&mut self, t_ref: f64, window: f64, min_window_s: f64) -> FrameSelection {
+    fn collect_frames(&mut self, t_ref: SimTime, window: f64, min_window_s: f64) -> FrameSelection {
         let num_required = self.config.required_sensors.len();
+        let mut dropped = 0;
         let mut selection = FrameSelection::with_capacity(num_required);
 
         // Collect sensor indices for required sensors
@@ -631,9 +1057,13 @@ impl SyncEngine {
                     continue;
                 }
             };
+            if self.is_dropped(idx) {
+                dropped += 1;
+                continue;
+            }
 
             let offset = self.sensors[idx].estimator.offset();
-            let t_target = t_ref + offset;
+            let t_target = t_ref + SimTime::from_secs_f64(offset);
 
             let packet_opt = self.sensors[idx]
                 .buffer
@@ -648,12 +1078,13 @@ impl SyncEngine {
                 }
             };
 
-            let time_delta = packet.timestamp - t_target;
+            let time_delta = (packet.timestamp - t_target).as_secs_f64();
             let load_index = self.buffer_pressure(&self.sensors[idx].buffer);
             let dt = self.estimator_dt(idx, t_ref);
             let (time_offset, kf_residual) = self.sensors[idx]
                 .estimator
                 .update(time_delta, dt, load_index);
+            self.sensors[idx].residual_jitter.record(kf_residual);
 
             let quality_score = self.compute_quality_score(
                 &packet,
@@ -663,11 +1094,18 @@ impl SyncEngine {
                 min_window_s,
                 load_index,
             );
-            if quality_score < self.quality_threshold(packet.sensor_type) {
+            if quality_score < self.quality_threshold(&sensor_id, packet.sensor_type) {
                 selection.missing_sensors.push(sensor_id);
                 continue;
             }
 
+            let packet = match self.config.output_clock {
+                OutputClock::FixedRate { .. } => {
+                    self.resample_at(idx, t_target, window).unwrap_or(packet)
+                }
+                OutputClock::Reference => packet,
+            };
+
             // Aggregate all sensor data in one struct
             selection.selected.push(SelectedSensor {
                 sensor_id,
@@ -679,7 +1117,7 @@ impl SyncEngine {
         }
 
         // Update adaptive threshold based on this frame's outcomes
-        let total = num_required;
+        let total = num_required - dropped;
         let accepted = selection.selected.len();
         self.update_adaptive_threshold(accepted, total);
 
@@ -740,7 +1178,7 @@ impl SyncEngine {
 
     fn record_frame_metrics(
         &mut self,
-        t_ref: f64,
+        t_ref: SimTime,
         frames: &HashMap<SensorId, SensorPacket>,
         time_offsets: &HashMap<SensorId, f64>,
         quality_scores: &HashMap<SensorId, f64>,
@@ -751,15 +1189,15 @@ impl SyncEngine {
         metrics::histogram!("sync_completeness_ratio").record(completeness);
 
         if let Some(last_t) = self.last_sync_time {
-            let jitter = (t_ref - last_t).abs();
+            let jitter = (t_ref - last_t).abs().as_secs_f64();
             metrics::histogram!("sync_jitter").record(jitter);
         }
         self.last_sync_time = Some(t_ref);
 
         for (sensor_id, packet) in frames {
             let offset = time_offsets.get(sensor_id).copied().unwrap_or(0.0);
-            let t_target = t_ref + offset;
-            let error = (packet.timestamp - t_target).abs();
+            let t_target = t_ref + SimTime::from_secs_f64(offset);
+            let error = (packet.timestamp - t_target).abs().as_secs_f64();
             metrics::histogram!(
                 "sync_alignment_error",
                 "sensor_id" => sensor_id.to_string()
@@ -781,13 +1219,13 @@ impl SyncEngine {
 mod tests {
     use super::*;
     use bytes::Bytes;
-    use contracts::{ImageData, ImageFormat, PointCloudData, SensorType, Vector3};
+    use contracts::{ImageData, ImageFormat, LivenessConfig, PointCloudData, SensorType, Vector3};
 
     fn make_camera_packet(sensor_id: &str, timestamp: f64) -> SensorPacket {
         SensorPacket {
             sensor_id: sensor_id.into(),
             sensor_type: SensorType::Camera,
-            timestamp,
+            timestamp: SimTime::from_secs_f64(timestamp),
             frame_id: None,
             payload: SensorPayload::Image(ImageData {
                 width: 100,
@@ -802,7 +1240,7 @@ mod tests {
         SensorPacket {
             sensor_id: sensor_id.into(),
             sensor_type: SensorType::Lidar,
-            timestamp,
+            timestamp: SimTime::from_secs_f64(timestamp),
             frame_id: None,
             payload: SensorPayload::PointCloud(PointCloudData {
                 num_points: 1000,
@@ -816,7 +1254,7 @@ mod tests {
         SensorPacket {
             sensor_id: sensor_id.into(),
             sensor_type: SensorType::Imu,
-            timestamp,
+            timestamp: SimTime::from_secs_f64(timestamp),
             frame_id: None,
             payload: SensorPayload::Imu(ImuData {
                 accelerometer: Vector3 {
@@ -840,6 +1278,13 @@ mod tests {
             adakf: Default::default(),
             missing_strategy: MissingDataStrategy::Drop,
             sensor_intervals: HashMap::new(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
         }
     }
 
@@ -854,7 +1299,7 @@ mod tests {
 
         assert!(result.is_some());
         let frame = result.unwrap();
-        assert_eq!(frame.t_sync, 0.1);
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.1));
         assert_eq!(frame.frames.len(), 2);
     }
 
@@ -903,6 +1348,321 @@ mod tests {
         assert!(engine.motion_intensity() > 0.3);
     }
 
+    #[test]
+    fn test_poll_emits_partial_frame_after_budget() {
+        let mut config = default_config();
+        config.missing_strategy = MissingDataStrategy::Empty;
+        config.latency_budget_s = Some(0.1);
+        let mut engine = SyncEngine::new(config);
+
+        assert!(engine.push(make_camera_packet("cam", 0.1)).is_none());
+        assert!(engine.poll(SimTime::from_secs_f64(0.15)).is_none());
+
+        let frame = engine
+            .poll(SimTime::from_secs_f64(0.25))
+            .expect("deadline should force a frame");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.1));
+        assert_eq!(frame.frames.len(), 1);
+        assert_eq!(
+            frame.sync_meta.missing_sensors,
+            vec![SensorId::from("lidar")]
+        );
+        assert!(engine.poll(SimTime::from_secs_f64(0.3)).is_none());
+    }
+
+    #[test]
+    fn test_push_advances_deadline_with_stalled_sensor() {
+        let mut config = default_config();
+        config.missing_strategy = MissingDataStrategy::Empty;
+        config.latency_budget_s = Some(0.1);
+        let mut engine = SyncEngine::new(config);
+
+        assert!(engine.push(make_camera_packet("cam", 0.1)).is_none());
+        assert!(engine.push(make_camera_packet("cam", 0.15)).is_none());
+
+        let frame = engine
+            .push(make_camera_packet("cam", 0.2))
+            .expect("newest timestamp should expire the first reference");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.1));
+    }
+
+    fn liveness_config(drop_degraded: bool) -> SyncEngineConfig {
+        SyncEngineConfig {
+            sensor_intervals: HashMap::from([("cam".into(), 0.05), ("lidar".into(), 0.1)]),
+            liveness: Some(LivenessConfig {
+                missed_periods: 3,
+                drop_degraded,
+            }),
+            ..default_config()
+        }
+    }
+
+    #[test]
+    fn test_liveness_drops_and_readmits_silent_sensor() {
+        let mut engine = SyncEngine::new(liveness_config(true));
+        engine.push(make_lidar_packet("lidar", 0.0));
+        assert!(engine.push(make_camera_packet("cam", 0.0)).is_some());
+
+        // LiDAR stops; after 3 missed periods frames resume without it
+        let frames: Vec<SyncedFrame> = (1..=8)
+            .filter_map(|i| engine.push(make_camera_packet("cam", i as f64 * 0.05)))
+            .collect();
+        let frame = frames.last().expect("frames resume once lidar is degraded");
+        assert_eq!(
+            frame.sync_meta.degraded_sensors,
+            vec![SensorId::from("lidar")]
+        );
+        assert!(frame.sync_meta.missing_sensors.is_empty());
+        assert!(!frame.frames.contains_key("lidar"));
+
+        engine.push(make_lidar_packet("lidar", 0.45));
+        assert!(engine.degraded_sensors().is_empty());
+    }
+
+    #[test]
+    fn test_liveness_without_drop_only_reports() {
+        let mut engine = SyncEngine::new(liveness_config(false));
+        engine.push(make_lidar_packet("lidar", 0.0));
+        engine.push(make_camera_packet("cam", 0.0));
+
+        for i in 1..=8 {
+            assert!(engine
+                .push(make_camera_packet("cam", i as f64 * 0.05))
+                .is_none());
+        }
+        assert_eq!(engine.degraded_sensors(), vec![SensorId::from("lidar")]);
+    }
+
+    #[test]
+    fn test_jitter_policy_shrinks_window_without_imu() {
+        let run = |policy: WindowPolicy| {
+            let mut config = default_config();
+            config.imu_sensor_id = None;
+            config.window.policy = policy;
+            config.sensor_intervals = HashMap::from([("cam".into(), 0.1), ("lidar".into(), 0.1)]);
+            let mut engine = SyncEngine::new(config);
+
+            let mut last = None;
+            for i in 0..60 {
+                let t = i as f64 * 0.1;
+                let jitter = if i % 2 == 0 { 0.001 } else { -0.001 };
+                engine.push(make_lidar_packet("lidar", t + jitter));
+                last = engine.push(make_camera_packet("cam", t)).or(last);
+            }
+            last.expect("frames emitted").sync_meta.window_size
+        };
+
+        let motion = run(WindowPolicy::Motion);
+        let auto = run(WindowPolicy::Auto);
+        assert!(motion > 0.08, "motion window {}", motion);
+        assert!(auto < 0.03, "jitter window {}", auto);
+        assert!(auto >= 0.02, "window clamped to min_ms");
+    }
+
+    #[test]
+    fn test_push_without_budget_consumes_one_reference() {
+        let mut engine = SyncEngine::new(default_config());
+
+        engine.push(make_camera_packet("cam", 0.1));
+        engine.push(make_camera_packet("cam", 0.2));
+        engine.push(make_camera_packet("cam", 0.3));
+        // Lidar only fits 0.3: the push drops 0.1 and leaves 0.2 pending
+        assert!(engine.push(make_lidar_packet("lidar", 0.3)).is_none());
+        assert_eq!(engine.buffer_stats().buffer_depths[&SensorType::Camera], 2);
+    }
+
+    #[test]
+    fn test_poll_without_budget_is_event_driven() {
+        let mut engine = SyncEngine::new(default_config());
+
+        engine.push(make_camera_packet("cam", 0.1));
+        assert!(engine.poll(SimTime::from_secs_f64(10.0)).is_none());
+        assert_eq!(engine.frame_count(), 0);
+    }
+
+    #[test]
+    fn test_fixed_rate_grid_interpolates_imu() {
+        let mut config = default_config();
+        config.required_sensors = vec!["cam".into(), "imu".into()];
+        config.output_clock = OutputClock::FixedRate { rate_hz: 10.0 };
+        let mut engine = SyncEngine::new(config);
+
+        let imu_at = |timestamp, accel_x| {
+            let mut packet = make_imu_packet("imu", timestamp);
+            if let SensorPayload::Imu(ref mut imu) = packet.payload {
+                imu.accelerometer.x = accel_x;
+            }
+            packet
+        };
+
+        assert!(engine.push(imu_at(0.095, 0.0)).is_none());
+        assert!(engine.push(make_camera_packet("cam", 0.098)).is_none());
+        assert!(engine.push(imu_at(0.105, 1.0)).is_none());
+        let frame = engine
+            .push(make_camera_packet("cam", 0.148))
+            .expect("tick 0.1 should be complete");
+
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.1));
+        assert_eq!(frame.frames["cam"].timestamp, SimTime::from_secs_f64(0.098));
+        let imu = &frame.frames["imu"];
+        assert_eq!(imu.timestamp, SimTime::from_secs_f64(0.1));
+        assert!(imu.frame_id.is_none());
+        let SensorPayload::Imu(data) = &imu.payload else {
+            panic!("expected IMU payload");
+        };
+        assert!((data.accelerometer.x - 0.5).abs() < 1e-9);
+
+        engine.push(imu_at(0.195, 0.0));
+        engine.push(imu_at(0.205, 0.0));
+        engine.push(make_camera_packet("cam", 0.198));
+        let frame = engine
+            .push(make_camera_packet("cam", 0.248))
+            .expect("tick 0.2 should be complete");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.2));
+    }
+
+    #[test]
+    fn test_fixed_rate_grid_faster_than_sensors_keeps_up() {
+        let mut config = default_config();
+        config.required_sensors = vec!["cam".into(), "imu".into()];
+        config.missing_strategy = MissingDataStrategy::Empty;
+        config.output_clock = OutputClock::FixedRate { rate_hz: 20.0 };
+        let mut engine = SyncEngine::new(config);
+
+        let mut ticks = Vec::new();
+        for k in 1..=20 {
+            let t = k as f64 * 0.1;
+            engine.push(make_imu_packet("imu", t));
+            ticks.extend(engine.push(make_camera_packet("cam", t)));
+            ticks.extend(engine.drain_ready());
+        }
+
+        // Every 50 ms tick up to the newest packet, none left behind
+        let ticks: Vec<SimTime> = ticks.iter().map(|frame| frame.t_sync).collect();
+        let expected: Vec<SimTime> = (2..=40)
+            .map(|tick| SimTime::from_secs_f64(tick as f64 / 20.0))
+            .collect();
+        assert_eq!(ticks, expected);
+        assert!(engine.buffer_stats().total_packets <= 4);
+    }
+
+    #[test]
+    fn test_fixed_rate_grid_exact_after_long_run() {
+        let mut config = default_config();
+        config.required_sensors = vec!["cam".into(), "imu".into()];
+        config.output_clock = OutputClock::FixedRate { rate_hz: 30.0 };
+        let mut engine = SyncEngine::new(config);
+        let period_ns = 33_333_333;
+        let at = |mut packet: SensorPacket, tick: i64| {
+            packet.timestamp = SimTime::from_nanos(tick * period_ns);
+            packet
+        };
+
+        // Five hours in, packets land exactly on grid ticks
+        let start = 30 * 3600 * 5;
+        let mut ticks = Vec::new();
+        for tick in start..start + 10 {
+            engine.push(at(make_imu_packet("imu", 0.0), tick));
+            ticks.extend(engine.push(at(make_camera_packet("cam", 0.0), tick)));
+            ticks.extend(engine.drain_ready());
+        }
+
+        let ticks: Vec<SimTime> = ticks.iter().map(|frame| frame.t_sync).collect();
+        let expected: Vec<SimTime> = (start..start + 10)
+            .map(|tick| SimTime::from_nanos(tick * period_ns))
+            .collect();
+        assert_eq!(ticks, expected);
+    }
+
+    #[test]
+    fn test_fixed_rate_grid_honours_latency_budget() {
+        let mut config = default_config();
+        config.missing_strategy = MissingDataStrategy::Empty;
+        config.latency_budget_s = Some(0.05);
+        config.output_clock = OutputClock::FixedRate { rate_hz: 10.0 };
+        let mut engine = SyncEngine::new(config);
+
+        assert!(engine.push(make_camera_packet("cam", 0.098)).is_none());
+        assert!(engine.poll(SimTime::from_secs_f64(0.12)).is_none());
+
+        let frame = engine
+            .poll(SimTime::from_secs_f64(0.16))
+            .expect("deadline should force tick 0.1");
+        assert_eq!(frame.t_sync, SimTime::from_secs_f64(0.1));
+        assert_eq!(
+            frame.sync_meta.missing_sensors,
+            vec![SensorId::from("lidar")]
+        );
+        assert!(engine.poll(SimTime::from_secs_f64(0.2)).is_none());
+    }
+
+    #[test]
+    fn test_deskew_records_correction() {
+        let mut config = default_config();
+        config.deskew = Some(Default::default());
+        let mut engine = SyncEngine::new(config);
+
+        let mut imu_packet = make_imu_packet("imu", 0.08);
+        if let SensorPayload::Imu(ref mut imu) = imu_packet.payload {
+            imu.gyroscope.z = 0.5;
+        }
+        engine.push(imu_packet);
+        engine.push(make_camera_packet("cam", 0.1));
+        let frame = engine.push(make_lidar_packet("lidar", 0.1)).unwrap();
+
+        let correction = frame.sync_meta.deskew["lidar"];
+        assert_eq!(correction.angular_velocity.z, 0.5);
+        assert_eq!(correction.sweep_duration_s, DEFAULT_SENSOR_INTERVAL);
+        assert!(!frame.sync_meta.deskew.contains_key("cam"));
+    }
+
+    #[test]
+    fn test_checkpoint_restores_learned_offsets() {
+        let mut engine = SyncEngine::new(default_config());
+        for i in 0..20 {
+            let t = i as f64 * 0.1;
+            engine.push(make_lidar_packet("lidar", t + 0.01));
+            engine.push(make_camera_packet("cam", t));
+        }
+        let checkpoint = engine.checkpoint();
+        let learned = checkpoint.sensors["lidar"].offset;
+        assert!(learned > 0.0);
+
+        let mut warm = SyncEngine::new(default_config());
+        let summary = warm.restore(&checkpoint).unwrap();
+        assert_eq!(summary.restored, checkpoint.sensors.len());
+        assert_eq!(summary.ignored, 0);
+        assert_eq!(warm.checkpoint(), checkpoint);
+
+        let other = SyncEngineConfig {
+            reference_sensor_id: "lidar".into(),
+            ..default_config()
+        };
+        assert!(matches!(
+            SyncEngine::new(other).restore(&checkpoint),
+            Err(SyncEngineError::ReferenceMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_skips_unknown_sensors() {
+        let mut engine = SyncEngine::new(default_config());
+        engine.push(make_camera_packet("cam", 0.1));
+        engine.push(make_lidar_packet("lidar", 0.11));
+        let mut checkpoint = engine.checkpoint();
+        let snapshot = checkpoint.sensors["lidar"].clone();
+        checkpoint.sensors.insert("retired_radar".into(), snapshot);
+
+        let mut warm = SyncEngine::new(default_config());
+        let sensors_before = warm.sensors.len();
+        let summary = warm.restore(&checkpoint).unwrap();
+        assert_eq!(summary.ignored, 1);
+        assert_eq!(summary.restored, checkpoint.sensors.len() - 1);
+        assert_eq!(warm.sensors.len(), sensors_before);
+        assert!(warm.find_sensor("retired_radar").is_none());
+        assert!(!warm.checkpoint().sensors.contains_key("retired_radar"));
+    }
+
     #[test]
     fn test_frame_counter() {
         let config = default_config();
@@ -0,0 +1,33 @@
+//! Sync engine error types
+
+use thiserror::Error;
+
+/// Sync engine errors
+#[derive(Debug, Error)]
+pub enum SyncEngineError {
+    /// Checkpoint written by an incompatible version
+    #[error("unsupported checkpoint version {found} (expected {expected})")]
+    CheckpointVersion { found: u32, expected: u32 },
+
+    /// Checkpoint learned against a different reference sensor
+    #[error("checkpoint reference sensor '{found}' does not match '{expected}'")]
+    ReferenceMismatch { expected: String, found: String },
+
+    /// Checkpoint (de)serialization error
+    #[error("checkpoint format error: {0}")]
+    Format(#[from] serde_json::Error),
+
+    /// IO error
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl SyncEngineError {
+    /// Create a reference mismatch error
+    pub fn reference_mismatch(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Self::ReferenceMismatch {
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+}
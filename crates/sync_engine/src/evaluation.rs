@@ -0,0 +1,291 @@
+//! Sync accuracy evaluation against ground-truth capture times.
+//!
+//! Scores emitted frames using a [`GroundTruthLog`]: how far each selected
+//! packet's true capture time is from the reference packet's, how long the
+//! AdaKF offset estimates take to settle on the true clock offsets, and how
+//! many required slots were filled.
+
+use std::collections::HashMap;
+
+use contracts::{GroundTruthLog, SensorId, SimTime, SyncedFrame};
+
+/// Default tolerance for declaring an offset estimate converged (seconds)
+pub const DEFAULT_CONVERGENCE_TOLERANCE_S: f64 = 0.002;
+
+/// Accuracy figures for one sensor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SensorEvaluation {
+    /// Frames in which the sensor had a ground-truth match
+    pub samples: u64,
+    /// Mean absolute capture-time error vs. the reference (seconds)
+    pub mean_abs_error_s: f64,
+    /// Worst absolute capture-time error (seconds)
+    pub max_abs_error_s: f64,
+    /// RMS capture-time error (seconds)
+    pub rms_error_s: f64,
+    /// Time from the first frame until the offset estimate stayed within
+    /// tolerance (None = not converged)
+    pub convergence_time_s: Option<f64>,
+    /// Offset estimate error in the last frame (seconds)
+    pub final_offset_error_s: Option<f64>,
+}
+
+/// Evaluation summary over all observed frames
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvaluationReport {
+    /// Frames observed
+    pub frames: u64,
+    /// Frames with every required sensor present
+    pub complete_frames: u64,
+    /// Filled required slots over all required slots (0-1)
+    pub completeness: f64,
+    /// Packets without a ground-truth record
+    pub unmatched_packets: u64,
+    /// Per-sensor figures (reference sensors excluded)
+    pub sensors: HashMap<SensorId, SensorEvaluation>,
+}
+
+impl EvaluationReport {
+    /// Worst mean absolute error across sensors (seconds)
+    pub fn worst_mean_abs_error(&self) -> f64 {
+        self.sensors
+            .values()
+            .map(|sensor| sensor.mean_abs_error_s)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SensorAccumulator {
+    samples: u64,
+    abs_sum: f64,
+    sq_sum: f64,
+    max_abs: f64,
+    converged_since: Option<f64>,
+    final_offset_error: Option<f64>,
+}
+
+/// Accumulates accuracy statistics from synced frames
+#[derive(Debug)]
+pub struct SyncEvaluator {
+    log: GroundTruthLog,
+    tolerance_s: f64,
+    first_t_sync: Option<SimTime>,
+    frames: u64,
+    complete_frames: u64,
+    filled_slots: u64,
+    total_slots: u64,
+    unmatched_packets: u64,
+    sensors: HashMap<SensorId, SensorAccumulator>,
+}
+
+impl SyncEvaluator {
+    /// Create an evaluator reading capture times from `log`
+    pub fn new(log: GroundTruthLog) -> Self {
+        Self {
+            log,
+            tolerance_s: DEFAULT_CONVERGENCE_TOLERANCE_S,
+            first_t_sync: None,
+            frames: 0,
+            complete_frames: 0,
+            filled_slots: 0,
+            total_slots: 0,
+            unmatched_packets: 0,
+            sensors: HashMap::new(),
+        }
+    }
+
+    /// Override the convergence tolerance
+    pub fn with_tolerance(mut self, tolerance_s: f64) -> Self {
+        self.tolerance_s = tolerance_s.abs();
+        self
+    }
+
+    /// Score one synced frame
+    pub fn observe(&mut self, frame: &SyncedFrame) {
+        let first_t_sync = *self.first_t_sync.get_or_insert(frame.t_sync);
+        self.frames += 1;
+
+        let missing = frame.sync_meta.missing_sensors.len() as u64;
+        self.filled_slots += frame.frames.len() as u64;
+        self.total_slots += frame.frames.len() as u64 + missing;
+        if missing == 0 {
+            self.complete_frames += 1;
+        }
+
+        let reference_id = &frame.sync_meta.reference_sensor_id;
+        let Some(reference) = frame
+            .frames
+            .get(reference_id.as_str())
+            .and_then(|packet| self.log.lookup(reference_id, packet.frame_id?))
+        else {
+            self.unmatched_packets += frame.frames.len() as u64;
+            return;
+        };
+        let reference_clock = self.log.clock(reference_id);
+
+        for (sensor_id, packet) in &frame.frames {
+            if sensor_id.as_ref() == reference_id.as_str() {
+                continue;
+            }
+            let Some(capture) = packet
+                .frame_id
+                .and_then(|frame_id| self.log.lookup(sensor_id, frame_id))
+            else {
+                self.unmatched_packets += 1;
+                continue;
+            };
+
+            let error = capture.true_time - reference.true_time;
+            let acc = self.sensors.entry(sensor_id.clone()).or_default();
+            acc.samples += 1;
+            acc.abs_sum += error.abs();
+            acc.sq_sum += error * error;
+            acc.max_abs = acc.max_abs.max(error.abs());
+
+            if let Some(estimate) = frame.sync_meta.time_offsets.get(sensor_id) {
+                let true_offset = self.log.clock(sensor_id).offset_at(reference.true_time)
+                    - reference_clock.offset_at(reference.true_time);
+                let offset_error = estimate - true_offset;
+                acc.final_offset_error = Some(offset_error);
+                if offset_error.abs() > self.tolerance_s {
+                    acc.converged_since = None;
+                } else if acc.converged_since.is_none() {
+                    acc.converged_since = Some((frame.t_sync - first_t_sync).as_secs_f64());
+                }
+            }
+        }
+    }
+
+    /// Summarize everything observed so far
+    pub fn report(&self) -> EvaluationReport {
+        let sensors = self
+            .sensors
+            .iter()
+            .map(|(sensor_id, acc)| {
+                let n = acc.samples.max(1) as f64;
+                (
+                    sensor_id.clone(),
+                    SensorEvaluation {
+                        samples: acc.samples,
+                        mean_abs_error_s: acc.abs_sum / n,
+                        max_abs_error_s: acc.max_abs,
+                        rms_error_s: (acc.sq_sum / n).sqrt(),
+                        convergence_time_s: acc.converged_since,
+                        final_offset_error_s: acc.final_offset_error,
+                    },
+                )
+            })
+            .collect();
+
+        EvaluationReport {
+            frames: self.frames,
+            complete_frames: self.complete_frames,
+            completeness: if self.total_slots > 0 {
+                self.filled_slots as f64 / self.total_slots as f64
+            } else {
+                0.0
+            },
+            unmatched_packets: self.unmatched_packets,
+            sensors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{CaptureRecord, ClockModel, SensorPacket, SensorPayload, SensorType, SyncMeta};
+
+    fn packet(sensor_id: &str, frame_id: u64, timestamp: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: Some(frame_id),
+            payload: SensorPayload::Raw(Bytes::new()),
+        }
+    }
+
+    fn frame(t_sync: f64, lidar: Option<(u64, f64)>, lidar_offset: f64) -> SyncedFrame {
+        let mut frames = HashMap::from([(SensorId::from("cam"), packet("cam", 1, t_sync))]);
+        let mut meta = SyncMeta {
+            reference_sensor_id: "cam".into(),
+            ..Default::default()
+        };
+        match lidar {
+            Some((frame_id, timestamp)) => {
+                frames.insert("lidar".into(), packet("lidar", frame_id, timestamp));
+                meta.time_offsets.insert("lidar".into(), lidar_offset);
+            }
+            None => meta.missing_sensors.push("lidar".into()),
+        }
+        SyncedFrame {
+            t_sync: SimTime::from_secs_f64(t_sync),
+            frame_id: 1,
+            vehicle_id: None,
+            frames,
+            sync_meta: meta,
+        }
+    }
+
+    fn log() -> GroundTruthLog {
+        let log = GroundTruthLog::new();
+        log.register_clock(
+            "lidar",
+            ClockModel {
+                offset_s: 0.01,
+                ..Default::default()
+            },
+        );
+        log.record(
+            "cam",
+            1,
+            CaptureRecord {
+                true_time: 1.0,
+                reported_time: 1.0,
+            },
+        );
+        log.record(
+            "lidar",
+            1,
+            CaptureRecord {
+                true_time: 1.004,
+                reported_time: 1.014,
+            },
+        );
+        log
+    }
+
+    #[test]
+    fn test_alignment_error_and_completeness() {
+        let mut evaluator = SyncEvaluator::new(log());
+        evaluator.observe(&frame(1.0, Some((1, 1.014)), 0.0));
+        evaluator.observe(&frame(1.1, None, 0.0));
+        evaluator.observe(&frame(1.2, Some((99, 1.214)), 0.0));
+
+        let report = evaluator.report();
+        assert_eq!(report.frames, 3);
+        assert_eq!(report.complete_frames, 2);
+        assert!((report.completeness - 5.0 / 6.0).abs() < 1e-12);
+        assert_eq!(report.unmatched_packets, 1);
+
+        let lidar = &report.sensors["lidar"];
+        assert_eq!(lidar.samples, 1);
+        assert!((lidar.mean_abs_error_s - 0.004).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convergence_resets_on_excursion() {
+        let mut evaluator = SyncEvaluator::new(log()).with_tolerance(0.001);
+        evaluator.observe(&frame(1.0, Some((1, 1.014)), 0.0105));
+        evaluator.observe(&frame(1.1, Some((1, 1.014)), 0.0));
+        evaluator.observe(&frame(1.2, Some((1, 1.014)), 0.0098));
+        evaluator.observe(&frame(1.3, Some((1, 1.014)), 0.0101));
+
+        let lidar = &evaluator.report().sensors["lidar"];
+        assert!((lidar.convergence_time_s.unwrap() - 0.2).abs() < 1e-9);
+        assert!((lidar.final_offset_error_s.unwrap() - 0.0001).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,280 @@
+//! Multi-vehicle sync groups: packet routing and cross-vehicle frame joins.
+
+use std::collections::{HashMap, VecDeque};
+
+use contracts::{SensorId, SimTime, SyncedFrame};
+
+use crate::SyncEngineConfig;
+
+/// Maximum frames held per vehicle while waiting for the other groups
+const MAX_PENDING_FRAMES: usize = 64;
+
+/// Routes sensor packets to the sync group that owns the sensor
+#[derive(Debug, Clone)]
+pub struct GroupRouter {
+    routes: HashMap<SensorId, usize>,
+    fallback: Option<usize>,
+}
+
+impl GroupRouter {
+    /// Build routes from group configs and sensor ownership.
+    ///
+    /// Sensors named in a group config route to that group; remaining
+    /// sensors follow their parent vehicle. A single group receives every
+    /// packet, matching the ungrouped pipeline.
+    pub fn new(groups: &[SyncEngineConfig], sensor_to_vehicle: &HashMap<String, String>) -> Self {
+        let mut routes = HashMap::new();
+
+        for (idx, group) in groups.iter().enumerate() {
+            let named = std::iter::once(&group.reference_sensor_id)
+                .chain(group.required_sensors.iter())
+                .chain(group.imu_sensor_id.iter());
+            for sensor_id in named {
+                routes.entry(sensor_id.clone()).or_insert(idx);
+            }
+        }
+
+        for (sensor_id, vehicle_id) in sensor_to_vehicle {
+            let owner = groups
+                .iter()
+                .position(|group| group.vehicle_id.as_deref() == Some(vehicle_id.as_str()));
+            if let Some(idx) = owner {
+                routes
+                    .entry(SensorId::from(sensor_id.as_str()))
+                    .or_insert(idx);
+            }
+        }
+
+        Self {
+            routes,
+            fallback: (groups.len() == 1).then_some(0),
+        }
+    }
+
+    /// Group index for a sensor, if any group consumes it
+    pub fn route(&self, sensor_id: &str) -> Option<usize> {
+        self.routes.get(sensor_id).copied().or(self.fallback)
+    }
+}
+
+/// Joins per-vehicle frames whose `t_sync` lie within a tolerance into a
+/// single cross-vehicle frame.
+///
+/// The first vehicle leads: its `t_sync` and reference sensor are used for
+/// the joined frame. Frames that can no longer be matched are discarded.
+#[derive(Debug)]
+pub struct FrameJoiner {
+    vehicle_ids: Vec<String>,
+    tolerance_s: f64,
+    pending: Vec<VecDeque<SyncedFrame>>,
+    frame_counter: u64,
+    unmatched_count: u64,
+}
+
+impl FrameJoiner {
+    /// Create a joiner over the given vehicles (leader first)
+    pub fn new(vehicle_ids: Vec<String>, tolerance_s: f64) -> Self {
+        let pending = vehicle_ids.iter().map(|_| VecDeque::new()).collect();
+        Self {
+            vehicle_ids,
+            tolerance_s,
+            pending,
+            frame_counter: 0,
+            unmatched_count: 0,
+        }
+    }
+
+    /// Push a vehicle frame, returning a joined frame once every vehicle
+    /// has a frame within tolerance
+    pub fn push(&mut self, frame: SyncedFrame) -> Option<SyncedFrame> {
+        let Some(idx) = frame
+            .vehicle_id
+            .as_deref()
+            .and_then(|id| self.vehicle_ids.iter().position(|v| v == id))
+        else {
+            tracing::debug!(vehicle_id = ?frame.vehicle_id, "frame from unknown vehicle ignored");
+            return None;
+        };
+
+        let queue = &mut self.pending[idx];
+        queue.push_back(frame);
+        if queue.len() > MAX_PENDING_FRAMES {
+            queue.pop_front();
+            self.record_unmatched(idx, 1);
+        }
+
+        self.try_join()
+    }
+
+    /// Frames discarded without being joined
+    pub fn unmatched_count(&self) -> u64 {
+        self.unmatched_count
+    }
+
+    fn try_join(&mut self) -> Option<SyncedFrame> {
+        loop {
+            let newest_head = self
+                .pending
+                .iter()
+                .map(|queue| queue.front().map(|frame| frame.t_sync))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .max()?;
+
+            // Heads too old to pair with the newest head can never be joined
+            let cutoff = newest_head - SimTime::from_secs_f64(self.tolerance_s);
+            let mut pruned = false;
+            for idx in 0..self.pending.len() {
+                let mut stale = 0;
+                while self.pending[idx]
+                    .front()
+                    .is_some_and(|frame| frame.t_sync < cutoff)
+                {
+                    self.pending[idx].pop_front();
+                    stale += 1;
+                }
+                if stale > 0 {
+                    self.record_unmatched(idx, stale);
+                    pruned = true;
+                }
+            }
+
+            if !pruned {
+                let parts: Vec<SyncedFrame> = self
+                    .pending
+                    .iter_mut()
+                    .filter_map(|queue| queue.pop_front())
+                    .collect();
+                return Some(self.merge(parts));
+            }
+        }
+    }
+
+    fn record_unmatched(&mut self, idx: usize, count: u64) {
+        self.unmatched_count += count;
+        metrics::counter!(
+            "sync_cross_vehicle_unmatched_total",
+            "vehicle_id" => self.vehicle_ids[idx].clone()
+        )
+        .increment(count);
+    }
+
+    fn merge(&mut self, parts: Vec<SyncedFrame>) -> SyncedFrame {
+        let t_min = parts.iter().map(|frame| frame.t_sync).min();
+        let t_max = parts.iter().map(|frame| frame.t_sync).max();
+        let spread = t_max
+            .zip(t_min)
+            .map_or(0.0, |(hi, lo)| (hi - lo).as_secs_f64());
+        metrics::counter!("sync_cross_vehicle_frames_total").increment(1);
+        metrics::histogram!("sync_cross_vehicle_spread").record(spread);
+
+        let mut parts = parts.into_iter();
+        let leader = parts.next().expect("joiner has at least one vehicle");
+        let mut frames = leader.frames;
+        let mut sync_meta = leader.sync_meta;
+
+        for part in parts {
+            frames.extend(part.frames);
+            let meta = part.sync_meta;
+            sync_meta.window_size = sync_meta.window_size.max(meta.window_size);
+            sync_meta.time_offsets.extend(meta.time_offsets);
+            sync_meta.kf_residuals.extend(meta.kf_residuals);
+            sync_meta.missing_sensors.extend(meta.missing_sensors);
+            sync_meta.degraded_sensors.extend(meta.degraded_sensors);
+            sync_meta.dropped_count += meta.dropped_count;
+            sync_meta.out_of_order_count += meta.out_of_order_count;
+        }
+
+        self.frame_counter += 1;
+        SyncedFrame {
+            t_sync: leader.t_sync,
+            frame_id: self.frame_counter,
+            vehicle_id: None,
+            frames,
+            sync_meta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::{SensorPacket, SensorPayload, SensorType, SyncMeta};
+
+    fn vehicle_frame(vehicle_id: &str, sensor_id: &str, t_sync: f64) -> SyncedFrame {
+        let packet = SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(t_sync),
+            frame_id: None,
+            payload: SensorPayload::Raw(Bytes::new()),
+        };
+        SyncedFrame {
+            t_sync: SimTime::from_secs_f64(t_sync),
+            frame_id: 1,
+            vehicle_id: Some(vehicle_id.into()),
+            frames: HashMap::from([(SensorId::from(sensor_id), packet)]),
+            sync_meta: SyncMeta {
+                reference_sensor_id: sensor_id.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn group(vehicle_id: &str, sensors: &[&str]) -> SyncEngineConfig {
+        SyncEngineConfig {
+            reference_sensor_id: sensors[0].into(),
+            required_sensors: sensors.iter().map(|s| SensorId::from(*s)).collect(),
+            imu_sensor_id: None,
+            window: Default::default(),
+            buffer: Default::default(),
+            adakf: Default::default(),
+            missing_strategy: Default::default(),
+            sensor_intervals: Default::default(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: Some(vehicle_id.into()),
+            deskew: None,
+            liveness: None,
+        }
+    }
+
+    #[test]
+    fn test_router_follows_vehicle_ownership() {
+        let groups = vec![group("ego", &["ego_cam"]), group("peer", &["peer_cam"])];
+        let ownership = HashMap::from([
+            ("ego_imu".to_string(), "ego".to_string()),
+            ("peer_cam".to_string(), "peer".to_string()),
+        ]);
+        let router = GroupRouter::new(&groups, &ownership);
+
+        assert_eq!(router.route("ego_cam"), Some(0));
+        assert_eq!(router.route("ego_imu"), Some(0));
+        assert_eq!(router.route("peer_cam"), Some(1));
+        assert_eq!(router.route("roadside"), None);
+
+        let single = GroupRouter::new(&groups[..1], &HashMap::new());
+        assert_eq!(single.route("roadside"), Some(0));
+    }
+
+    #[test]
+    fn test_joiner_pairs_frames_within_tolerance() {
+        let mut joiner = FrameJoiner::new(vec!["ego".into(), "peer".into()], 0.02);
+
+        assert!(joiner.push(vehicle_frame("ego", "ego_cam", 0.10)).is_none());
+        assert!(joiner.push(vehicle_frame("ego", "ego_cam", 0.15)).is_none());
+
+        // Peer at 0.16 cannot pair with ego 0.10, which is discarded
+        let joined = joiner
+            .push(vehicle_frame("peer", "peer_cam", 0.16))
+            .expect("ego 0.15 and peer 0.16 should join");
+        assert_eq!(joined.t_sync, SimTime::from_secs_f64(0.15));
+        assert_eq!(joined.vehicle_id, None);
+        assert_eq!(joined.frames.len(), 2);
+        assert_eq!(joined.sync_meta.reference_sensor_id, "ego_cam");
+        assert_eq!(joiner.unmatched_count(), 1);
+    }
+}
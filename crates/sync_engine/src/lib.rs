@@ -4,9 +4,17 @@
 //!
 //! Responsibilities:
 //! - Event-driven sync triggering
-//! - IMU adaptive windowing
+//! - Deadline-driven emission when required sensors stall
+//! - Liveness tracking that degrades silent sensors out of the required set
+//! - Fixed-rate output grid with IMU/GNSS interpolation
+//! - IMU adaptive windowing, or jitter-percentile windowing without an IMU
 //! - KF/AdaKF time offset correction
+//! - Checkpointing of learned offsets for warm starts
+//! - Optional LiDAR motion compensation (deskew) to `t_sync`
 //! - Output `SyncedFrame`
+//! - Accuracy evaluation against ground-truth capture times
+//! - Offline batch synchronization of complete recordings
+//! - Per-vehicle sync groups with optional cross-vehicle frame joins
 //!
 //! ## Usage Example
 //!
@@ -29,15 +37,34 @@
 //! ```
 
 mod adakf;
+mod batch;
 mod buffer;
+mod checkpoint;
+mod deskew;
+mod driver;
 mod engine;
+mod error;
+mod evaluation;
+mod group;
+mod resample;
 mod window;
 
 // Re-exports
+pub use batch::{BatchOutput, BatchSynchronizer};
+pub use checkpoint::{
+    CheckpointFile, EngineCheckpoint, EstimatorSnapshot, RestoreSummary, CHECKPOINT_VERSION,
+};
 pub use contracts::{
-    AdaKFConfig, BufferConfig, MissingDataStrategy, SyncEngineConfig, WindowConfig,
+    AdaKFConfig, BufferConfig, LivenessConfig, MissingDataStrategy, OutputClock, SyncEngineConfig,
+    WindowConfig, WindowPolicy,
 };
+pub use driver::{DeadlineDriver, SimClock};
 pub use engine::SyncEngine;
+pub use error::SyncEngineError;
+pub use evaluation::{
+    EvaluationReport, SensorEvaluation, SyncEvaluator, DEFAULT_CONVERGENCE_TOLERANCE_S,
+};
+pub use group::{FrameJoiner, GroupRouter};
 
 // Re-export contracts types
 pub use contracts::{BufferStats, SensorPacket, SyncMeta, SyncedFrame};
@@ -0,0 +1,113 @@
+//! Packet interpolation for fixed-rate output.
+
+use std::f64::consts::TAU;
+
+use contracts::{GnssData, ImuData, SensorPacket, SensorPayload, SimTime, Vector3};
+
+/// Linearly interpolate a packet at time `t` between two bracketing packets.
+///
+/// Only low-dimensional payloads (IMU, GNSS) are interpolated; other
+/// payloads return `None` and are filled with the nearest packet instead.
+pub fn interpolate_packet(
+    before: &SensorPacket,
+    after: &SensorPacket,
+    t: SimTime,
+) -> Option<SensorPacket> {
+    let span = after.timestamp - before.timestamp;
+    if span <= SimTime::ZERO {
+        return None;
+    }
+    let alpha = ((t - before.timestamp).as_nanos() as f64 / span.as_nanos() as f64).clamp(0.0, 1.0);
+
+    let payload = match (&before.payload, &after.payload) {
+        (SensorPayload::Imu(a), SensorPayload::Imu(b)) => SensorPayload::Imu(ImuData {
+            accelerometer: lerp_vector(&a.accelerometer, &b.accelerometer, alpha),
+            gyroscope: lerp_vector(&a.gyroscope, &b.gyroscope, alpha),
+            compass: lerp_angle(a.compass, b.compass, alpha),
+        }),
+        (SensorPayload::Gnss(a), SensorPayload::Gnss(b)) => SensorPayload::Gnss(GnssData {
+            latitude: lerp(a.latitude, b.latitude, alpha),
+            longitude: lerp(a.longitude, b.longitude, alpha),
+            altitude: lerp(a.altitude, b.altitude, alpha),
+        }),
+        _ => return None,
+    };
+
+    Some(SensorPacket {
+        sensor_id: before.sensor_id.clone(),
+        sensor_type: before.sensor_type,
+        timestamp: t,
+        frame_id: None,
+        payload,
+    })
+}
+
+fn lerp(a: f64, b: f64, alpha: f64) -> f64 {
+    a + (b - a) * alpha
+}
+
+fn lerp_vector(a: &Vector3, b: &Vector3, alpha: f64) -> Vector3 {
+    Vector3 {
+        x: lerp(a.x, b.x, alpha),
+        y: lerp(a.y, b.y, alpha),
+        z: lerp(a.z, b.z, alpha),
+    }
+}
+
+/// Interpolate a heading in radians along the shortest arc
+fn lerp_angle(a: f64, b: f64, alpha: f64) -> f64 {
+    let delta = (b - a + TAU / 2.0).rem_euclid(TAU) - TAU / 2.0;
+    (a + delta * alpha).rem_euclid(TAU)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use contracts::SensorType;
+
+    fn imu_packet(timestamp: f64, accel_x: f64, compass: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: "imu".into(),
+            sensor_type: SensorType::Imu,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: Some(1),
+            payload: SensorPayload::Imu(ImuData {
+                accelerometer: Vector3 {
+                    x: accel_x,
+                    y: 0.0,
+                    z: 9.8,
+                },
+                gyroscope: Vector3::default(),
+                compass,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_interpolate_imu() {
+        let before = imu_packet(1.0, 0.0, TAU - 0.1);
+        let after = imu_packet(1.1, 1.0, 0.1);
+
+        let packet = interpolate_packet(&before, &after, SimTime::from_secs_f64(1.05)).unwrap();
+        assert_eq!(packet.timestamp, SimTime::from_secs_f64(1.05));
+        let SensorPayload::Imu(imu) = packet.payload else {
+            panic!("expected IMU payload");
+        };
+        assert!((imu.accelerometer.x - 0.5).abs() < 1e-9);
+        // Heading wraps through north instead of sweeping the long way round
+        assert!(imu.compass < 1e-9 || (TAU - imu.compass) < 1e-9);
+    }
+
+    #[test]
+    fn test_interpolate_rejects_raw_payloads() {
+        let raw = |timestamp| SensorPacket {
+            sensor_id: "cam".into(),
+            sensor_type: SensorType::Camera,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Raw(Bytes::new()),
+        };
+        assert!(interpolate_packet(&raw(0.0), &raw(0.1), SimTime::from_secs_f64(0.05)).is_none());
+    }
+}
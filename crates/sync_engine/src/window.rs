@@ -1,9 +1,16 @@
 //! IMU adaptive window calculation.
 
+use std::collections::VecDeque;
+
 use contracts::ImuData;
 
 use crate::WindowConfig;
 
+/// Timing error samples kept per statistic
+const JITTER_HISTORY: usize = 200;
+/// Samples needed before a percentile is trusted
+const MIN_JITTER_SAMPLES: usize = 10;
+
 /// Calculate motion intensity from IMU data
 ///
 /// Returns a value between 0.0 (stationary) and 1.0 (high motion)
@@ -47,6 +54,57 @@ pub fn compute_window_size(intensity: f64, config: &WindowConfig) -> f64 {
     window_ms / 1000.0 // Convert to seconds
 }
 
+/// Rolling distribution of absolute timing errors (seconds)
+#[derive(Debug, Clone, Default)]
+pub struct JitterStats {
+    samples: VecDeque<f64>,
+}
+
+impl JitterStats {
+    /// Record an absolute timing error
+    pub fn record(&mut self, error: f64) {
+        if !error.is_finite() {
+            return;
+        }
+        self.samples.push_back(error.abs());
+        if self.samples.len() > JITTER_HISTORY {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Record an inter-arrival interval as its deviation from the nearest
+    /// multiple of the expected period (so dropped packets are not jitter)
+    pub fn record_interval(&mut self, interval: f64, expected: f64) {
+        if interval <= 0.0 || expected <= 0.0 {
+            return;
+        }
+        let periods = (interval / expected).round().max(1.0);
+        self.record(interval - periods * expected);
+    }
+
+    /// Nearest-rank percentile (0-1), None until enough samples exist
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.samples.len() < MIN_JITTER_SAMPLES {
+            return None;
+        }
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        let rank = (p.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+/// Window covering a sensor's timing errors at the target percentile.
+///
+/// The packet search spans `±window/2`, so the half-width must absorb both
+/// timestamp jitter and the estimator's residual error.
+pub fn jitter_half_width(arrival: &JitterStats, residual: &JitterStats, p: f64) -> Option<f64> {
+    match (arrival.percentile(p), residual.percentile(p)) {
+        (None, None) => None,
+        (a, r) => Some(a.unwrap_or(0.0) + r.unwrap_or(0.0)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +188,29 @@ mod tests {
         // 0.2*0.7 + 0.8*0.3 = 0.38
         assert!((fused - 0.38).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_jitter_percentile_needs_samples() {
+        let mut stats = JitterStats::default();
+        for i in 0..MIN_JITTER_SAMPLES - 1 {
+            stats.record(i as f64 * 0.001);
+        }
+        assert!(stats.percentile(0.95).is_none());
+
+        for i in 0..=100 {
+            stats.record(i as f64 * 0.0001);
+        }
+        let p95 = stats.percentile(0.95).unwrap();
+        assert!(p95 > 0.009 && p95 <= 0.01, "p95 = {}", p95);
+    }
+
+    #[test]
+    fn test_interval_jitter_ignores_dropped_packets() {
+        let mut stats = JitterStats::default();
+        for _ in 0..MIN_JITTER_SAMPLES {
+            // Two periods elapsed, 1ms late
+            stats.record_interval(0.201, 0.1);
+        }
+        assert!((stats.percentile(0.5).unwrap() - 0.001).abs() < 1e-9);
+    }
 }
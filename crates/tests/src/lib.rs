@@ -22,7 +22,7 @@ mod e2e_tests {
     use std::sync::atomic::{AtomicU64, Ordering};
     use std::sync::Arc;
 
-    use contracts::{SinkConfig, SinkType, SyncedFrame};
+    use contracts::{SimTime, SinkConfig, SinkType, SyncedFrame};
     use dispatcher::create_dispatcher;
     use ingestion::MockSensorSource;
     use sync_engine::{MissingDataStrategy, SyncEngine, SyncEngineConfig};
@@ -50,6 +50,13 @@ mod e2e_tests {
             adakf: Default::default(),
             missing_strategy: MissingDataStrategy::Drop,
             sensor_intervals: HashMap::new(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
         };
         let mut sync_engine = SyncEngine::new(sync_config);
 
@@ -167,6 +174,13 @@ mod e2e_tests {
             adakf: Default::default(),
             missing_strategy: MissingDataStrategy::Drop,
             sensor_intervals: HashMap::new(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
         };
         let mut sync_engine = SyncEngine::new(sync_config);
 
@@ -266,8 +280,9 @@ mod e2e_tests {
         // Send frames
         for i in 0..5 {
             let frame = SyncedFrame {
-                t_sync: i as f64 * 0.1,
+                t_sync: SimTime::from_secs_f64(i as f64 * 0.1),
                 frame_id: i,
+                vehicle_id: None,
                 frames: HashMap::new(),
                 sync_meta: contracts::SyncMeta::default(),
             };
@@ -281,3 +296,373 @@ mod e2e_tests {
         let _ = tokio::time::timeout(std::time::Duration::from_secs(2), handle).await;
     }
 }
+
+#[cfg(test)]
+mod accuracy_tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+    use contracts::{
+        CaptureRecord, ClockModel, GroundTruthLog, SensorPacket, SensorPayload, SensorType, SimTime,
+    };
+    use sync_engine::{MissingDataStrategy, SyncEngine, SyncEngineConfig, SyncEvaluator};
+
+    /// Deterministic jitter sample in [-1, 1]
+    fn jitter_unit(sensor: u64, frame_id: u64) -> f64 {
+        let x = (frame_id.wrapping_mul(2654435761) ^ sensor.wrapping_mul(40503)) % 2001;
+        x as f64 / 1000.0 - 1.0
+    }
+
+    /// Simulated 10 Hz camera (reference, ideal clock) and LiDAR with
+    /// offset, drift and jitter, delivered to a sync engine in capture order
+    fn run_simulation(duration_s: f64) -> sync_engine::EvaluationReport {
+        let log = GroundTruthLog::new();
+        let lidar_clock = ClockModel {
+            offset_s: 0.004,
+            drift: 1e-4,
+            jitter_s: 0.001,
+            seed: 0,
+            ..Default::default()
+        };
+        log.register_clock("cam", ClockModel::default());
+        log.register_clock("lidar", lidar_clock);
+
+        let mut packets = Vec::new();
+        // LiDAR first so it is buffered before the simultaneous camera frame
+        let sensors = [
+            ("lidar", SensorType::Lidar, 0.1, lidar_clock),
+            ("cam", SensorType::Camera, 0.1, ClockModel::default()),
+        ];
+        for (sensor_idx, (sensor_id, sensor_type, period, clock)) in sensors.iter().enumerate() {
+            let count = (duration_s / period) as u64;
+            for frame_id in 1..=count {
+                let true_time = frame_id as f64 * period;
+                let reported_time =
+                    clock.reported_time(true_time, jitter_unit(sensor_idx as u64, frame_id));
+                log.record(
+                    *sensor_id,
+                    frame_id,
+                    CaptureRecord {
+                        true_time,
+                        reported_time,
+                    },
+                );
+                packets.push((
+                    true_time,
+                    SensorPacket {
+                        sensor_id: (*sensor_id).into(),
+                        sensor_type: *sensor_type,
+                        timestamp: SimTime::from_secs_f64(reported_time),
+                        frame_id: Some(frame_id),
+                        payload: SensorPayload::Raw(Bytes::new()),
+                    },
+                ));
+            }
+        }
+        packets.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut engine = SyncEngine::new(SyncEngineConfig {
+            reference_sensor_id: "cam".into(),
+            required_sensors: vec!["cam".into(), "lidar".into()],
+            imu_sensor_id: None,
+            window: Default::default(),
+            buffer: Default::default(),
+            adakf: Default::default(),
+            missing_strategy: MissingDataStrategy::Drop,
+            sensor_intervals: HashMap::from([("cam".into(), 0.1), ("lidar".into(), 0.1)]),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
+        });
+        let mut evaluator = SyncEvaluator::new(log);
+        for (_, packet) in packets {
+            if let Some(frame) = engine.push(packet) {
+                evaluator.observe(&frame);
+            }
+        }
+        evaluator.report()
+    }
+
+    /// Regression baseline for alignment accuracy under clock errors
+    #[test]
+    fn test_alignment_accuracy_baseline() {
+        let report = run_simulation(10.0);
+
+        assert_eq!(report.frames, 100);
+        assert_eq!(report.complete_frames, report.frames);
+        assert!((report.completeness - 1.0).abs() < 1e-12);
+        assert_eq!(report.unmatched_packets, 0);
+
+        // The 4ms offset is well inside the window, so the true partner
+        // packet must always be selected
+        let lidar = &report.sensors["lidar"];
+        assert_eq!(lidar.samples, 100);
+        assert!(lidar.max_abs_error_s < 1e-9, "{:?}", lidar);
+
+        // Baseline: the AdaKF estimate currently settles ~3ms from the
+        // true ~4.5ms offset; tighten this when the estimator improves
+        let offset_error = lidar.final_offset_error_s.expect("offset reported");
+        assert!(offset_error.abs() < 0.004, "{:?}", lidar);
+    }
+}
+
+#[cfg(test)]
+mod fault_tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use actor_factory::FaultInjectingSource;
+    use bytes::Bytes;
+    use contracts::{
+        FaultModel, SensorDataCallback, SensorPacket, SensorPayload, SensorSource, SensorType,
+        SimTime,
+    };
+    use ingestion::IngestionPipeline;
+    use sync_engine::{MissingDataStrategy, SyncEngine, SyncEngineConfig};
+
+    const PACKETS: u64 = 40;
+
+    /// 10 Hz IMU stream emitted synchronously from `listen`
+    struct SequenceSource {
+        listening: AtomicBool,
+    }
+
+    impl SensorSource for SequenceSource {
+        fn sensor_id(&self) -> &str {
+            "imu"
+        }
+
+        fn sensor_type(&self) -> SensorType {
+            SensorType::Imu
+        }
+
+        fn listen(&self, callback: SensorDataCallback) {
+            self.listening.store(true, Ordering::SeqCst);
+            for frame_id in 1..=PACKETS {
+                callback(SensorPacket {
+                    sensor_id: "imu".into(),
+                    sensor_type: SensorType::Imu,
+                    timestamp: SimTime::from_secs_f64(frame_id as f64 * 0.1),
+                    frame_id: Some(frame_id),
+                    payload: SensorPayload::Raw(Bytes::new()),
+                });
+            }
+        }
+
+        fn stop(&self) {
+            self.listening.store(false, Ordering::SeqCst);
+        }
+
+        fn is_listening(&self) -> bool {
+            self.listening.load(Ordering::SeqCst)
+        }
+    }
+
+    fn faulted_source(model: FaultModel) -> Box<dyn SensorSource> {
+        Box::new(FaultInjectingSource::new(
+            Box::new(SequenceSource {
+                listening: AtomicBool::new(false),
+            }),
+            model,
+        ))
+    }
+
+    /// Reordered and duplicated packets pass validation and reach the engine
+    #[tokio::test]
+    async fn test_faulted_packets_reach_sync_engine() {
+        let model = FaultModel {
+            reorder_prob: 0.3,
+            duplicate_prob: 0.3,
+            seed: 7,
+            ..Default::default()
+        };
+
+        // Reference: what the fault source emits on its own
+        let expected = Arc::new(Mutex::new(Vec::new()));
+        let sink = expected.clone();
+        faulted_source(model).listen(Arc::new(move |packet: SensorPacket| {
+            sink.lock().unwrap().push(packet.frame_id.unwrap());
+        }));
+        let expected = expected.lock().unwrap().clone();
+        assert!(expected.windows(2).any(|w| w[0] > w[1]), "no reordering");
+        assert!(expected.windows(2).any(|w| w[0] == w[1]), "no duplicates");
+
+        let mut ingestion = IngestionPipeline::new(2 * PACKETS as usize);
+        ingestion.register_sensor_source("imu".into(), faulted_source(model), None);
+        let mut receiver = ingestion.take_receiver().unwrap();
+        ingestion.start_all();
+
+        let mut engine = SyncEngine::new(SyncEngineConfig {
+            reference_sensor_id: "imu".into(),
+            required_sensors: vec!["imu".into()],
+            imu_sensor_id: None,
+            window: Default::default(),
+            buffer: Default::default(),
+            adakf: Default::default(),
+            missing_strategy: MissingDataStrategy::Empty,
+            sensor_intervals: Default::default(),
+            latency_budget_s: None,
+            deadline_clock: Default::default(),
+            quality: Default::default(),
+            output_clock: Default::default(),
+            vehicle_id: None,
+            deskew: None,
+            liveness: None,
+        });
+        let mut received = Vec::new();
+        let mut out_of_order = 0;
+        while let Some(packet) = receiver.try_recv() {
+            received.push(packet.frame_id.unwrap());
+            if let Some(frame) = engine.push(packet) {
+                out_of_order = out_of_order.max(frame.sync_meta.out_of_order_count);
+            }
+        }
+        ingestion.stop_all();
+
+        assert_eq!(received, expected);
+        assert_eq!(ingestion.metrics().snapshot().parse_errors, 0);
+        assert!(out_of_order > 0, "engine saw no out-of-order packets");
+        assert!(engine.frame_count() > 0);
+    }
+}
+
+#[cfg(test)]
+mod deskew_tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+    use config_loader::{ConfigFormat, ConfigLoader};
+    use contracts::{
+        GnssData, PointCloudData, SensorPacket, SensorPayload, SensorType, SimTime, SyncedFrame,
+    };
+    use sync_engine::{GroupRouter, SyncEngine};
+
+    const CONFIG: &str = r#"
+version = "V1"
+sinks = []
+
+[world]
+map = "Town01"
+
+[[vehicles]]
+id = "ego"
+blueprint = "vehicle.test"
+
+[[vehicles.sensors]]
+id = "ego_lidar"
+sensor_type = "lidar"
+frequency_hz = 10.0
+transform = { location = { x = 0.0, y = 0.0, z = 2.0 }, rotation = { pitch = 0.0, yaw = 0.0, roll = 0.0 } }
+
+[[vehicles.sensors]]
+id = "ego_gnss"
+sensor_type = "gnss"
+frequency_hz = 10.0
+transform = { location = { x = 0.0, y = 0.0, z = 2.0 }, rotation = { pitch = 0.0, yaw = 0.0, roll = 0.0 } }
+
+[[vehicles]]
+id = "peer"
+blueprint = "vehicle.test"
+
+[[vehicles.sensors]]
+id = "peer_lidar"
+sensor_type = "lidar"
+frequency_hz = 10.0
+transform = { location = { x = 0.0, y = 0.0, z = 2.0 }, rotation = { pitch = 0.0, yaw = 0.0, roll = 0.0 } }
+
+[[vehicles.sensors]]
+id = "peer_gnss"
+sensor_type = "gnss"
+frequency_hz = 10.0
+transform = { location = { x = 0.0, y = 0.0, z = 2.0 }, rotation = { pitch = 0.0, yaw = 0.0, roll = 0.0 } }
+
+[sync]
+primary_sensor_id = "ego_lidar"
+
+[[sync.groups]]
+vehicle_id = "ego"
+required_sensor_ids = ["ego_lidar"]
+
+[[sync.groups]]
+vehicle_id = "peer"
+required_sensor_ids = ["peer_lidar"]
+
+[sync.engine.deskew]
+gnss_sensor_id = "ego_gnss"
+"#;
+
+    /// Metres travelled north per degree of latitude
+    const METRES_PER_DEGREE: f64 = 6_378_137.0 * std::f64::consts::PI / 180.0;
+
+    fn gnss(sensor_id: &str, timestamp: f64, north_m: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type: SensorType::Gnss,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::Gnss(GnssData {
+                latitude: north_m / METRES_PER_DEGREE,
+                longitude: 0.0,
+                altitude: 0.0,
+            }),
+        }
+    }
+
+    fn sweep(sensor_id: &str, timestamp: f64) -> SensorPacket {
+        SensorPacket {
+            sensor_id: sensor_id.into(),
+            sensor_type: SensorType::Lidar,
+            timestamp: SimTime::from_secs_f64(timestamp),
+            frame_id: None,
+            payload: SensorPayload::PointCloud(PointCloudData {
+                num_points: 16,
+                point_stride: 16,
+                data: Bytes::from(vec![0u8; 256]),
+            }),
+        }
+    }
+
+    /// Each vehicle's LiDAR is deskewed with that vehicle's own GNSS speed
+    #[test]
+    fn test_group_deskew_uses_own_vehicle_speed() {
+        let blueprint = ConfigLoader::load_from_str(CONFIG, ConfigFormat::Toml).unwrap();
+        let configs = blueprint.to_sync_group_configs();
+        let sensor_to_vehicle: HashMap<String, String> = blueprint
+            .vehicles
+            .iter()
+            .flat_map(|vehicle| {
+                vehicle
+                    .sensors
+                    .iter()
+                    .map(|sensor| (sensor.id.clone(), vehicle.id.clone()))
+            })
+            .collect();
+        let router = GroupRouter::new(&configs, &sensor_to_vehicle);
+        let mut engines: Vec<SyncEngine> = configs.into_iter().map(SyncEngine::new).collect();
+
+        // Ego drives at 10 m/s, the peer at 25 m/s
+        let packets = [
+            gnss("ego_gnss", 0.0, 0.0),
+            gnss("peer_gnss", 0.0, 0.0),
+            gnss("ego_gnss", 1.0, 10.0),
+            gnss("peer_gnss", 1.0, 25.0),
+            sweep("ego_lidar", 1.0),
+            sweep("peer_lidar", 1.0),
+        ];
+        let mut frames: Vec<SyncedFrame> = Vec::new();
+        for packet in packets {
+            let group = router.route(&packet.sensor_id).expect("sensor has a group");
+            frames.extend(engines[group].push(packet));
+        }
+
+        assert_eq!(frames.len(), 2);
+        let speed =
+            |frame: &SyncedFrame, lidar: &str| frame.sync_meta.deskew[lidar].linear_velocity.x;
+        assert!((speed(&frames[0], "ego_lidar") - 10.0).abs() < 1e-6);
+        assert!((speed(&frames[1], "peer_lidar") - 25.0).abs() < 1e-6);
+    }
+}